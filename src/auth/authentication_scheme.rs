@@ -0,0 +1,121 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::parse_utils::is_token;
+use crate::{Error, StatusCode};
+
+/// HTTP authentication schemes.
+///
+/// [Read more](https://www.iana.org/assignments/http-authschemes/http-authschemes.xhtml)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AuthenticationScheme {
+    /// [RFC7617](https://tools.ietf.org/html/rfc7617) Basic auth
+    Basic,
+    /// [RFC6750](https://tools.ietf.org/html/rfc6750) Bearer auth
+    Bearer,
+    /// [RFC7616](https://tools.ietf.org/html/rfc7616) Digest auth
+    Digest,
+    /// [RFC7486](https://tools.ietf.org/html/rfc7486) HTTP Origin-Bound Authentication (HOBA)
+    Hoba,
+    /// [RFC8120](https://tools.ietf.org/html/rfc8120) Mutual auth
+    Mutual,
+    /// [RFC4559](https://tools.ietf.org/html/rfc4559) Negotiate auth
+    Negotiate,
+    /// [RFC5849](https://tools.ietf.org/html/rfc5849) OAuth
+    OAuth,
+    /// [RFC7804](https://tools.ietf.org/html/rfc7804) SCRAM SHA1 auth
+    ScramSha1,
+    /// [RFC7804](https://tools.ietf.org/html/rfc7804) SCRAM SHA256 auth
+    ScramSha256,
+    /// [RFC8292](https://tools.ietf.org/html/rfc8292) Vapid auth
+    Vapid,
+    /// An unregistered scheme, such as `NTLM` or `AWS4-HMAC-SHA256`, as it
+    /// was written.
+    Custom(String),
+}
+
+impl Display for AuthenticationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic => write!(f, "Basic"),
+            Self::Bearer => write!(f, "Bearer"),
+            Self::Digest => write!(f, "Digest"),
+            Self::Hoba => write!(f, "HOBA"),
+            Self::Mutual => write!(f, "Mutual"),
+            Self::Negotiate => write!(f, "Negotiate"),
+            Self::OAuth => write!(f, "OAuth"),
+            Self::ScramSha1 => write!(f, "SCRAM-SHA-1"),
+            Self::ScramSha256 => write!(f, "SCRAM-SHA-256"),
+            Self::Vapid => write!(f, "vapid"),
+            Self::Custom(scheme) => write!(f, "{}", scheme),
+        }
+    }
+}
+
+impl FromStr for AuthenticationScheme {
+    type Err = Error;
+
+    /// Parse a scheme. Schemes which aren't registered are returned as
+    /// `Custom`, as long as they're a valid `token`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // NOTE: auth schemes are case-insensitive.
+        match s.to_lowercase().as_str() {
+            "basic" => Ok(Self::Basic),
+            "bearer" => Ok(Self::Bearer),
+            "digest" => Ok(Self::Digest),
+            "hoba" => Ok(Self::Hoba),
+            "mutual" => Ok(Self::Mutual),
+            "negotiate" => Ok(Self::Negotiate),
+            "oauth" => Ok(Self::OAuth),
+            "scram-sha-1" => Ok(Self::ScramSha1),
+            "scram-sha-256" => Ok(Self::ScramSha256),
+            "vapid" => Ok(Self::Vapid),
+            _ if is_token(s) => Ok(Self::Custom(s.to_string())),
+            _ => Err(Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid authentication scheme", s),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn case_insensitive() -> crate::Result<()> {
+        assert_eq!(
+            "basic".parse::<AuthenticationScheme>()?,
+            AuthenticationScheme::Basic
+        );
+        assert_eq!(
+            "BEARER".parse::<AuthenticationScheme>()?,
+            AuthenticationScheme::Bearer
+        );
+        assert_eq!(
+            "Scram-Sha-256".parse::<AuthenticationScheme>()?,
+            AuthenticationScheme::ScramSha256
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn custom_scheme() -> crate::Result<()> {
+        let scheme: AuthenticationScheme = "AWS4-HMAC-SHA256".parse()?;
+        assert_eq!(
+            scheme,
+            AuthenticationScheme::Custom("AWS4-HMAC-SHA256".into())
+        );
+        assert_eq!(scheme.to_string(), "AWS4-HMAC-SHA256");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_scheme() {
+        for s in &["", "No pe", "a/b"] {
+            let err = s.parse::<AuthenticationScheme>().unwrap_err();
+            assert_eq!(err.status(), StatusCode::BadRequest, "{}", s);
+        }
+    }
+}
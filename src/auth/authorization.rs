@@ -0,0 +1,198 @@
+use crate::auth::AuthenticationScheme;
+use crate::headers::{HeaderName, HeaderValue, Headers, AUTHORIZATION, PROXY_AUTHORIZATION};
+use crate::parse_utils::trim_ows;
+use crate::{Error, StatusCode};
+
+use std::str::FromStr;
+
+/// Credentials to authenticate a user agent with a server.
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Authorization)
+///
+/// # Specifications
+///
+/// - [RFC 7235, section 4.2: Authorization](https://tools.ietf.org/html/rfc7235#section-4.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::auth::{AuthenticationScheme, Authorization};
+///
+/// let scheme = AuthenticationScheme::Basic;
+/// let credentials = "0xdeadbeef202020";
+/// let authz = Authorization::new(scheme, credentials.into());
+///
+/// let mut res = Response::new(200);
+/// authz.apply(&mut res);
+///
+/// let authz = Authorization::from_headers(res)?.unwrap();
+///
+/// assert_eq!(authz.scheme(), AuthenticationScheme::Basic);
+/// assert_eq!(authz.credentials(), credentials);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    scheme: AuthenticationScheme,
+    credentials: String,
+}
+
+impl Authorization {
+    /// Create a new instance of `Authorization`.
+    pub fn new(scheme: AuthenticationScheme, credentials: String) -> Self {
+        Self {
+            scheme,
+            credentials,
+        }
+    }
+
+    /// Create a new instance from the `Authorization` header.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::from_header_name(headers.as_ref(), AUTHORIZATION)
+    }
+
+    /// Create a new instance from the `Proxy-Authorization` header.
+    pub fn from_proxy_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::from_header_name(headers.as_ref(), PROXY_AUTHORIZATION)
+    }
+
+    fn from_header_name(headers: &Headers, name: HeaderName) -> crate::Result<Option<Self>> {
+        match headers.get(name) {
+            Some(values) => values.last().as_str().parse().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Sets the `Authorization` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Sets the `Proxy-Authorization` header.
+    pub fn apply_proxy(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(PROXY_AUTHORIZATION, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the credentials contain non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = format!("{} {}", self.scheme, self.credentials);
+        HeaderValue::from_str(&output).expect("Authorization credentials should be valid ASCII")
+    }
+
+    /// Get the authorization scheme.
+    pub fn scheme(&self) -> AuthenticationScheme {
+        self.scheme.clone()
+    }
+
+    /// Set the authorization scheme.
+    pub fn set_scheme(&mut self, scheme: AuthenticationScheme) {
+        self.scheme = scheme;
+    }
+
+    /// Get the authorization credentials.
+    pub fn credentials(&self) -> &str {
+        self.credentials.as_str()
+    }
+
+    /// Set the authorization credentials.
+    pub fn set_credentials(&mut self, credentials: String) {
+        self.credentials = credentials;
+    }
+}
+
+impl FromStr for Authorization {
+    type Err = Error;
+
+    /// Parse an `auth-scheme [ 1*SP ( token68 / #auth-param ) ]` pair.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = trim_ows(s);
+        let (scheme, credentials) = match s.find(' ') {
+            Some(index) => (&s[..index], trim_ows(&s[index + 1..])),
+            None => (s, ""),
+        };
+
+        if scheme.is_empty() {
+            return Err(Error::from_str(
+                StatusCode::BadRequest,
+                "Could not find an authentication scheme",
+            ));
+        }
+
+        Ok(Self {
+            scheme: scheme.parse()?,
+            credentials: credentials.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let scheme = AuthenticationScheme::Basic;
+        let credentials = "0xdeadbeef202020";
+        let authz = Authorization::new(scheme, credentials.into());
+
+        let mut headers = Headers::new();
+        authz.apply(&mut headers);
+
+        let authz = Authorization::from_headers(headers)?.unwrap();
+
+        assert_eq!(authz.scheme(), AuthenticationScheme::Basic);
+        assert_eq!(authz.credentials(), credentials);
+        Ok(())
+    }
+
+    #[test]
+    fn proxy() -> crate::Result<()> {
+        let authz = Authorization::new(AuthenticationScheme::Bearer, "abc".into());
+
+        let mut headers = Headers::new();
+        authz.apply_proxy(&mut headers);
+
+        assert_eq!(headers["proxy-authorization"], "Bearer abc");
+        assert_eq!(Authorization::from_headers(&headers)?, None);
+        assert_eq!(Authorization::from_proxy_headers(&headers)?, Some(authz));
+        Ok(())
+    }
+
+    #[test]
+    fn auth_params() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            "Authorization",
+            r#"digest username="Mufasa", realm="http-auth@example.org""#,
+        );
+        let authz = Authorization::from_headers(headers)?.unwrap();
+        assert_eq!(authz.scheme(), AuthenticationScheme::Digest);
+        assert_eq!(
+            authz.credentials(),
+            r#"username="Mufasa", realm="http-auth@example.org""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Authorization", "<nori ate the tag. yum.>");
+        let err = Authorization::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
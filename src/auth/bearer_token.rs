@@ -0,0 +1,138 @@
+use crate::auth::{AuthenticationScheme, Authorization};
+use crate::headers::{HeaderName, HeaderValue, Headers, AUTHORIZATION};
+use crate::{Error, StatusCode};
+
+/// HTTP Bearer token authorization.
+///
+/// # Specifications
+///
+/// - [RFC6750, section 2.1: Authorization Request Header Field](https://tools.ietf.org/html/rfc6750#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Method, Request, Url};
+/// use http_types::auth::BearerToken;
+///
+/// let token = BearerToken::new("mF_9.B5f-4.1JqM")?;
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// token.apply(&mut req);
+/// assert_eq!(req["Authorization"], "Bearer mF_9.B5f-4.1JqM");
+///
+/// let token = BearerToken::from_headers(req)?.unwrap();
+/// assert_eq!(token.token(), "mF_9.B5f-4.1JqM");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerToken {
+    token: String,
+}
+
+impl BearerToken {
+    /// Create a new instance of `BearerToken`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the token is not valid `token68` syntax.
+    pub fn new(token: impl Into<String>) -> crate::Result<Self> {
+        let token = token.into();
+        if !is_token68(&token) {
+            return Err(Error::from_str(
+                StatusCode::BadRequest,
+                "Bearer tokens should be valid token68 syntax",
+            ));
+        }
+        Ok(Self { token })
+    }
+
+    /// Create a new instance from the `Authorization` header.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let auth = match Authorization::from_headers(headers)? {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+
+        let scheme = auth.scheme();
+        if !matches!(scheme, AuthenticationScheme::Bearer) {
+            return Err(Error::from_str(
+                StatusCode::BadRequest,
+                format!("Expected bearer auth scheme found `{}`", scheme),
+            ));
+        }
+        Self::new(auth.credentials()).map(Some)
+    }
+
+    /// Sets the header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let auth = Authorization::new(AuthenticationScheme::Bearer, self.token.clone());
+        auth.value()
+    }
+
+    /// Get the token.
+    pub fn token(&self) -> &str {
+        self.token.as_str()
+    }
+}
+
+/// Validates `token68 = 1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" ) *"="`.
+pub(crate) fn is_token68(input: &str) -> bool {
+    let body = input.trim_end_matches('=');
+    !body.is_empty()
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~' | '+' | '/'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let token = BearerToken::new("mF_9.B5f-4.1JqM")?;
+
+        let mut headers = Headers::new();
+        token.apply(&mut headers);
+
+        let token = BearerToken::from_headers(headers)?.unwrap();
+        assert_eq!(token.token(), "mF_9.B5f-4.1JqM");
+        Ok(())
+    }
+
+    #[test]
+    fn token68() {
+        assert!(is_token68("abc"));
+        assert!(is_token68("YWJj=="));
+        assert!(!is_token68(""));
+        assert!(!is_token68("=="));
+        assert!(!is_token68("a=b"));
+        assert!(!is_token68("a b"));
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Authorization", "Bearer not a token");
+        let err = BearerToken::from_headers(&headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+
+        headers.insert("Authorization", "Basic YWJj");
+        let err = BearerToken::from_headers(&headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,389 @@
+use crate::auth::bearer_token::is_token68;
+use crate::auth::AuthenticationScheme;
+use crate::headers::{HeaderName, HeaderValue, Headers, PROXY_AUTHENTICATE, WWW_AUTHENTICATE};
+use crate::parse_utils::{
+    fmt_quoted_string, parse_token, parse_token_or_quoted_string, trim_ows_start,
+};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Define the authentication method that should be used to gain access to a
+/// resource.
+///
+/// A single `WWW-Authenticate` header may carry several challenges. Use
+/// `WwwAuthenticate::all_from_headers` to read every one of them.
+///
+/// # Specifications
+///
+/// - [RFC 7235, section 4.1: WWW-Authenticate](https://tools.ietf.org/html/rfc7235#section-4.1)
+/// - [RFC 7617, section 2.1: The 'charset' auth-param](https://tools.ietf.org/html/rfc7617#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::auth::{AuthenticationScheme, WwwAuthenticate};
+///
+/// let mut challenge = WwwAuthenticate::new(AuthenticationScheme::Basic, "Nori's catnip".into());
+/// challenge.set_charset("UTF-8".into());
+///
+/// let mut res = Response::new(401);
+/// challenge.apply(&mut res);
+/// assert_eq!(res["WWW-Authenticate"], r#"Basic realm="Nori's catnip", charset="UTF-8""#);
+///
+/// let challenge = WwwAuthenticate::from_headers(res)?.unwrap();
+/// assert_eq!(challenge.scheme(), AuthenticationScheme::Basic);
+/// assert_eq!(challenge.realm(), Some("Nori's catnip"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WwwAuthenticate {
+    scheme: AuthenticationScheme,
+    token68: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl WwwAuthenticate {
+    /// Create a new instance of `WwwAuthenticate`.
+    pub fn new(scheme: AuthenticationScheme, realm: String) -> Self {
+        let mut challenge = Self {
+            scheme,
+            token68: None,
+            params: vec![],
+        };
+        challenge.set_realm(realm);
+        challenge
+    }
+
+    /// Create a new instance from the first challenge in the `WWW-Authenticate` header.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let challenges = Self::all_from_header_name(headers.as_ref(), WWW_AUTHENTICATE)?;
+        Ok(challenges.into_iter().next())
+    }
+
+    /// Create a new instance from the first challenge in the `Proxy-Authenticate` header.
+    pub fn from_proxy_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let challenges = Self::all_from_header_name(headers.as_ref(), PROXY_AUTHENTICATE)?;
+        Ok(challenges.into_iter().next())
+    }
+
+    /// Parse every challenge in the `WWW-Authenticate` header.
+    pub fn all_from_headers(headers: impl AsRef<Headers>) -> crate::Result<Vec<Self>> {
+        Self::all_from_header_name(headers.as_ref(), WWW_AUTHENTICATE)
+    }
+
+    fn all_from_header_name(headers: &Headers, name: HeaderName) -> crate::Result<Vec<Self>> {
+        let mut challenges = vec![];
+        if let Some(values) = headers.get(name) {
+            for value in values {
+                challenges.append(&mut parse_challenges(value.as_str())?);
+            }
+        }
+        Ok(challenges)
+    }
+
+    /// Sets the `WWW-Authenticate` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Sets the `Proxy-Authenticate` header.
+    pub fn apply_proxy(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(PROXY_AUTHENTICATE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        WWW_AUTHENTICATE
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the parameters contain non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("WWW-Authenticate params should be valid ASCII")
+    }
+
+    /// Get the authorization scheme.
+    pub fn scheme(&self) -> AuthenticationScheme {
+        self.scheme.clone()
+    }
+
+    /// Set the authorization scheme.
+    pub fn set_scheme(&mut self, scheme: AuthenticationScheme) {
+        self.scheme = scheme;
+    }
+
+    /// Get the `realm` parameter.
+    pub fn realm(&self) -> Option<&str> {
+        self.param("realm")
+    }
+
+    /// Set the `realm` parameter.
+    pub fn set_realm(&mut self, realm: String) {
+        self.insert_param("realm", realm);
+    }
+
+    /// Get the `charset` parameter.
+    ///
+    /// For the `Basic` scheme the only allowed value is `UTF-8`.
+    pub fn charset(&self) -> Option<&str> {
+        self.param("charset")
+    }
+
+    /// Set the `charset` parameter.
+    pub fn set_charset(&mut self, charset: String) {
+        self.insert_param("charset", charset);
+    }
+
+    /// Get the `token68` value, for schemes which use it instead of parameters.
+    pub fn token68(&self) -> Option<&str> {
+        self.token68.as_deref()
+    }
+
+    /// Get an auth-param by name.
+    ///
+    /// Parameter names are case-insensitive.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Insert an auth-param, replacing any existing value with the same name.
+    pub fn insert_param(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into().to_ascii_lowercase();
+        let value = value.into();
+        match self.params.iter_mut().find(|(key, _)| *key == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name, value)),
+        }
+    }
+}
+
+impl Display for WwwAuthenticate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.scheme)?;
+        if let Some(token68) = &self.token68 {
+            return write!(f, " {}", token68);
+        }
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}={}", separator, name, fmt_quoted_string(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for WwwAuthenticate {
+    type Err = Error;
+
+    /// Parse a single challenge.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut challenges = parse_challenges(s)?;
+        match challenges.len() {
+            1 => Ok(challenges.remove(0)),
+            _ => Err(bad_request("Expected exactly one authentication challenge")),
+        }
+    }
+}
+
+fn bad_request(msg: &'static str) -> Error {
+    Error::from_str(StatusCode::BadRequest, msg)
+}
+
+/// Parse `1#challenge`, where `challenge = auth-scheme [ 1*SP ( token68 / #auth-param ) ]`.
+fn parse_challenges(mut input: &str) -> crate::Result<Vec<WwwAuthenticate>> {
+    let mut challenges = vec![];
+    loop {
+        input = input.trim_start_matches(&[',', ' ', '\t'][..]);
+        if input.is_empty() {
+            break;
+        }
+
+        let (scheme, rest) = parse_token(input);
+        let scheme = scheme.ok_or_else(|| bad_request("Expected an authentication scheme"))?;
+        let mut challenge = WwwAuthenticate {
+            scheme: scheme.parse()?,
+            token68: None,
+            params: vec![],
+        };
+
+        let after_ws = trim_ows_start(rest);
+        if after_ws.is_empty() || after_ws.starts_with(',') {
+            input = after_ws;
+            validate_challenge(&challenge)?;
+            challenges.push(challenge);
+            continue;
+        }
+        if after_ws.len() == rest.len() {
+            return Err(bad_request(
+                "Expected whitespace after the authentication scheme",
+            ));
+        }
+        input = after_ws;
+
+        // A token68 is followed by the end of the challenge, whereas an
+        // auth-param always contains a `=` followed by a value.
+        let end = input
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "-._~+/=".contains(c)))
+            .unwrap_or(input.len());
+        let rest = trim_ows_start(&input[end..]);
+        if is_token68(&input[..end]) && (rest.is_empty() || rest.starts_with(',')) {
+            challenge.token68 = Some(input[..end].to_owned());
+            input = rest;
+            validate_challenge(&challenge)?;
+            challenges.push(challenge);
+            continue;
+        }
+
+        loop {
+            input = input.trim_start_matches(&[',', ' ', '\t'][..]);
+            let (name, rest) = parse_token(input);
+            let name = match name {
+                Some(name) => name,
+                None if input.is_empty() => break,
+                None => return Err(bad_request("Expected an auth-param name")),
+            };
+
+            let rest = trim_ows_start(rest);
+            if !rest.starts_with('=') {
+                // This token is the scheme of the next challenge.
+                break;
+            }
+
+            let (value, rest) = parse_token_or_quoted_string(trim_ows_start(&rest[1..]));
+            let value = value.ok_or_else(|| bad_request("Expected an auth-param value"))?;
+            if challenge.param(name).is_none() {
+                challenge.insert_param(name, value);
+            }
+
+            input = trim_ows_start(rest);
+            if !input.is_empty() && !input.starts_with(',') {
+                return Err(bad_request("Expected a comma after the auth-param"));
+            }
+        }
+
+        validate_challenge(&challenge)?;
+        challenges.push(challenge);
+    }
+
+    if challenges.is_empty() {
+        return Err(bad_request(
+            "Expected at least one authentication challenge",
+        ));
+    }
+    Ok(challenges)
+}
+
+/// Validate the scheme specific requirements of RFC 7617 for the `Basic` scheme.
+fn validate_challenge(challenge: &WwwAuthenticate) -> crate::Result<()> {
+    if challenge.scheme != AuthenticationScheme::Basic {
+        return Ok(());
+    }
+    if challenge.realm().is_none() {
+        return Err(bad_request("The Basic scheme requires a realm"));
+    }
+    match challenge.charset() {
+        Some(charset) if !charset.eq_ignore_ascii_case("UTF-8") => Err(bad_request(
+            "The Basic scheme only allows a charset of UTF-8",
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let scheme = AuthenticationScheme::Basic;
+        let realm = "/some/resource";
+        let authz = WwwAuthenticate::new(scheme, realm.into());
+
+        let mut headers = Headers::new();
+        authz.apply(&mut headers);
+
+        let authz = WwwAuthenticate::from_headers(headers)?.unwrap();
+
+        assert_eq!(authz.scheme(), AuthenticationScheme::Basic);
+        assert_eq!(authz.realm(), Some(realm));
+        Ok(())
+    }
+
+    #[test]
+    fn quoted_params() -> crate::Result<()> {
+        let challenge: WwwAuthenticate =
+            r#"Basic realm="say \"hi\", please", charset=utf-8"#.parse()?;
+        assert_eq!(challenge.realm(), Some(r#"say "hi", please"#));
+        assert_eq!(challenge.charset(), Some("utf-8"));
+        assert_eq!(
+            challenge.to_string(),
+            r#"Basic realm="say \"hi\", please", charset="utf-8""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_challenges() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            "WWW-Authenticate",
+            r#"Bearer realm="apps", scope="a b", Basic realm="simple""#,
+        );
+        headers.append("WWW-Authenticate", "Negotiate, Negotiate YWJj==");
+        headers.append("WWW-Authenticate", "NTLM");
+
+        let challenges = WwwAuthenticate::all_from_headers(&headers)?;
+        assert_eq!(challenges.len(), 5);
+        assert_eq!(challenges[0].scheme(), AuthenticationScheme::Bearer);
+        assert_eq!(challenges[0].realm(), Some("apps"));
+        assert_eq!(challenges[0].param("Scope"), Some("a b"));
+        assert_eq!(challenges[1].scheme(), AuthenticationScheme::Basic);
+        assert_eq!(challenges[1].realm(), Some("simple"));
+        assert_eq!(challenges[2].scheme(), AuthenticationScheme::Negotiate);
+        assert_eq!(challenges[2].token68(), None);
+        assert_eq!(challenges[3].token68(), Some("YWJj=="));
+        assert_eq!(challenges[3].to_string(), "Negotiate YWJj==");
+        assert_eq!(
+            challenges[4].scheme(),
+            AuthenticationScheme::Custom("NTLM".into())
+        );
+
+        let first = WwwAuthenticate::from_headers(&headers)?.unwrap();
+        assert_eq!(first, challenges[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn basic_requirements() {
+        let err = "Basic".parse::<WwwAuthenticate>().unwrap_err();
+        assert_eq!(err.status(), 400);
+
+        let err = r#"Basic realm="x", charset="latin1""#.parse::<WwwAuthenticate>().unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("WWW-Authenticate", r#"Basic realm="unterminated"#);
+        let err = WwwAuthenticate::from_headers(&headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+
+        headers.insert("WWW-Authenticate", r#"Basic realm=x y"#);
+        let err = WwwAuthenticate::from_headers(&headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
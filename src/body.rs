@@ -1,13 +1,15 @@
+use async_compression::futures::bufread::{GzipDecoder, GzipEncoder, ZlibDecoder, ZlibEncoder};
 use async_std::io::prelude::*;
-use async_std::io::{self, Cursor};
+use async_std::io::{self, BufReader, Cursor};
 use serde::{de::DeserializeOwned, Serialize};
 
 use std::fmt::{self, Debug};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::content::ContentEncoding;
 use crate::{mime, Mime};
-use crate::{Status, StatusCode};
+use crate::{Error, Status, StatusCode};
 
 pin_project_lite::pin_project! {
     /// A streaming HTTP body.
@@ -414,6 +416,89 @@ impl Body {
     pub fn set_mime(&mut self, mime: impl Into<Mime>) {
         self.mime = mime.into();
     }
+
+    /// Compress the body with a content coding.
+    ///
+    /// The body is compressed as it is read, so the length of the resulting
+    /// body is unknown. The mime type is preserved. Compressing with
+    /// `identity` returns the body unchanged.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the content coding is not supported. Only
+    /// `gzip` and `deflate` are currently supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+    /// use http_types::content::ContentEncoding;
+    /// use http_types::Body;
+    ///
+    /// let body = Body::from_string("Hello Nori".to_string());
+    /// let body = body.compress(ContentEncoding::Gzip)?;
+    /// assert_eq!(body.len(), None);
+    ///
+    /// let body = body.decompress(ContentEncoding::Gzip)?;
+    /// assert_eq!(&body.into_string().await?, "Hello Nori");
+    /// # Ok(()) }) }
+    /// ```
+    pub fn compress(self, encoding: ContentEncoding) -> crate::Result<Self> {
+        let reader: Box<dyn BufRead + Unpin + Send + Sync + 'static> = match encoding {
+            ContentEncoding::Identity => return Ok(self),
+            ContentEncoding::Gzip => Box::new(BufReader::new(GzipEncoder::new(self.reader))),
+            ContentEncoding::Deflate => Box::new(BufReader::new(ZlibEncoder::new(self.reader))),
+            encoding => {
+                return Err(Error::from_str(
+                    StatusCode::InternalServerError,
+                    format!("Content coding `{}` is not supported", encoding),
+                ))
+            }
+        };
+        Ok(Self {
+            reader,
+            mime: self.mime,
+            length: None,
+        })
+    }
+
+    /// Decompress a body which was encoded with a content coding.
+    ///
+    /// The body is decompressed as it is read, so the length of the resulting
+    /// body is unknown. The mime type is preserved. Decompressing with
+    /// `identity` returns the body unchanged.
+    ///
+    /// # Errors
+    ///
+    /// An error with the status `415` is returned if the content coding is
+    /// not supported. Only `gzip` and `deflate` are currently supported.
+    pub fn decompress(self, encoding: ContentEncoding) -> crate::Result<Self> {
+        let reader: Box<dyn BufRead + Unpin + Send + Sync + 'static> = match encoding {
+            ContentEncoding::Identity => return Ok(self),
+            ContentEncoding::Gzip => Box::new(BufReader::new(GzipDecoder::new(self.reader))),
+            ContentEncoding::Deflate => Box::new(BufReader::new(ZlibDecoder::new(self.reader))),
+            encoding => {
+                return Err(Error::from_str(
+                    StatusCode::UnsupportedMediaType,
+                    format!("Content coding `{}` is not supported", encoding),
+                ))
+            }
+        };
+        Ok(Self {
+            reader,
+            mime: self.mime,
+            length: None,
+        })
+    }
+
+    /// Returns `true` if the body can be compressed and decompressed with the
+    /// content coding.
+    pub(crate) fn supports_encoding(encoding: &ContentEncoding) -> bool {
+        matches!(
+            encoding,
+            ContentEncoding::Identity | ContentEncoding::Gzip | ContentEncoding::Deflate
+        )
+    }
 }
 
 impl Debug for Body {
@@ -505,6 +590,34 @@ mod test {
     use super::*;
     use serde::Deserialize;
 
+    #[async_std::test]
+    async fn compression_roundtrip() -> crate::Result<()> {
+        for encoding in &[ContentEncoding::Gzip, ContentEncoding::Deflate] {
+            let mut body = Body::from_string("Hello Chashu".to_string());
+            body.set_mime(mime::PLAIN);
+
+            let body = body.compress(encoding.clone())?;
+            assert_eq!(body.len(), None);
+            assert_eq!(body.mime(), &mime::PLAIN);
+
+            let body = body.decompress(encoding.clone())?;
+            assert_eq!(body.mime(), &mime::PLAIN);
+            assert_eq!(&body.into_string().await?, "Hello Chashu");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_encoding() {
+        let err = Body::empty()
+            .decompress(ContentEncoding::Brotli)
+            .unwrap_err();
+        assert_eq!(err.status(), 415);
+
+        let err = Body::empty().compress(ContentEncoding::Zstd).unwrap_err();
+        assert_eq!(err.status(), 500);
+    }
+
     #[async_std::test]
     async fn json_status() {
         #[derive(Debug, Deserialize)]
@@ -0,0 +1,309 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::cache::CacheDirective;
+use crate::headers::{HeaderName, HeaderValue, Headers, CACHE_CONTROL, PRAGMA};
+use crate::parse_utils::split_list;
+use crate::Error;
+
+/// A list of directives for the `Cache-Control` header.
+///
+/// # Specifications
+///
+/// - [RFC7234, section 5.2: Cache-Control](https://tools.ietf.org/html/rfc7234#section-5.2)
+/// - [RFC8246: HTTP Immutable Responses](https://tools.ietf.org/html/rfc8246)
+/// - [RFC5861: HTTP Cache-Control Extensions for Stale Content](https://tools.ietf.org/html/rfc5861)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::cache::{CacheControl, CacheDirective};
+///
+/// let mut entries = CacheControl::new();
+/// entries.push(CacheDirective::Immutable);
+/// entries.push(CacheDirective::NoStore);
+///
+/// let mut res = Response::new(200);
+/// entries.apply(&mut res);
+///
+/// let entries = CacheControl::from_headers(res)?.unwrap();
+/// let mut entries = entries.iter();
+/// assert_eq!(entries.next().unwrap(), &CacheDirective::Immutable);
+/// assert_eq!(entries.next().unwrap(), &CacheDirective::NoStore);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    entries: Vec<CacheDirective>,
+}
+
+impl CacheControl {
+    /// Create a new instance of `CacheControl`.
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Cache-Control` header values are combined. When no `Cache-Control`
+    /// header is present, a `Pragma: no-cache` header is treated as
+    /// `Cache-Control: no-cache`, as required by [RFC7234, section
+    /// 5.4](https://tools.ietf.org/html/rfc7234#section-5.4).
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        let values = match headers.get(CACHE_CONTROL) {
+            Some(values) => values,
+            None => return Ok(Self::from_pragma(headers)),
+        };
+
+        let mut entries = Self::new();
+        for value in values {
+            for directive in split_list(value.as_str()) {
+                entries.push(directive.parse()?);
+            }
+        }
+        Ok(Some(entries))
+    }
+
+    fn from_pragma(headers: &Headers) -> Option<Self> {
+        let values = headers.get(PRAGMA)?;
+        let no_cache = values.iter().any(|value| {
+            split_list(value.as_str())
+                .into_iter()
+                .any(|directive| directive.eq_ignore_ascii_case("no-cache"))
+        });
+        if no_cache {
+            let mut entries = Self::new();
+            entries.push(CacheDirective::NoCache(vec![]));
+            Some(entries)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the `Cache-Control` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CACHE_CONTROL
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an extension directive contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Cache-Control directives should be valid ASCII")
+    }
+
+    /// Push a directive into the list of entries.
+    pub fn push(&mut self, directive: CacheDirective) {
+        self.entries.push(directive);
+    }
+
+    /// An iterator visiting all directives.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Get the `max-age` directive.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.entries.iter().find_map(|directive| match directive {
+            CacheDirective::MaxAge(dur) => Some(*dur),
+            _ => None,
+        })
+    }
+
+    /// Get the `s-maxage` directive.
+    pub fn s_max_age(&self) -> Option<Duration> {
+        self.entries.iter().find_map(|directive| match directive {
+            CacheDirective::SMaxAge(dur) => Some(*dur),
+            _ => None,
+        })
+    }
+
+    /// Get the `stale-while-revalidate` directive.
+    pub fn stale_while_revalidate(&self) -> Option<Duration> {
+        self.entries.iter().find_map(|directive| match directive {
+            CacheDirective::StaleWhileRevalidate(dur) => Some(*dur),
+            _ => None,
+        })
+    }
+
+    /// Get the `stale-if-error` directive.
+    pub fn stale_if_error(&self) -> Option<Duration> {
+        self.entries.iter().find_map(|directive| match directive {
+            CacheDirective::StaleIfError(dur) => Some(*dur),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if the `no-store` directive is present.
+    pub fn is_no_store(&self) -> bool {
+        self.entries.contains(&CacheDirective::NoStore)
+    }
+
+    /// Returns `true` if the `no-cache` directive is present.
+    pub fn is_no_cache(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|directive| matches!(directive, CacheDirective::NoCache(_)))
+    }
+
+    /// Returns `true` if the `immutable` directive is present.
+    pub fn is_immutable(&self) -> bool {
+        self.entries.contains(&CacheDirective::Immutable)
+    }
+}
+
+impl Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, directive) in self.entries.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", directive)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CacheControl {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = split_list(s)
+            .into_iter()
+            .map(|directive| directive.parse())
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { entries })
+    }
+}
+
+impl IntoIterator for CacheControl {
+    type Item = CacheDirective;
+    type IntoIter = std::vec::IntoIter<CacheDirective>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CacheControl {
+    type Item = &'a CacheDirective;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `CacheControl`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, CacheDirective>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a CacheDirective;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut entries = CacheControl::new();
+        entries.push(CacheDirective::Immutable);
+        entries.push(CacheDirective::NoStore);
+
+        let mut headers = Headers::new();
+        entries.apply(&mut headers);
+
+        let entries = CacheControl::from_headers(headers)?.unwrap();
+        let mut entries = entries.iter();
+        assert_eq!(entries.next().unwrap(), &CacheDirective::Immutable);
+        assert_eq!(entries.next().unwrap(), &CacheDirective::NoStore);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        let value = "public, max-age=31536000, s-maxage=600, immutable, \
+                     stale-while-revalidate=30, stale-if-error=86400, \
+                     private=\"set-cookie\", ext=\"a, b\", flag";
+        let entries: CacheControl = value.parse()?;
+        assert_eq!(entries.iter().count(), 9);
+        assert_eq!(entries.max_age(), Some(Duration::from_secs(31_536_000)));
+        assert_eq!(entries.s_max_age(), Some(Duration::from_secs(600)));
+        assert_eq!(
+            entries.stale_while_revalidate(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(entries.stale_if_error(), Some(Duration::from_secs(86400)));
+        assert!(entries.is_immutable());
+        assert_eq!(entries.value(), value.replace("                     ", ""));
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_headers() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append("Cache-Control", "no-cache");
+        headers.append("Cache-Control", "NO-STORE, max-age=0");
+        let entries = CacheControl::from_headers(headers)?.unwrap();
+        assert!(entries.is_no_cache());
+        assert!(entries.is_no_store());
+        assert_eq!(entries.max_age(), Some(Duration::from_secs(0)));
+        Ok(())
+    }
+
+    #[test]
+    fn pragma_fallback() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        assert_eq!(CacheControl::from_headers(&headers)?, None);
+
+        headers.insert("Pragma", "no-cache");
+        let entries = CacheControl::from_headers(&headers)?.unwrap();
+        assert!(entries.is_no_cache());
+
+        headers.insert("Cache-Control", "max-age=60");
+        let entries = CacheControl::from_headers(&headers)?.unwrap();
+        assert!(!entries.is_no_cache());
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Cache-Control", "max-age=soon");
+        let err = CacheControl::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
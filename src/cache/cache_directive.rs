@@ -0,0 +1,326 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::headers::HeaderName;
+use crate::parse_utils::{
+    self, fmt_quoted_string, fmt_token_or_quoted_string, is_token, parse_token,
+    parse_token_or_quoted_string, split_list, trim_ows,
+};
+use crate::{Error, StatusCode};
+
+/// Directives which must not have an argument.
+const NO_ARGUMENT: &[&str] = &[
+    "immutable",
+    "must-revalidate",
+    "no-store",
+    "no-transform",
+    "only-if-cached",
+    "proxy-revalidate",
+    "public",
+];
+
+/// Directives which must have an argument.
+const REQUIRES_ARGUMENT: &[&str] = &[
+    "max-age",
+    "min-fresh",
+    "s-maxage",
+    "stale-if-error",
+    "stale-while-revalidate",
+];
+
+/// A single directive of the `Cache-Control` header.
+///
+/// # Specifications
+///
+/// - [RFC7234, section 5.2: Cache-Control](https://tools.ietf.org/html/rfc7234#section-5.2)
+/// - [RFC8246: HTTP Immutable Responses](https://tools.ietf.org/html/rfc8246)
+/// - [RFC5861: HTTP Cache-Control Extensions for Stale Content](https://tools.ietf.org/html/rfc5861)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    /// The response body will not change over time.
+    Immutable,
+    /// The maximum amount of time a resource is considered fresh.
+    MaxAge(Duration),
+    /// Indicates the client will accept a stale response, optionally limited
+    /// to a maximum staleness.
+    MaxStale(Option<Duration>),
+    /// A response that will still be fresh for at least the specified duration.
+    MinFresh(Duration),
+    /// Once a response is stale, a fresh response must be retrieved.
+    MustRevalidate,
+    /// The response may be cached, but must always be revalidated before being used.
+    ///
+    /// When header names are listed, only those fields must not be sent
+    /// without revalidation.
+    NoCache(Vec<HeaderName>),
+    /// The response may not be cached.
+    NoStore,
+    /// An intermediate cache or proxy should not edit the response body,
+    /// Content-Encoding, Content-Range, or Content-Type.
+    NoTransform,
+    /// Do not use the network for a response.
+    OnlyIfCached,
+    /// The response may be stored only by a browser's cache, even if the
+    /// response is normally non-cacheable.
+    ///
+    /// When header names are listed, only those fields are private.
+    Private(Vec<HeaderName>),
+    /// Like must-revalidate, but only for shared caches (e.g., proxies).
+    ProxyRevalidate,
+    /// The response may be stored by any cache, even if the response is
+    /// normally non-cacheable.
+    Public,
+    /// Overrides max-age or the Expires header, but only for shared caches.
+    SMaxAge(Duration),
+    /// The client will accept a stale response if retrieving a fresh one fails.
+    StaleIfError(Duration),
+    /// The client will accept a stale response, while asynchronously
+    /// checking in the background for a fresh one.
+    StaleWhileRevalidate(Duration),
+    /// A directive not defined by the specifications, with an optional argument.
+    Extension(String, Option<String>),
+}
+
+impl CacheDirective {
+    /// Check whether this directive is valid in an HTTP request.
+    pub fn valid_in_req(&self) -> bool {
+        use CacheDirective::*;
+        matches!(
+            self,
+            MaxAge(_)
+                | MaxStale(_)
+                | MinFresh(_)
+                | NoCache(_)
+                | NoStore
+                | NoTransform
+                | OnlyIfCached
+                | StaleIfError(_)
+                | Extension(..)
+        )
+    }
+
+    /// Check whether this directive is valid in an HTTP response.
+    pub fn valid_in_res(&self) -> bool {
+        use CacheDirective::*;
+        matches!(
+            self,
+            Immutable
+                | MaxAge(_)
+                | MustRevalidate
+                | NoCache(_)
+                | NoStore
+                | NoTransform
+                | Private(_)
+                | ProxyRevalidate
+                | Public
+                | SMaxAge(_)
+                | StaleIfError(_)
+                | StaleWhileRevalidate(_)
+                | Extension(..)
+        )
+    }
+}
+
+impl Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CacheDirective::*;
+        match self {
+            Immutable => write!(f, "immutable"),
+            MaxAge(dur) => write!(f, "max-age={}", dur.as_secs()),
+            MaxStale(Some(dur)) => write!(f, "max-stale={}", dur.as_secs()),
+            MaxStale(None) => write!(f, "max-stale"),
+            MinFresh(dur) => write!(f, "min-fresh={}", dur.as_secs()),
+            MustRevalidate => write!(f, "must-revalidate"),
+            NoCache(names) => fmt_field_names(f, "no-cache", names),
+            NoStore => write!(f, "no-store"),
+            NoTransform => write!(f, "no-transform"),
+            OnlyIfCached => write!(f, "only-if-cached"),
+            Private(names) => fmt_field_names(f, "private", names),
+            ProxyRevalidate => write!(f, "proxy-revalidate"),
+            Public => write!(f, "public"),
+            SMaxAge(dur) => write!(f, "s-maxage={}", dur.as_secs()),
+            StaleIfError(dur) => write!(f, "stale-if-error={}", dur.as_secs()),
+            StaleWhileRevalidate(dur) => write!(f, "stale-while-revalidate={}", dur.as_secs()),
+            Extension(name, Some(value)) => {
+                write!(f, "{}={}", name, fmt_token_or_quoted_string(value))
+            }
+            Extension(name, None) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Field names are always sent as a quoted string, as recommended by the RFC.
+fn fmt_field_names(
+    f: &mut fmt::Formatter<'_>,
+    directive: &str,
+    names: &[HeaderName],
+) -> fmt::Result {
+    if names.is_empty() {
+        return write!(f, "{}", directive);
+    }
+    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    write!(f, "{}={}", directive, fmt_quoted_string(&names.join(", ")))
+}
+
+impl FromStr for CacheDirective {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = trim_ows(s);
+        let (name, rest) = match parse_token(s) {
+            (Some(name), rest) => (name.to_ascii_lowercase(), rest),
+            (None, _) => return Err(invalid(s)),
+        };
+
+        let value = match rest.strip_prefix('=') {
+            Some(rest) => match parse_token_or_quoted_string(rest) {
+                (Some(value), rest) if trim_ows(rest).is_empty() => Some(value.into_owned()),
+                _ => return Err(invalid(s)),
+            },
+            None if trim_ows(rest).is_empty() => None,
+            None => return Err(invalid(s)),
+        };
+
+        use CacheDirective::*;
+        let directive = match (name.as_str(), value) {
+            ("immutable", None) => Immutable,
+            ("max-age", Some(value)) => MaxAge(parse_delta_seconds(&value)?),
+            ("max-stale", Some(value)) => MaxStale(Some(parse_delta_seconds(&value)?)),
+            ("max-stale", None) => MaxStale(None),
+            ("min-fresh", Some(value)) => MinFresh(parse_delta_seconds(&value)?),
+            ("must-revalidate", None) => MustRevalidate,
+            ("no-cache", value) => NoCache(parse_field_names(value.as_deref())?),
+            ("no-store", None) => NoStore,
+            ("no-transform", None) => NoTransform,
+            ("only-if-cached", None) => OnlyIfCached,
+            ("private", value) => Private(parse_field_names(value.as_deref())?),
+            ("proxy-revalidate", None) => ProxyRevalidate,
+            ("public", None) => Public,
+            ("s-maxage", Some(value)) => SMaxAge(parse_delta_seconds(&value)?),
+            ("stale-if-error", Some(value)) => StaleIfError(parse_delta_seconds(&value)?),
+            ("stale-while-revalidate", Some(value)) => {
+                StaleWhileRevalidate(parse_delta_seconds(&value)?)
+            }
+            (name, Some(_)) if NO_ARGUMENT.contains(&name) => {
+                return Err(Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("The `{}` cache directive does not take an argument", name),
+                ))
+            }
+            (name, None) if REQUIRES_ARGUMENT.contains(&name) => {
+                return Err(Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("The `{}` cache directive requires an argument", name),
+                ))
+            }
+            (name, value) => Extension(name.to_string(), value),
+        };
+        Ok(directive)
+    }
+}
+
+fn invalid(s: &str) -> Error {
+    Error::from_str(
+        StatusCode::BadRequest,
+        format!("`{}` is not a valid cache directive", s),
+    )
+}
+
+/// Parse `delta-seconds`, saturating values too large to represent.
+fn parse_delta_seconds(s: &str) -> crate::Result<Duration> {
+    parse_utils::parse_delta_seconds(s).ok_or_else(|| {
+        Error::from_str(
+            StatusCode::BadRequest,
+            format!("`{}` is not a valid number of seconds", s),
+        )
+    })
+}
+
+fn parse_field_names(s: Option<&str>) -> crate::Result<Vec<HeaderName>> {
+    let s = match s {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+    split_list(s)
+        .into_iter()
+        .map(|name| {
+            if is_token(name) {
+                name.parse()
+            } else {
+                Err(Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("`{}` is not a valid header name", name),
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_utils::DELTA_SECONDS_MAX;
+
+    #[test]
+    fn parse_directives() -> crate::Result<()> {
+        assert_eq!(
+            "max-age=60".parse::<CacheDirective>()?,
+            CacheDirective::MaxAge(Duration::from_secs(60))
+        );
+        assert_eq!(
+            "Max-Age=\"60\"".parse::<CacheDirective>()?,
+            CacheDirective::MaxAge(Duration::from_secs(60))
+        );
+        assert_eq!(
+            "max-stale".parse::<CacheDirective>()?,
+            CacheDirective::MaxStale(None)
+        );
+        assert_eq!(
+            "immutable".parse::<CacheDirective>()?,
+            CacheDirective::Immutable
+        );
+        assert_eq!(
+            "no-cache=\"Set-Cookie, X-Foo\"".parse::<CacheDirective>()?,
+            CacheDirective::NoCache(vec!["set-cookie".into(), "x-foo".into()])
+        );
+        assert_eq!(
+            "community=\"UCI\"".parse::<CacheDirective>()?,
+            CacheDirective::Extension("community".into(), Some("UCI".into()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn large_delta_seconds_saturate() -> crate::Result<()> {
+        assert_eq!(
+            "max-age=99999999999999999999999".parse::<CacheDirective>()?,
+            CacheDirective::MaxAge(Duration::from_secs(DELTA_SECONDS_MAX))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "max-age",
+            "max-age=-1",
+            "max-age=1.5",
+            "no-store=1",
+            "private=\"not a header\"",
+            "=60",
+            "max-age=60 extra",
+        ] {
+            let err = s.parse::<CacheDirective>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let directive = CacheDirective::Private(vec!["set-cookie".into()]);
+        assert_eq!(directive.to_string(), "private=\"set-cookie\"");
+        let directive = CacheDirective::Extension("ext".into(), Some("a b".into()));
+        assert_eq!(directive.to_string(), "ext=\"a b\"");
+    }
+}
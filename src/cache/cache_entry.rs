@@ -0,0 +1,341 @@
+use std::time::{Duration, SystemTime};
+
+use crate::cache::{CacheControl, CacheDirective, Vary};
+use crate::headers::{
+    HeaderName, Headers, AGE, CONTENT_LENGTH, CONTENT_TYPE, DATE, EXPIRES, LAST_MODIFIED,
+};
+use crate::parse_utils::parse_delta_seconds;
+use crate::{Body, HttpDate, Response, StatusCode, Version};
+
+/// Status codes which are cacheable by default, and may be given a heuristic
+/// freshness lifetime.
+///
+/// [RFC7231, section 6.1](https://tools.ietf.org/html/rfc7231#section-6.1)
+const CACHEABLE_BY_DEFAULT: &[u16] = &[200, 203, 204, 300, 301, 404, 405, 410, 414, 501];
+
+/// A response stored in a cache, along with the request headers it was
+/// selected on.
+///
+/// # Specifications
+///
+/// - [RFC7234, section 4.2: Freshness](https://tools.ietf.org/html/rfc7234#section-4.2)
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    status: StatusCode,
+    version: Option<Version>,
+    headers: Headers,
+    body: Vec<u8>,
+    request_headers: Headers,
+    request_time: SystemTime,
+    response_time: SystemTime,
+}
+
+impl CacheEntry {
+    /// Create a new entry from a response and the request it answers.
+    ///
+    /// `request_time` is when the request was sent, and `response_time` when
+    /// the response was received.
+    pub(crate) fn new(
+        request_headers: Headers,
+        res: &Response,
+        body: Vec<u8>,
+        request_time: SystemTime,
+        response_time: SystemTime,
+    ) -> Self {
+        Self {
+            status: res.status(),
+            version: res.version(),
+            headers: res.as_ref().clone(),
+            body,
+            request_headers,
+            request_time,
+            response_time,
+        }
+    }
+
+    /// Get the status of the stored response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Get the headers of the stored response.
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Get the body of the stored response.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Get the headers of the request the response was stored for.
+    pub fn request_headers(&self) -> &Headers {
+        &self.request_headers
+    }
+
+    /// Get the time the request was sent.
+    pub fn request_time(&self) -> SystemTime {
+        self.request_time
+    }
+
+    /// Get the time the response was received.
+    pub fn response_time(&self) -> SystemTime {
+        self.response_time
+    }
+
+    /// Get the `Vary` header of the stored response.
+    pub fn vary(&self) -> Option<Vary> {
+        Vary::from_headers(&self.headers).ok().flatten()
+    }
+
+    /// Get the `Cache-Control` header of the stored response.
+    pub fn cache_control(&self) -> Option<CacheControl> {
+        CacheControl::from_headers(&self.headers).ok().flatten()
+    }
+
+    /// Returns `true` if the stored response can be used to satisfy a request
+    /// with these headers.
+    pub fn matches(&self, headers: impl AsRef<Headers>) -> bool {
+        match Vary::from_headers(&self.headers) {
+            Ok(Some(vary)) => vary.matches(&self.request_headers, headers),
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Calculate how long the response is fresh for after it was generated.
+    ///
+    /// The lifetime is taken from `Cache-Control: max-age`, then `Expires`,
+    /// and otherwise estimated from `Last-Modified` for responses cacheable
+    /// by default.
+    pub fn freshness_lifetime(&self) -> Duration {
+        if let Some(max_age) = self.cache_control().and_then(|cc| cc.max_age()) {
+            return max_age;
+        }
+
+        let date = self.date();
+        if let Some(expires) = self.headers.get(EXPIRES) {
+            return match expires.last().as_str().parse::<HttpDate>() {
+                Ok(expires) => duration_between(date, expires.into()),
+                Err(_) => Duration::from_secs(0),
+            };
+        }
+
+        match self.header_date(LAST_MODIFIED) {
+            Some(last_modified) if is_cacheable_by_default(self.status) => {
+                duration_between(last_modified, date) / 10
+            }
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    /// Calculate the age of the response at a point in time.
+    ///
+    /// `Age` values too large to represent are capped at 2^31 seconds.
+    pub fn current_age(&self, now: SystemTime) -> Duration {
+        let age_value = self
+            .headers
+            .get(AGE)
+            .and_then(|age| parse_delta_seconds(age.last().as_str()))
+            .unwrap_or_default();
+
+        let apparent_age = duration_between(self.date(), self.response_time);
+        let response_delay = duration_between(self.request_time, self.response_time);
+        let corrected_initial_age = apparent_age.max(age_value.saturating_add(response_delay));
+        let resident_time = duration_between(self.response_time, now);
+        corrected_initial_age.saturating_add(resident_time)
+    }
+
+    /// Returns `true` if the response is fresh at a point in time.
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        self.freshness_lifetime() > self.current_age(now)
+    }
+
+    /// Returns `true` if the response can be used without revalidation, given
+    /// the `Cache-Control` directives of a request.
+    pub(crate) fn is_usable(&self, directives: Option<&CacheControl>, now: SystemTime) -> bool {
+        let response_directives = self.cache_control();
+        if let Some(cc) = &response_directives {
+            if cc.is_no_cache() {
+                return false;
+            }
+        }
+
+        let age = self.current_age(now);
+        let lifetime = self.freshness_lifetime();
+        let directives = match directives {
+            Some(directives) => directives,
+            None => return lifetime > age,
+        };
+        if directives.is_no_cache() {
+            return false;
+        }
+        if let Some(max_age) = directives.max_age() {
+            if age > max_age {
+                return false;
+            }
+        }
+
+        let min_fresh = directives.iter().find_map(|directive| match directive {
+            CacheDirective::MinFresh(dur) => Some(*dur),
+            _ => None,
+        });
+        if lifetime > age.saturating_add(min_fresh.unwrap_or_default()) {
+            return true;
+        }
+
+        let must_revalidate = response_directives
+            .map(|cc| cc.iter().any(|d| d == &CacheDirective::MustRevalidate))
+            .unwrap_or(false);
+        let max_stale = directives.iter().find_map(|directive| match directive {
+            CacheDirective::MaxStale(dur) => Some(*dur),
+            _ => None,
+        });
+        match max_stale {
+            _ if must_revalidate => false,
+            Some(None) => true,
+            Some(Some(max_stale)) => age.checked_sub(lifetime).unwrap_or_default() <= max_stale,
+            None => false,
+        }
+    }
+
+    /// Update the stored response with the headers of a `304 Not Modified`
+    /// response.
+    pub(crate) fn update(
+        &mut self,
+        res: &Response,
+        request_time: SystemTime,
+        response_time: SystemTime,
+    ) {
+        for (name, values) in res.iter() {
+            if name != &CONTENT_LENGTH {
+                self.headers.insert(name, values);
+            }
+        }
+        self.request_time = request_time;
+        self.response_time = response_time;
+    }
+
+    /// Create a response from the entry, with the `Age` header set.
+    pub(crate) fn to_response(&self, now: SystemTime) -> Response {
+        let mut res = Response::new(self.status);
+        res.set_version(self.version);
+        for (name, values) in self.headers.iter() {
+            res.insert_header(name, values);
+        }
+        res.set_body(Body::from(self.body.clone()));
+        if self.headers.get(CONTENT_TYPE).is_none() {
+            res.remove_header(CONTENT_TYPE);
+        }
+        res.insert_header(AGE, self.current_age(now).as_secs().to_string());
+        res
+    }
+
+    fn date(&self) -> SystemTime {
+        self.header_date(DATE).unwrap_or(self.response_time)
+    }
+
+    fn header_date(&self, name: HeaderName) -> Option<SystemTime> {
+        let date: HttpDate = self.headers.get(name)?.last().as_str().parse().ok()?;
+        Some(date.into())
+    }
+}
+
+/// Returns `true` if responses with the status code may be stored without
+/// explicit freshness information.
+pub(crate) fn is_cacheable_by_default(status: StatusCode) -> bool {
+    CACHEABLE_BY_DEFAULT.contains(&(status as u16))
+}
+
+/// The time elapsed between two points in time, or zero if `later` is
+/// earlier.
+fn duration_between(earlier: SystemTime, later: SystemTime) -> Duration {
+    later.duration_since(earlier).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(headers: &[(&str, &str)], now: SystemTime) -> CacheEntry {
+        let mut res = Response::new(StatusCode::Ok);
+        for (name, value) in headers {
+            res.insert_header(*name, *value);
+        }
+        CacheEntry::new(Headers::new(), &res, vec![], now, now)
+    }
+
+    fn date(time: SystemTime) -> String {
+        HttpDate::from(time).to_string()
+    }
+
+    #[test]
+    fn freshness_lifetime() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+
+        let entry1 = entry(&[("Cache-Control", "max-age=60"), ("Expires", "0")], now);
+        assert_eq!(entry1.freshness_lifetime(), Duration::from_secs(60));
+
+        let expires = date(now + hour);
+        let entry2 = entry(&[("Date", &date(now)), ("Expires", &expires)], now);
+        assert_eq!(entry2.freshness_lifetime(), hour);
+
+        let entry3 = entry(&[("Expires", "0")], now);
+        assert_eq!(entry3.freshness_lifetime(), Duration::from_secs(0));
+
+        let last_modified = date(now - hour * 10);
+        let entry4 = entry(&[("Last-Modified", &last_modified)], now);
+        assert_eq!(entry4.freshness_lifetime().as_secs() / 60, 60);
+        assert!(entry4.is_fresh(now));
+
+        let entry5 = entry(&[], now);
+        assert_eq!(entry5.freshness_lifetime(), Duration::from_secs(0));
+        assert!(!entry5.is_fresh(now));
+    }
+
+    #[test]
+    fn current_age() {
+        let now = SystemTime::now();
+        let minute = Duration::from_secs(60);
+
+        let entry1 = entry(&[("Age", "30")], now);
+        assert_eq!(entry1.current_age(now), Duration::from_secs(30));
+        assert_eq!(entry1.current_age(now + minute), Duration::from_secs(90));
+
+        let date = date(now - minute * 2);
+        let entry2 = entry(&[("Age", "30"), ("Date", &date)], now);
+        assert_eq!(entry2.current_age(now).as_secs() / 60, 2);
+
+        let age = u64::MAX.to_string();
+        let entry3 = entry(&[("Age", &age), ("Cache-Control", "max-age=60")], now);
+        let delta_seconds_max = Duration::from_secs(2_147_483_648);
+        assert_eq!(entry3.current_age(now), delta_seconds_max);
+        assert_eq!(entry3.current_age(now + minute), delta_seconds_max + minute);
+        assert!(!entry3.is_fresh(now));
+        assert!(!entry3.is_usable(Some(&"min-fresh=60".parse().unwrap()), now));
+    }
+
+    #[test]
+    fn usable() -> crate::Result<()> {
+        let now = SystemTime::now();
+        let minute = Duration::from_secs(60);
+        let entry1 = entry(&[("Cache-Control", "max-age=120")], now);
+
+        assert!(entry1.is_usable(None, now + minute));
+        assert!(!entry1.is_usable(Some(&"no-cache".parse()?), now));
+        assert!(!entry1.is_usable(Some(&"max-age=30".parse()?), now + minute));
+        assert!(!entry1.is_usable(Some(&"min-fresh=90".parse()?), now + minute));
+        assert!(!entry1.is_usable(None, now + minute * 3));
+        assert!(entry1.is_usable(Some(&"max-stale".parse()?), now + minute * 3));
+        assert!(entry1.is_usable(Some(&"max-stale=90".parse()?), now + minute * 3));
+        assert!(!entry1.is_usable(Some(&"max-stale=30".parse()?), now + minute * 3));
+
+        let entry2 = entry(&[("Cache-Control", "max-age=120, must-revalidate")], now);
+        assert!(!entry2.is_usable(Some(&"max-stale".parse()?), now + minute * 3));
+
+        let entry3 = entry(&[("Cache-Control", "max-age=120, no-cache")], now);
+        assert!(!entry3.is_usable(None, now));
+        Ok(())
+    }
+}
@@ -0,0 +1,550 @@
+use async_std::io::{prelude::*, Cursor};
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::cache::cache_entry::is_cacheable_by_default;
+use crate::cache::storage::BoxFuture;
+use crate::cache::{CacheControl, CacheDirective, CacheEntry, CacheStorage, MemoryStorage, Vary};
+use crate::headers::{
+    CONTENT_TYPE, ETAG, EXPIRES, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE,
+    IF_UNMODIFIED_SINCE, LAST_MODIFIED, RANGE,
+};
+use crate::{Body, Client, Method, Request, Response, StatusCode, Url};
+
+/// The default maximum size of a stored response body: 1 MiB.
+const DEFAULT_MAX_ENTRY_SIZE: usize = 1024 * 1024;
+
+/// Status codes of responses the cache understands, and may store. Partial
+/// content and `304 Not Modified` responses aren't complete responses, and
+/// are never stored.
+///
+/// [RFC7234, section 3](https://tools.ietf.org/html/rfc7234#section-3)
+const UNDERSTOOD: &[u16] = &[
+    200, 203, 204, 300, 301, 302, 307, 308, 404, 405, 410, 414, 501,
+];
+
+/// A client which keeps a private cache of responses.
+///
+/// `GET` responses are stored when they are cacheable and have either an
+/// explicit freshness lifetime or a validator, and reused for as long as
+/// they are fresh. Stale responses with an `ETag` or `Last-Modified` header
+/// are revalidated with a conditional request. Successful requests with
+/// unsafe methods invalidate the stored responses for their URL.
+///
+/// Bodies are buffered in memory to be stored, up to
+/// [`max_entry_size`](#method.max_entry_size). Larger responses are streamed
+/// through without being stored.
+///
+/// # Specifications
+///
+/// - [RFC7234: Caching](https://tools.ietf.org/html/rfc7234)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+/// #
+/// use http_types::cache::CachingClient;
+/// use http_types::{Client, Method, Request, Response, Url};
+/// use std::future::Future;
+/// use std::pin::Pin;
+///
+/// /// A client which answers every request with a cacheable response.
+/// #[derive(Debug, Clone)]
+/// struct Origin;
+///
+/// impl Client for Origin {
+///     fn send_req(
+///         &self,
+///         _req: Request,
+///     ) -> Pin<Box<dyn Future<Output = http_types::Result<Response>> + Send>> {
+///         let mut res = Response::new(200);
+///         res.insert_header("Cache-Control", "max-age=60");
+///         res.set_body("Hello, world");
+///         Box::pin(async { Ok(res) })
+///     }
+/// }
+///
+/// let client = CachingClient::new(Origin);
+///
+/// let req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// let res = client.send_req(req).await?;
+/// assert_eq!(res.status(), 200);
+///
+/// // The second request is answered from the cache.
+/// let req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// let mut res = client.send_req(req).await?;
+/// assert_eq!(res.body_string().await?, "Hello, world");
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachingClient<C> {
+    client: C,
+    storage: Arc<dyn CacheStorage>,
+    max_entry_size: usize,
+}
+
+impl<C: Client> CachingClient<C> {
+    /// Create a new instance which stores responses in memory.
+    pub fn new(client: C) -> Self {
+        Self::with_storage(client, MemoryStorage::new())
+    }
+
+    /// Create a new instance with a custom storage.
+    pub fn with_storage(client: C, storage: impl CacheStorage) -> Self {
+        Self {
+            client,
+            storage: Arc::new(storage),
+            max_entry_size: DEFAULT_MAX_ENTRY_SIZE,
+        }
+    }
+
+    /// Get a reference to the underlying client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Get a reference to the storage.
+    pub fn storage(&self) -> &dyn CacheStorage {
+        &*self.storage
+    }
+
+    /// Get the maximum size of a stored response body, in bytes.
+    pub fn max_entry_size(&self) -> usize {
+        self.max_entry_size
+    }
+
+    /// Set the maximum size of a stored response body, in bytes.
+    ///
+    /// Defaults to 1 MiB.
+    pub fn set_max_entry_size(&mut self, max_entry_size: usize) {
+        self.max_entry_size = max_entry_size;
+    }
+
+    async fn send(self, mut req: Request) -> crate::Result<Response> {
+        let key = cache_key(req.url());
+        if req.method() != Method::Get || req.header(RANGE).is_some() {
+            let is_safe = req.method().is_safe();
+            let res = self.client.send_req(req).await?;
+            if !is_safe && (res.status().is_success() || res.status().is_redirection()) {
+                self.storage.remove(&key).await?;
+            }
+            return Ok(res);
+        }
+
+        let directives = CacheControl::from_headers(&req).ok().flatten();
+        let req_headers = req.as_ref().clone();
+        let mut entries = self.storage.get(&key).await?;
+
+        let now = SystemTime::now();
+        let mut revalidated = None;
+        if let Some(idx) = entries.iter().position(|entry| entry.matches(&req_headers)) {
+            let entry = &entries[idx];
+            if entry.is_usable(directives.as_ref(), now) {
+                return Ok(entry.to_response(now));
+            }
+            if !is_conditional(&req) {
+                if let Some(etag) = entry.headers().get(ETAG) {
+                    req.insert_header(IF_NONE_MATCH, etag);
+                    revalidated = Some(idx);
+                }
+                if let Some(last_modified) = entry.headers().get(LAST_MODIFIED) {
+                    req.insert_header(IF_MODIFIED_SINCE, last_modified);
+                    revalidated = Some(idx);
+                }
+            }
+        }
+
+        let only_if_cached = directives
+            .as_ref()
+            .map(|cc| cc.iter().any(|d| d == &CacheDirective::OnlyIfCached))
+            .unwrap_or(false);
+        if only_if_cached {
+            return Ok(Response::new(StatusCode::GatewayTimeout));
+        }
+
+        let request_time = SystemTime::now();
+        let mut res = self.client.send_req(req).await?;
+        let response_time = SystemTime::now();
+
+        if let Some(idx) = revalidated {
+            if res.status() == StatusCode::NotModified {
+                let mut entry = entries.remove(idx);
+                entry.update(&res, request_time, response_time);
+                let res = entry.to_response(response_time);
+                entries.push(entry);
+                self.storage.put(&key, entries).await?;
+                return Ok(res);
+            }
+        }
+
+        if is_storable(directives.as_ref(), &res) {
+            let has_content_type = res.header(CONTENT_TYPE).is_some();
+            let (body, bytes) = buffer_body(res.take_body(), self.max_entry_size).await?;
+            res.set_body(body);
+            if !has_content_type {
+                res.remove_header(CONTENT_TYPE);
+            }
+
+            if let Some(bytes) = bytes {
+                let entry = CacheEntry::new(
+                    req_headers.clone(),
+                    &res,
+                    bytes,
+                    request_time,
+                    response_time,
+                );
+                entries.retain(|stored| !stored.matches(&req_headers));
+                entries.push(entry);
+                self.storage.put(&key, entries).await?;
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<C: Client> Client for CachingClient<C> {
+    fn send_req(&self, req: Request) -> BoxFuture<'static, crate::Result<Response>> {
+        let this = self.clone();
+        Box::pin(this.send(req))
+    }
+}
+
+/// The primary cache key of a request: its URL without the fragment.
+fn cache_key(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url.to_string()
+}
+
+/// Returns `true` if the request has preconditions set by the caller.
+fn is_conditional(req: &Request) -> bool {
+    [
+        IF_MATCH,
+        IF_NONE_MATCH,
+        IF_MODIFIED_SINCE,
+        IF_UNMODIFIED_SINCE,
+        IF_RANGE,
+    ]
+    .iter()
+    .any(|name| req.header(name).is_some())
+}
+
+/// Read a body into memory if it's no larger than `max_size`.
+///
+/// Returns the body to send on, along with its bytes if they were buffered.
+/// Larger bodies are streamed on without being buffered.
+async fn buffer_body(mut body: Body, max_size: usize) -> crate::Result<(Body, Option<Vec<u8>>)> {
+    if matches!(body.len(), Some(len) if len > max_size) {
+        return Ok((body, None));
+    }
+
+    let mut bytes = vec![];
+    (&mut body)
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut bytes)
+        .await?;
+    let (mime, len) = (body.mime().clone(), body.len());
+    if bytes.len() > max_size {
+        let mut body = Body::from_reader(Cursor::new(bytes).chain(body), len);
+        body.set_mime(mime);
+        return Ok((body, None));
+    }
+
+    let mut buffered = Body::from(bytes.clone());
+    buffered.set_mime(mime);
+    Ok((buffered, Some(bytes)))
+}
+
+/// Returns `true` if a private cache may store the response to a `GET`
+/// request.
+///
+/// Responses are only stored if they have an explicit freshness lifetime, or
+/// a validator to revalidate them with.
+///
+/// [RFC7234, section 3](https://tools.ietf.org/html/rfc7234#section-3)
+fn is_storable(directives: Option<&CacheControl>, res: &Response) -> bool {
+    if directives.map(|cc| cc.is_no_store()).unwrap_or(false) {
+        return false;
+    }
+    if !UNDERSTOOD.contains(&(res.status() as u16)) {
+        return false;
+    }
+    match Vary::from_headers(res) {
+        Ok(Some(vary)) if vary.is_wildcard() => return false,
+        Err(_) => return false,
+        _ => {}
+    }
+
+    let cc = match CacheControl::from_headers(res) {
+        Ok(cc) => cc,
+        Err(_) => return false,
+    };
+    if let Some(cc) = &cc {
+        if cc.is_no_store() {
+            return false;
+        }
+        if cc.max_age().is_some() {
+            return true;
+        }
+    }
+    if res.header(EXPIRES).is_some() {
+        return true;
+    }
+
+    let is_public = cc
+        .map(|cc| cc.iter().any(|d| d == &CacheDirective::Public))
+        .unwrap_or(false);
+    let has_validator = res.header(ETAG).is_some() || res.header(LAST_MODIFIED).is_some();
+    has_validator && (is_public || is_cacheable_by_default(res.status()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+    use std::sync::Mutex;
+
+    /// A client answering requests with a function, and recording their
+    /// headers.
+    #[derive(Debug, Clone)]
+    struct Origin {
+        requests: Arc<Mutex<Vec<Headers>>>,
+        respond: fn(&Request) -> Response,
+    }
+
+    impl Origin {
+        fn new(respond: fn(&Request) -> Response) -> Self {
+            Self {
+                requests: Arc::new(Mutex::new(vec![])),
+                respond,
+            }
+        }
+
+        fn requests(&self) -> Vec<Headers> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl Client for Origin {
+        fn send_req(&self, req: Request) -> BoxFuture<'static, crate::Result<Response>> {
+            self.requests.lock().unwrap().push(req.as_ref().clone());
+            let res = (self.respond)(&req);
+            Box::pin(async move { Ok(res) })
+        }
+    }
+
+    fn request(method: Method) -> Request {
+        Request::new(method, Url::parse("https://example.com/#top").unwrap())
+    }
+
+    #[async_std::test]
+    async fn fresh_responses_are_reused() -> crate::Result<()> {
+        let origin = Origin::new(|_| {
+            let mut res = Response::new(StatusCode::Ok);
+            res.insert_header("Cache-Control", "max-age=60");
+            res.set_body("Hello Nori");
+            res
+        });
+        let client = CachingClient::new(origin.clone());
+
+        let mut res = client.send_req(request(Method::Get)).await?;
+        assert_eq!(res.body_string().await?, "Hello Nori");
+        assert!(res.header("Age").is_none());
+
+        let mut res = client.send_req(request(Method::Get)).await?;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res["Age"], "0");
+        assert_eq!(res["Content-Type"], "text/plain;charset=utf-8");
+        assert_eq!(res.body_string().await?, "Hello Nori");
+        assert_eq!(origin.requests().len(), 1);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn stale_responses_are_revalidated() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = match req.header("If-None-Match") {
+                Some(_) => Response::new(StatusCode::NotModified),
+                None => {
+                    let mut res = Response::new(StatusCode::Ok);
+                    res.set_body("Hello Chashu");
+                    res
+                }
+            };
+            res.insert_header("ETag", "\"v1\"");
+            res.insert_header("Cache-Control", "no-cache");
+            res
+        });
+        let client = CachingClient::new(origin.clone());
+
+        client.send_req(request(Method::Get)).await?;
+        let mut res = client.send_req(request(Method::Get)).await?;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res.body_string().await?, "Hello Chashu");
+
+        let requests = origin.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].get("If-None-Match").is_none());
+        assert_eq!(requests[1]["If-None-Match"], "\"v1\"");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn caller_revalidations_are_not_stored() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = match req.header("If-None-Match") {
+                Some(_) => Response::new(StatusCode::NotModified),
+                None => {
+                    let mut res = Response::new(StatusCode::Ok);
+                    res.set_body("Hello Nori");
+                    res
+                }
+            };
+            res.insert_header("ETag", "\"v1\"");
+            res.insert_header("Cache-Control", "max-age=60");
+            res
+        });
+        let storage = MemoryStorage::new();
+        let client = CachingClient::with_storage(origin.clone(), storage.clone());
+
+        let mut req = request(Method::Get);
+        req.insert_header("If-None-Match", "\"v1\"");
+        let res = client.send_req(req).await?;
+        assert_eq!(res.status(), StatusCode::NotModified);
+        assert!(storage.is_empty());
+
+        let mut res = client.send_req(request(Method::Get)).await?;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res.body_string().await?, "Hello Nori");
+        assert_eq!(origin.requests().len(), 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn responses_vary_on_request_headers() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = Response::new(StatusCode::Ok);
+            res.insert_header("Cache-Control", "max-age=60");
+            res.insert_header("Vary", "Accept-Language");
+            res.set_body(req["Accept-Language"].as_str());
+            res
+        });
+        let client = CachingClient::new(origin.clone());
+
+        for lang in &["en", "fr", "en", "fr"] {
+            let mut req = request(Method::Get);
+            req.insert_header("Accept-Language", *lang);
+            let mut res = client.send_req(req).await?;
+            assert_eq!(&res.body_string().await?, lang);
+        }
+        assert_eq!(origin.requests().len(), 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn uncacheable_responses() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = Response::new(StatusCode::Ok);
+            if req.header("X-No-Store").is_some() {
+                res.insert_header("Cache-Control", "no-store");
+            }
+            res
+        });
+        let storage = MemoryStorage::new();
+        let client = CachingClient::with_storage(origin.clone(), storage.clone());
+
+        let mut req = request(Method::Get);
+        req.insert_header("X-No-Store", "1");
+        client.send_req(req).await?;
+        let mut req = request(Method::Get);
+        req.insert_header("Cache-Control", "no-store");
+        client.send_req(req).await?;
+        assert!(storage.is_empty());
+
+        let mut req = request(Method::Get);
+        req.insert_header("Cache-Control", "only-if-cached");
+        let res = client.send_req(req).await?;
+        assert_eq!(res.status(), StatusCode::GatewayTimeout);
+        assert_eq!(origin.requests().len(), 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn unsafe_methods_invalidate() -> crate::Result<()> {
+        let origin = Origin::new(|_| {
+            let mut res = Response::new(StatusCode::Ok);
+            res.insert_header("Cache-Control", "max-age=60");
+            res
+        });
+        let storage = MemoryStorage::new();
+        let client = CachingClient::with_storage(origin.clone(), storage.clone());
+
+        client.send_req(request(Method::Get)).await?;
+        assert_eq!(storage.len(), 1);
+        client.send_req(request(Method::Head)).await?;
+        assert_eq!(storage.len(), 1);
+        client.send_req(request(Method::Post)).await?;
+        assert!(storage.is_empty());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn responses_without_freshness_or_validator() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = Response::new(StatusCode::Ok);
+            if req.header("X-Validator").is_some() {
+                res.insert_header("ETag", "\"v1\"");
+            }
+            res
+        });
+        let storage = MemoryStorage::new();
+        let client = CachingClient::with_storage(origin, storage.clone());
+
+        client.send_req(request(Method::Get)).await?;
+        assert!(storage.is_empty());
+
+        let mut req = request(Method::Get);
+        req.insert_header("X-Validator", "1");
+        client.send_req(req).await?;
+        assert_eq!(storage.len(), 1);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn large_responses_are_streamed() -> crate::Result<()> {
+        let origin = Origin::new(|req| {
+            let mut res = Response::new(StatusCode::Ok);
+            res.insert_header("Cache-Control", "max-age=60");
+            match req.header("X-Unknown-Length") {
+                Some(_) => res.set_body(Body::from_reader(Cursor::new("Hello Nori"), None)),
+                None => res.set_body("Hello Nori"),
+            }
+            res
+        });
+        let storage = MemoryStorage::new();
+        let mut client = CachingClient::with_storage(origin.clone(), storage.clone());
+        client.set_max_entry_size(4);
+
+        let mut res = client.send_req(request(Method::Get)).await?;
+        assert_eq!(res.body_string().await?, "Hello Nori");
+
+        let mut req = request(Method::Get);
+        req.insert_header("X-Unknown-Length", "1");
+        let mut res = client.send_req(req).await?;
+        assert_eq!(res["Content-Type"], "application/octet-stream");
+        assert_eq!(res.body_string().await?, "Hello Nori");
+        assert!(storage.is_empty());
+
+        client.set_max_entry_size(10);
+        let mut req = request(Method::Get);
+        req.insert_header("X-Unknown-Length", "1");
+        let mut res = client.send_req(req).await?;
+        assert_eq!(res.body_string().await?, "Hello Nori");
+        assert_eq!(storage.len(), 1);
+        assert_eq!(origin.requests().len(), 3);
+        Ok(())
+    }
+}
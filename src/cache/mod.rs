@@ -0,0 +1,42 @@
+//! HTTP caching.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::Response;
+//! use http_types::cache::{CacheControl, CacheDirective};
+//! use std::time::Duration;
+//!
+//! let mut entries = CacheControl::new();
+//! entries.push(CacheDirective::Public);
+//! entries.push(CacheDirective::MaxAge(Duration::from_secs(60)));
+//!
+//! let mut res = Response::new(200);
+//! entries.apply(&mut res);
+//! assert_eq!(res["Cache-Control"], "public, max-age=60");
+//!
+//! let entries = CacheControl::from_headers(res)?.unwrap();
+//! assert_eq!(entries.max_age(), Some(Duration::from_secs(60)));
+//! #
+//! # Ok(()) }
+//! ```
+
+mod cache_control;
+mod cache_directive;
+mod vary;
+
+pub use cache_control::CacheControl;
+pub use cache_directive::CacheDirective;
+pub use vary::Vary;
+
+cfg_unstable! {
+    mod cache_entry;
+    mod caching_client;
+    mod storage;
+
+    pub use cache_entry::CacheEntry;
+    pub use caching_client::CachingClient;
+    pub use storage::{CacheStorage, MemoryStorage};
+}
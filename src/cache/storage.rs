@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use crate::cache::CacheEntry;
+
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a + Send>>;
+
+/// Storage for the responses of a [`CachingClient`](crate::cache::CachingClient).
+///
+/// Responses are stored under the URL of the request. A single URL may have
+/// several entries when responses vary on request headers.
+pub trait CacheStorage: Debug + Send + Sync + 'static {
+    /// Get all entries stored under a key.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, crate::Result<Vec<CacheEntry>>>;
+
+    /// Replace all entries stored under a key.
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        entries: Vec<CacheEntry>,
+    ) -> BoxFuture<'a, crate::Result<()>>;
+
+    /// Remove all entries stored under a key.
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, crate::Result<()>>;
+}
+
+/// A `CacheStorage` which keeps entries in memory.
+///
+/// The stored response bodies are limited to a total size, 64 MiB by default.
+/// When storing entries would exceed it, the keys which were least recently
+/// used are evicted. Entries larger than the whole capacity aren't stored.
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct MemoryStorage {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    keys: HashMap<String, Stored>,
+    size: usize,
+    capacity: usize,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Stored {
+    entries: Vec<CacheEntry>,
+    size: usize,
+    last_used: u64,
+}
+
+/// The default total size of the bodies in a `MemoryStorage`: 64 MiB.
+const DEFAULT_CAPACITY: usize = 64 * 1024 * 1024;
+
+impl MemoryStorage {
+    /// Create a new instance of `MemoryStorage`, with the default capacity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance of `MemoryStorage`, storing up to `capacity`
+    /// bytes of response bodies.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let inner = Inner {
+            keys: HashMap::new(),
+            size: 0,
+            capacity,
+            clock: 0,
+        };
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    /// Get the number of keys with stored entries.
+    pub fn len(&self) -> usize {
+        self.lock().keys.len()
+    }
+
+    /// Returns `true` if no entries are stored.
+    pub fn is_empty(&self) -> bool {
+        self.lock().keys.is_empty()
+    }
+
+    /// Get the total size of the stored response bodies, in bytes.
+    pub fn size(&self) -> usize {
+        self.lock().size
+    }
+
+    /// Get the maximum total size of the stored response bodies, in bytes.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Remove all stored entries.
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.keys.clear();
+        inner.size = 0;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for MemoryStorage {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl Inner {
+    fn remove(&mut self, key: &str) {
+        if let Some(stored) = self.keys.remove(key) {
+            self.size -= stored.size;
+        }
+    }
+
+    /// Evict the least recently used keys until the entries fit.
+    fn evict(&mut self) {
+        while self.size > self.capacity {
+            let oldest = self
+                .keys
+                .iter()
+                .min_by_key(|(_, stored)| stored.last_used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.remove(&key),
+                None => break,
+            }
+        }
+    }
+}
+
+impl CacheStorage for MemoryStorage {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, crate::Result<Vec<CacheEntry>>> {
+        let mut inner = self.lock();
+        inner.clock += 1;
+        let clock = inner.clock;
+        let entries = match inner.keys.get_mut(key) {
+            Some(stored) => {
+                stored.last_used = clock;
+                stored.entries.clone()
+            }
+            None => vec![],
+        };
+        Box::pin(async move { Ok(entries) })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        entries: Vec<CacheEntry>,
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        let mut inner = self.lock();
+        inner.remove(key);
+        let size = entries.iter().map(|entry| entry.body().len()).sum();
+        if !entries.is_empty() && size <= inner.capacity {
+            inner.clock += 1;
+            let last_used = inner.clock;
+            let stored = Stored {
+                entries,
+                size,
+                last_used,
+            };
+            inner.keys.insert(key.to_string(), stored);
+            inner.size += size;
+            inner.evict();
+        }
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, crate::Result<()>> {
+        self.lock().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+    use crate::Response;
+    use std::time::SystemTime;
+
+    fn entry(body: &str) -> CacheEntry {
+        let now = SystemTime::now();
+        let res = Response::new(200);
+        CacheEntry::new(Headers::new(), &res, body.into(), now, now)
+    }
+
+    #[async_std::test]
+    async fn evicts_least_recently_used() -> crate::Result<()> {
+        let storage = MemoryStorage::with_capacity(10);
+        storage.put("a", vec![entry("1234")]).await?;
+        storage.put("b", vec![entry("1234")]).await?;
+        assert_eq!(storage.size(), 8);
+
+        storage.get("a").await?;
+        storage.put("c", vec![entry("1234")]).await?;
+        assert_eq!(storage.size(), 8);
+        assert_eq!(storage.get("a").await?.len(), 1);
+        assert!(storage.get("b").await?.is_empty());
+        assert_eq!(storage.get("c").await?.len(), 1);
+
+        storage.put("d", vec![entry("12345678901")]).await?;
+        assert!(storage.get("d").await?.is_empty());
+        assert_eq!(storage.len(), 2);
+
+        storage.put("a", vec![]).await?;
+        assert_eq!(storage.size(), 4);
+        Ok(())
+    }
+}
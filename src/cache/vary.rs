@@ -0,0 +1,321 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, VARY};
+use crate::parse_utils::{is_token, split_list};
+use crate::{Error, StatusCode};
+
+/// The request headers a response was selected on, from the `Vary` header.
+///
+/// Caches use it to decide whether a stored response can be used to satisfy
+/// a new request: all headers listed must match those of the request the
+/// response was stored for. A `Vary: *` response never matches.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 7.1.4: Vary](https://tools.ietf.org/html/rfc7231#section-7.1.4)
+/// - [RFC7234, section 4.1: Calculating Secondary Keys with Vary](https://tools.ietf.org/html/rfc7234#section-4.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::cache::Vary;
+/// use http_types::{Method, Request, Response, Url};
+///
+/// let mut vary = Vary::new();
+/// vary.push("Accept-Encoding");
+///
+/// let mut res = Response::new(200);
+/// vary.apply(&mut res);
+/// assert_eq!(res["Vary"], "accept-encoding");
+///
+/// let mut stored = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// stored.insert_header("Accept-Encoding", "gzip,  br");
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Accept-Encoding", "gzip, br");
+///
+/// let vary = Vary::from_headers(res)?.unwrap();
+/// assert!(vary.matches(&stored, &req));
+/// assert_eq!(vary.cache_key(&req), Some("accept-encoding: gzip, br".into()));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vary {
+    entries: Vec<HeaderName>,
+    wildcard: bool,
+}
+
+impl Vary {
+    /// Create a new instance of `Vary`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance of `Vary: *`, which varies on more than the
+    /// request headers.
+    pub fn wildcard() -> Self {
+        Self {
+            entries: vec![],
+            wildcard: true,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Vary` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(VARY) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut vary = Self::new();
+        for value in values {
+            vary.extend_from_str(value.as_str())?;
+        }
+        Ok(Some(vary))
+    }
+
+    fn extend_from_str(&mut self, s: &str) -> crate::Result<()> {
+        for name in split_list(s) {
+            if name == "*" {
+                self.wildcard = true;
+            } else if is_token(name) {
+                self.push(name);
+            } else {
+                return Err(Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("`{}` is not a valid Vary header name", name),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the `Vary` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        VARY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Header names should be valid ASCII")
+    }
+
+    /// Push a header name into the list of entries.
+    ///
+    /// Names already in the list are ignored.
+    pub fn push(&mut self, name: impl Into<HeaderName>) {
+        let name = name.into();
+        if !self.entries.contains(&name) {
+            self.entries.push(name);
+        }
+    }
+
+    /// Returns `true` if this is `Vary: *`.
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Returns `true` if the header name is in the list of entries.
+    pub fn contains(&self, name: impl Into<HeaderName>) -> bool {
+        self.entries.contains(&name.into())
+    }
+
+    /// An iterator visiting all header names.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Compute the secondary cache key of a request.
+    ///
+    /// The key holds the normalized values of all the listed headers, so
+    /// requests which only differ in whitespace or casing of the header names
+    /// get the same key. Headers missing from the request are distinct from
+    /// empty headers.
+    ///
+    /// Returns `None` for `Vary: *`, as responses varying on more than the
+    /// request headers should not be reused.
+    pub fn cache_key(&self, headers: impl AsRef<Headers>) -> Option<String> {
+        if self.wildcard {
+            return None;
+        }
+
+        let headers = headers.as_ref();
+        let mut names: Vec<&str> = self.entries.iter().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+
+        let lines: Vec<String> = names
+            .into_iter()
+            .map(|name| match headers.get(name) {
+                Some(values) => {
+                    let values: Vec<&str> = values
+                        .iter()
+                        .flat_map(|value| split_list(value.as_str()))
+                        .collect();
+                    format!("{}: {}", name, values.join(", "))
+                }
+                None => name.to_string(),
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    /// Returns `true` if a response stored for the `stored` request can be
+    /// used to satisfy the new request.
+    pub fn matches(&self, stored: impl AsRef<Headers>, req: impl AsRef<Headers>) -> bool {
+        match (self.cache_key(stored), self.cache_key(req)) {
+            (Some(stored), Some(key)) => stored == key,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Vary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.wildcard {
+            return write!(f, "*");
+        }
+        for (n, name) in self.entries.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", name)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Vary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vary = Self::new();
+        vary.extend_from_str(s)?;
+        Ok(vary)
+    }
+}
+
+impl IntoIterator for Vary {
+    type Item = HeaderName;
+    type IntoIter = std::vec::IntoIter<HeaderName>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Vary {
+    type Item = &'a HeaderName;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `Vary`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, HeaderName>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a HeaderName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append("Vary", "Accept-Encoding, Origin");
+        headers.append("Vary", "accept-encoding,Accept-Language");
+
+        let vary = Vary::from_headers(&headers)?.unwrap();
+        assert!(!vary.is_wildcard());
+        assert!(vary.contains("ORIGIN"));
+        assert_eq!(vary.iter().count(), 3);
+        assert_eq!(vary.value(), "accept-encoding, origin, accept-language");
+        Ok(())
+    }
+
+    #[test]
+    fn wildcard() -> crate::Result<()> {
+        let vary: Vary = "origin, *".parse()?;
+        assert!(vary.is_wildcard());
+        assert_eq!(vary.value(), "*");
+        assert_eq!(vary.cache_key(Headers::new()), None);
+        assert!(!vary.matches(Headers::new(), Headers::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn cache_key() -> crate::Result<()> {
+        let vary: Vary = "Origin, Accept-Encoding".parse()?;
+
+        let mut a = Headers::new();
+        a.append("Accept-Encoding", "gzip");
+        a.append("Accept-Encoding", "br ,deflate");
+        a.insert("Origin", "https://example.com");
+        assert_eq!(
+            vary.cache_key(&a).unwrap(),
+            "accept-encoding: gzip, br, deflate\norigin: https://example.com"
+        );
+
+        let mut b = Headers::new();
+        b.insert("origin", "https://example.com");
+        b.insert("accept-encoding", "gzip, br, deflate");
+        b.insert("Accept-Language", "en");
+        assert!(vary.matches(&a, &b));
+
+        b.insert("Accept-Encoding", "gzip");
+        assert!(!vary.matches(&a, &b));
+
+        // A missing header doesn't match an empty one.
+        let mut c = Headers::new();
+        c.insert("Origin", "https://example.com");
+        let mut d = c.clone();
+        d.insert("Accept-Encoding", "");
+        assert_eq!(
+            vary.cache_key(&c).unwrap(),
+            "accept-encoding\norigin: https://example.com"
+        );
+        assert!(!vary.matches(&c, &d));
+        assert!(Vary::new().matches(&c, &d));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Vary", "accept encoding");
+        let err = Vary::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, ETAG};
+use crate::{Error, StatusCode};
+
+/// HTTP Entity Tags.
+///
+/// ETags provide an ID for a particular resource, enabling clients and servers
+/// to reason about caches and make conditional requests.
+///
+/// # Specifications
+///
+/// - [RFC7232, section 2.3: ETag](https://tools.ietf.org/html/rfc7232#section-2.3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::conditional::ETag;
+///
+/// let etag = ETag::new("0xcafebeef");
+///
+/// let mut res = Response::new(200);
+/// etag.apply(&mut res);
+/// assert_eq!(res["ETag"], "\"0xcafebeef\"");
+///
+/// let etag = ETag::from_headers(res)?.unwrap();
+/// assert_eq!(etag, ETag::Strong(String::from("0xcafebeef")));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ETag {
+    /// An ETag using strong validation.
+    Strong(String),
+    /// An ETag using weak validation.
+    Weak(String),
+}
+
+impl ETag {
+    /// Create a new ETag that uses strong validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag contains characters which aren't allowed in an
+    /// entity tag: whitespace, control characters, and `"`.
+    pub fn new(s: impl Into<String>) -> Self {
+        let s = s.into();
+        assert!(
+            is_etag(&s),
+            "ETags should only contain valid etag characters"
+        );
+        Self::Strong(s)
+    }
+
+    /// Create a new ETag that uses weak validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag contains characters which aren't allowed in an
+    /// entity tag: whitespace, control characters, and `"`.
+    pub fn new_weak(s: impl Into<String>) -> Self {
+        let s = s.into();
+        assert!(
+            is_etag(&s),
+            "ETags should only contain valid etag characters"
+        );
+        Self::Weak(s)
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only a single ETag per resource is assumed to exist. If multiple ETag
+    /// headers are found the last one is used.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ETAG) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If a header is returned we can assume at least one exists.
+        headers.last().as_str().parse().map(Some)
+    }
+
+    /// Sets the `ETag` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ETAG
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let s = self.to_string();
+        HeaderValue::from_str(&s).expect("ETags should be valid ASCII")
+    }
+
+    /// Returns `true` if the ETag is a `Strong` value.
+    pub fn is_strong(&self) -> bool {
+        matches!(self, Self::Strong(_))
+    }
+
+    /// Returns `true` if the ETag is a `Weak` value.
+    pub fn is_weak(&self) -> bool {
+        matches!(self, Self::Weak(_))
+    }
+
+    /// Get the opaque tag, without the quotes or weakness indicator.
+    pub fn tag(&self) -> &str {
+        match self {
+            Self::Strong(s) | Self::Weak(s) => s.as_str(),
+        }
+    }
+
+    /// Compare two ETags using the strong comparison function.
+    ///
+    /// Both ETags must be strong and have identical opaque tags.
+    ///
+    /// [RFC7232, section 2.3.2](https://tools.ietf.org/html/rfc7232#section-2.3.2)
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        self.is_strong() && other.is_strong() && self.tag() == other.tag()
+    }
+
+    /// Compare two ETags using the weak comparison function.
+    ///
+    /// The opaque tags must be identical, regardless of either or both being
+    /// tagged as weak.
+    ///
+    /// [RFC7232, section 2.3.2](https://tools.ietf.org/html/rfc7232#section-2.3.2)
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag() == other.tag()
+    }
+}
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strong(s) => write!(f, r#""{}""#, s),
+            Self::Weak(s) => write!(f, r#"W/"{}""#, s),
+        }
+    }
+}
+
+impl FromStr for ETag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (weak, tag) = match s.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let tag = tag
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .filter(|tag| is_etag(tag))
+            .ok_or_else(|| {
+                Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("`{}` is not a valid ETag", s),
+                )
+            })?;
+
+        let tag = tag.to_string();
+        Ok(if weak {
+            Self::Weak(tag)
+        } else {
+            Self::Strong(tag)
+        })
+    }
+}
+
+/// Validates an opaque tag: `*etagc`, where `etagc = %x21 / %x23-7E / obs-text`.
+fn is_etag(s: &str) -> bool {
+    s.chars()
+        .all(|c| matches!(c, '\x21' | '\x23'..='\x7e') || c >= '\u{80}')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let etag = ETag::new("0xcafebeef");
+
+        let mut headers = Headers::new();
+        etag.apply(&mut headers);
+
+        let etag = ETag::from_headers(headers)?.unwrap();
+        assert_eq!(etag, ETag::Strong(String::from("0xcafebeef")));
+        Ok(())
+    }
+
+    #[test]
+    fn smoke_weak() -> crate::Result<()> {
+        let etag = ETag::new_weak("0xcafebeef");
+
+        let mut headers = Headers::new();
+        etag.apply(&mut headers);
+        assert_eq!(headers["ETag"], r#"W/"0xcafebeef""#);
+
+        let etag = ETag::from_headers(headers)?.unwrap();
+        assert_eq!(etag, ETag::Weak(String::from("0xcafebeef")));
+        Ok(())
+    }
+
+    #[test]
+    fn comparison() {
+        // Examples from RFC7232, section 2.3.2.
+        let weak_1 = ETag::new_weak("1");
+        let weak_2 = ETag::new_weak("2");
+        let strong_1 = ETag::new("1");
+
+        assert!(!weak_1.strong_eq(&weak_1));
+        assert!(weak_1.weak_eq(&weak_1));
+
+        assert!(!weak_1.strong_eq(&weak_2));
+        assert!(!weak_1.weak_eq(&weak_2));
+
+        assert!(!weak_1.strong_eq(&strong_1));
+        assert!(weak_1.weak_eq(&strong_1));
+
+        assert!(strong_1.strong_eq(&strong_1));
+        assert!(strong_1.weak_eq(&strong_1));
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "abc", "\"abc", "w/\"abc\"", "\"a\"b\"", "\"a b\""] {
+            let err = s.parse::<ETag>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+
+    #[test]
+    fn empty_tag() -> crate::Result<()> {
+        assert_eq!(r#""""#.parse::<ETag>()?, ETag::Strong(String::new()));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_validates_tag() {
+        ETag::new("has \"quotes\"");
+    }
+}
@@ -0,0 +1,35 @@
+//! HTTP conditional requests.
+//!
+//! # Specifications
+//!
+//! - [RFC7232: Conditional Requests](https://tools.ietf.org/html/rfc7232)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::conditional::{self, ETag, Precondition, Validators};
+//! use http_types::{Method, Request, Response, StatusCode, Url};
+//!
+//! let etag = ETag::new("33a64df551425fcc55e4d42a148795d9f25f89d4");
+//! let mut validators = Validators::new();
+//! validators.set_etag(etag.clone());
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//! req.insert_header("If-None-Match", etag.value());
+//!
+//! let res = match conditional::evaluate(&req, Some(&validators))?.status() {
+//!     Some(status) => Response::new(status),
+//!     None => Response::new(StatusCode::Ok),
+//! };
+//! assert_eq!(res.status(), StatusCode::NotModified);
+//! #
+//! # Ok(()) }
+//! ```
+
+mod etag;
+mod precondition;
+
+pub use etag::ETag;
+pub use precondition::{evaluate, Precondition, Validators};
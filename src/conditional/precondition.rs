@@ -0,0 +1,355 @@
+use std::time::SystemTime;
+
+use crate::conditional::ETag;
+use crate::headers::{HeaderName, Headers, IF_MATCH, IF_NONE_MATCH, IF_RANGE, RANGE};
+use crate::parse_utils::split_list;
+use crate::{HttpDate, Method, Request, StatusCode};
+
+/// The current validators of a resource, used to evaluate conditional requests.
+///
+/// # Examples
+///
+/// ```
+/// use http_types::conditional::{ETag, Validators};
+/// use std::time::SystemTime;
+///
+/// let mut validators = Validators::new();
+/// validators.set_etag(ETag::new("xyzzy"));
+/// validators.set_last_modified(SystemTime::now());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    etag: Option<ETag>,
+    last_modified: Option<SystemTime>,
+}
+
+impl Validators {
+    /// Create a new instance of `Validators`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current entity tag of the resource.
+    pub fn etag(&self) -> Option<&ETag> {
+        self.etag.as_ref()
+    }
+
+    /// Set the current entity tag of the resource.
+    pub fn set_etag(&mut self, etag: ETag) {
+        self.etag = Some(etag);
+    }
+
+    /// Get the last modification time of the resource.
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+    }
+
+    /// Set the last modification time of the resource.
+    pub fn set_last_modified(&mut self, last_modified: SystemTime) {
+        self.last_modified = Some(last_modified);
+    }
+}
+
+/// The outcome of evaluating the preconditions of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precondition {
+    /// All preconditions passed, and the request should be performed as usual.
+    Proceed,
+    /// All preconditions passed, but `If-Range` did not match so the `Range`
+    /// header should be ignored and the full representation sent.
+    IgnoreRange,
+    /// The request should be answered with `304 Not Modified`.
+    NotModified,
+    /// The request should be answered with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+impl Precondition {
+    /// Get the status code the request should be answered with, if the
+    /// request should not be performed.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Proceed | Self::IgnoreRange => None,
+            Self::NotModified => Some(StatusCode::NotModified),
+            Self::PreconditionFailed => Some(StatusCode::PreconditionFailed),
+        }
+    }
+}
+
+/// Evaluate the preconditions of a request against the current validators of
+/// the target resource.
+///
+/// Pass `None` as the validators if the resource currently has no
+/// representation, e.g. when a `PUT` request would create it.
+///
+/// The `If-Match`, `If-Unmodified-Since`, `If-None-Match`,
+/// `If-Modified-Since` and `If-Range` headers are evaluated in the order
+/// defined by [RFC7232, section 6](https://tools.ietf.org/html/rfc7232#section-6).
+/// Dates which fail to parse are ignored, as the RFC requires.
+///
+/// # Errors
+///
+/// An error with status `400 Bad Request` is returned if an `If-Match`,
+/// `If-None-Match` or `If-Range` header contains a malformed entity tag.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::conditional::{self, ETag, Precondition, Validators};
+/// use http_types::{Method, Request, StatusCode, Url};
+///
+/// let mut validators = Validators::new();
+/// validators.set_etag(ETag::new("xyzzy"));
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("If-None-Match", r#"W/"xyzzy""#);
+///
+/// let precondition = conditional::evaluate(&req, Some(&validators))?;
+/// assert_eq!(precondition, Precondition::NotModified);
+/// assert_eq!(precondition.status(), Some(StatusCode::NotModified));
+/// #
+/// # Ok(()) }
+/// ```
+pub fn evaluate(req: &Request, validators: Option<&Validators>) -> crate::Result<Precondition> {
+    let headers: &Headers = req.as_ref();
+    let method = req.method();
+    let is_get_or_head = method == Method::Get || method == Method::Head;
+    let etag = validators.and_then(|validators| validators.etag());
+    let last_modified = validators
+        .and_then(|validators| validators.last_modified())
+        .map(HttpDate::from);
+
+    // Step 1 and 2: If-Match, or If-Unmodified-Since when If-Match is absent.
+    if let Some(tags) = EntityTags::from_headers(headers, IF_MATCH)? {
+        let matches = match (tags, etag) {
+            (EntityTags::Any, _) => validators.is_some(),
+            (EntityTags::List(tags), Some(etag)) => tags.iter().any(|tag| tag.strong_eq(etag)),
+            (EntityTags::List(_), None) => false,
+        };
+        if !matches {
+            return Ok(Precondition::PreconditionFailed);
+        }
+    } else if let (Some(date), Some(last_modified)) = (req.if_unmodified_since(), last_modified) {
+        if last_modified > date {
+            return Ok(Precondition::PreconditionFailed);
+        }
+    }
+
+    // Step 3 and 4: If-None-Match, or If-Modified-Since when If-None-Match is absent.
+    if let Some(tags) = EntityTags::from_headers(headers, IF_NONE_MATCH)? {
+        let matches = match (tags, etag) {
+            (EntityTags::Any, _) => validators.is_some(),
+            (EntityTags::List(tags), Some(etag)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+            (EntityTags::List(_), None) => false,
+        };
+        if matches {
+            return Ok(if is_get_or_head {
+                Precondition::NotModified
+            } else {
+                Precondition::PreconditionFailed
+            });
+        }
+    } else if is_get_or_head {
+        if let (Some(date), Some(last_modified)) = (req.if_modified_since(), last_modified) {
+            if last_modified <= date {
+                return Ok(Precondition::NotModified);
+            }
+        }
+    }
+
+    // Step 5: If-Range, which only applies to GET requests with a Range header.
+    if method == Method::Get && headers.get(RANGE).is_some() {
+        if let Some(value) = headers.get(IF_RANGE) {
+            let value = value.last().as_str().trim();
+            let matches = if value.starts_with('"') || value.starts_with("W/") {
+                let tag: ETag = value.parse()?;
+                matches!(etag, Some(etag) if tag.strong_eq(etag))
+            } else {
+                match (value.parse::<HttpDate>(), last_modified) {
+                    (Ok(date), Some(last_modified)) => date == last_modified,
+                    _ => false,
+                }
+            };
+            if !matches {
+                return Ok(Precondition::IgnoreRange);
+            }
+        }
+    }
+
+    Ok(Precondition::Proceed)
+}
+
+/// The value of an `If-Match` or `If-None-Match` header.
+enum EntityTags {
+    /// `*`, matching any current representation.
+    Any,
+    /// A list of entity tags.
+    List(Vec<ETag>),
+}
+
+impl EntityTags {
+    fn from_headers(headers: &Headers, name: HeaderName) -> crate::Result<Option<Self>> {
+        let values = match headers.get(name) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut tags = vec![];
+        for value in values {
+            for tag in split_list(value.as_str()) {
+                if tag == "*" {
+                    return Ok(Some(Self::Any));
+                }
+                tags.push(tag.parse()?);
+            }
+        }
+        Ok(Some(Self::List(tags)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Url;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const LAST_MODIFIED: &str = "Sun, 06 Nov 1994 08:49:37 GMT";
+
+    fn validators() -> Validators {
+        let mut validators = Validators::new();
+        validators.set_etag(ETag::new("xyzzy"));
+        validators.set_last_modified(UNIX_EPOCH + Duration::from_secs(784_111_777));
+        validators
+    }
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(method, Url::parse("https://example.com").unwrap());
+        for (name, value) in headers {
+            req.append_header(*name, *value);
+        }
+        req
+    }
+
+    fn eval(method: Method, headers: &[(&str, &str)]) -> Precondition {
+        evaluate(&request(method, headers), Some(&validators())).unwrap()
+    }
+
+    #[test]
+    fn unconditional() {
+        assert_eq!(eval(Method::Get, &[]), Precondition::Proceed);
+        assert_eq!(eval(Method::Put, &[]), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_match() {
+        let headers = [("If-Match", r#""abc", "xyzzy""#)];
+        assert_eq!(eval(Method::Put, &headers), Precondition::Proceed);
+
+        // If-Match uses the strong comparison function.
+        let headers = [("If-Match", r#"W/"xyzzy""#)];
+        assert_eq!(
+            eval(Method::Put, &headers),
+            Precondition::PreconditionFailed
+        );
+
+        let req = request(Method::Put, &[("If-Match", "*")]);
+        assert_eq!(
+            evaluate(&req, Some(&validators())).unwrap(),
+            Precondition::Proceed
+        );
+        assert_eq!(
+            evaluate(&req, None).unwrap(),
+            Precondition::PreconditionFailed
+        );
+    }
+
+    #[test]
+    fn if_unmodified_since() {
+        let headers = [("If-Unmodified-Since", LAST_MODIFIED)];
+        assert_eq!(eval(Method::Put, &headers), Precondition::Proceed);
+
+        let headers = [("If-Unmodified-Since", "Sat, 05 Nov 1994 08:49:37 GMT")];
+        assert_eq!(
+            eval(Method::Put, &headers),
+            Precondition::PreconditionFailed
+        );
+
+        // Ignored when If-Match is present, or when the date is invalid.
+        let headers = [
+            ("If-Match", r#""xyzzy""#),
+            ("If-Unmodified-Since", "Sat, 05 Nov 1994 08:49:37 GMT"),
+        ];
+        assert_eq!(eval(Method::Put, &headers), Precondition::Proceed);
+        let headers = [("If-Unmodified-Since", "yesterday")];
+        assert_eq!(eval(Method::Put, &headers), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_none_match() {
+        let headers = [("If-None-Match", r#"W/"xyzzy""#)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::NotModified);
+        assert_eq!(eval(Method::Head, &headers), Precondition::NotModified);
+        assert_eq!(
+            eval(Method::Post, &headers),
+            Precondition::PreconditionFailed
+        );
+
+        let headers = [("If-None-Match", r#""abc""#)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+
+        let req = request(Method::Put, &[("If-None-Match", "*")]);
+        assert_eq!(
+            evaluate(&req, Some(&validators())).unwrap(),
+            Precondition::PreconditionFailed
+        );
+        assert_eq!(evaluate(&req, None).unwrap(), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_modified_since() {
+        let headers = [("If-Modified-Since", LAST_MODIFIED)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::NotModified);
+        assert_eq!(eval(Method::Post, &headers), Precondition::Proceed);
+
+        let headers = [("If-Modified-Since", "Sat, 05 Nov 1994 08:49:37 GMT")];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+
+        // Ignored when If-None-Match is present.
+        let headers = [
+            ("If-None-Match", r#""abc""#),
+            ("If-Modified-Since", LAST_MODIFIED),
+        ];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_range() {
+        let headers = [("Range", "bytes=0-99"), ("If-Range", r#""xyzzy""#)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+
+        let headers = [("Range", "bytes=0-99"), ("If-Range", r#"W/"xyzzy""#)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::IgnoreRange);
+
+        let headers = [("Range", "bytes=0-99"), ("If-Range", LAST_MODIFIED)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+
+        let headers = [
+            ("Range", "bytes=0-99"),
+            ("If-Range", "Sat, 05 Nov 1994 08:49:37 GMT"),
+        ];
+        assert_eq!(eval(Method::Get, &headers), Precondition::IgnoreRange);
+
+        // Ignored without a Range header.
+        let headers = [("If-Range", r#""abc""#)];
+        assert_eq!(eval(Method::Get, &headers), Precondition::Proceed);
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let req = request(Method::Get, &[("If-None-Match", "xyzzy")]);
+        let err = evaluate(&req, Some(&validators())).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
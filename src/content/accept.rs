@@ -0,0 +1,305 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::content::MediaTypeProposal;
+use crate::headers::{HeaderName, HeaderValue, Headers, ACCEPT};
+use crate::parse_utils::split_list;
+use crate::{Error, Mime, StatusCode};
+
+/// Client header advertising which media types the client is able to understand.
+///
+/// Using content negotiation, the server then selects one of the proposals, uses
+/// it and informs the client of its choice with the `Content-Type` response
+/// header.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 5.3.2: Accept](https://tools.ietf.org/html/rfc7231#section-5.3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{Accept, MediaTypeProposal};
+/// use http_types::{mime, Response};
+///
+/// let mut accept = Accept::new();
+/// accept.push(MediaTypeProposal::new(mime::HTML, Some(0.8))?);
+/// accept.push(MediaTypeProposal::new(mime::XML, Some(0.4))?);
+/// accept.push(mime::PLAIN);
+///
+/// let mut res = Response::new(200);
+/// let content_type = accept.negotiate(&[mime::XML])?;
+/// res.set_content_type(content_type);
+///
+/// assert_eq!(res["Content-Type"], "application/xml;charset=utf-8");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accept {
+    entries: Vec<MediaTypeProposal>,
+}
+
+impl Accept {
+    /// Create a new instance of `Accept`.
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Create an instance of `Accept` from a `Headers` instance.
+    ///
+    /// All `Accept` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(ACCEPT) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in values {
+            for part in split_list(value.as_str()) {
+                accept.push(part.parse::<MediaTypeProposal>()?);
+            }
+        }
+        Ok(Some(accept))
+    }
+
+    /// Push a directive into the list of entries.
+    pub fn push(&mut self, prop: impl Into<MediaTypeProposal>) {
+        self.entries.push(prop.into());
+    }
+
+    /// Sort the entries in-place, from most to least preferred.
+    ///
+    /// Entries are ordered by weight, and entries of the same weight are
+    /// ordered from most to least specific: `text/html;level=1` before
+    /// `text/html` before `text/*` before `*/*`.
+    pub fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.quality()
+                .partial_cmp(&a.quality())
+                .unwrap()
+                .then_with(|| b.specificity().cmp(&a.specificity()))
+        });
+    }
+
+    /// Get the weight the client gives to a media type, if it's acceptable at all.
+    ///
+    /// The weight is taken from the most specific media range which matches
+    /// the media type.
+    pub fn quality_of(&self, mime: &Mime) -> Option<f32> {
+        self.entries
+            .iter()
+            .filter(|proposal| proposal.matches(mime))
+            .max_by_key(|proposal| proposal.specificity())
+            .map(|proposal| proposal.quality())
+    }
+
+    /// Determine the most suitable `Content-Type` encoding.
+    ///
+    /// The available media types are expected to be in the server's order of
+    /// preference, which is used to break ties between equally weighted types.
+    ///
+    /// # Errors
+    ///
+    /// If no suitable media type is found, an error with the status of `406` will be returned.
+    pub fn negotiate(&self, available: &[Mime]) -> crate::Result<Mime> {
+        let mut best: Option<(&Mime, f32)> = None;
+        for mime in available {
+            let quality = match self.quality_of(mime) {
+                Some(quality) if quality > 0.0 => quality,
+                _ => continue,
+            };
+            match best {
+                Some((_, best_quality)) if best_quality >= quality => {}
+                _ => best = Some((mime, quality)),
+            }
+        }
+
+        match best {
+            Some((mime, _)) => Ok(mime.clone()),
+            None => Err(Error::from_str(
+                StatusCode::NotAcceptable,
+                "No suitable Content-Type found",
+            )),
+        }
+    }
+
+    /// Sets the `Accept` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Media types should be valid ASCII")
+    }
+
+    /// An iterator visiting all entries.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+}
+
+impl Display for Accept {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, proposal) in self.entries.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", proposal)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Accept {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = split_list(s)
+            .into_iter()
+            .map(|part| part.parse())
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { entries })
+    }
+}
+
+impl IntoIterator for Accept {
+    type Item = MediaTypeProposal;
+    type IntoIter = std::vec::IntoIter<MediaTypeProposal>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Accept {
+    type Item = &'a MediaTypeProposal;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `Accept`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, MediaTypeProposal>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a MediaTypeProposal;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mime;
+    use crate::Response;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut accept = Accept::new();
+        accept.push(mime::HTML);
+
+        let mut headers = Response::new(200);
+        accept.apply(&mut headers);
+
+        let accept = Accept::from_headers(headers)?.unwrap();
+        assert_eq!(accept.iter().next().unwrap(), mime::HTML);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rfc_example() -> crate::Result<()> {
+        let mut headers = Response::new(200);
+        headers.insert_header(
+            "Accept",
+            "text/*;q=0.3, text/html;q=0.7, text/html;level=1, text/html;level=2;q=0.4, */*;q=0.5",
+        );
+        let accept = Accept::from_headers(headers)?.unwrap();
+
+        // Example from RFC7231, section 5.3.2.
+        for (mime, quality) in &[
+            ("text/html;level=1", 1.0),
+            ("text/html", 0.7),
+            ("text/plain", 0.3),
+            ("image/jpeg", 0.5),
+            ("text/html;level=2", 0.4),
+            ("text/html;level=3", 0.7),
+        ] {
+            let mime: Mime = mime.parse()?;
+            assert_eq!(accept.quality_of(&mime), Some(*quality), "{}", mime);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sort_by_weight_and_specificity() -> crate::Result<()> {
+        let mut accept: Accept = "*/*;q=0.8, text/*, text/html;level=1, text/html".parse()?;
+        accept.sort();
+        assert_eq!(
+            accept.to_string(),
+            "text/html;level=1, text/html, text/*, */*;q=0.8"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate() -> crate::Result<()> {
+        let accept: Accept = "text/html, application/json;q=0.9".parse()?;
+        assert_eq!(accept.negotiate(&[mime::JSON, mime::HTML])?, mime::HTML);
+        assert_eq!(accept.negotiate(&[mime::JSON, mime::XML])?, mime::JSON);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_prefers_server_order_on_ties() -> crate::Result<()> {
+        let accept: Accept = "*/*".parse()?;
+        assert_eq!(accept.negotiate(&[mime::JSON, mime::HTML])?, mime::JSON);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_ignores_zero_weights() -> crate::Result<()> {
+        let accept: Accept = "text/*, text/plain;q=0".parse()?;
+        assert_eq!(accept.negotiate(&[mime::PLAIN, mime::CSS])?, mime::CSS);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_not_acceptable() -> crate::Result<()> {
+        let accept: Accept = "text/html".parse()?;
+        let err = accept.negotiate(&[mime::JSON]).unwrap_err();
+        assert_eq!(err.status(), 406);
+
+        let accept = Accept::new();
+        let err = accept.negotiate(&[mime::JSON]).unwrap_err();
+        assert_eq!(err.status(), 406);
+        Ok(())
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::content::{ContentEncoding, EncodingProposal};
+use crate::headers::{HeaderName, HeaderValue, Headers, ACCEPT_ENCODING};
+use crate::parse_utils::split_list;
+use crate::{Error, StatusCode};
+
+/// Client header advertising which content codings the client is able to
+/// understand.
+///
+/// Using content negotiation, the server then selects one of the proposals, uses
+/// it and informs the client of its choice with the `Content-Encoding` response
+/// header.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 5.3.4: Accept-Encoding](https://tools.ietf.org/html/rfc7231#section-5.3.4)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{AcceptEncoding, ContentEncoding, EncodingProposal};
+/// use http_types::Response;
+///
+/// let mut accept = AcceptEncoding::new();
+/// accept.push(EncodingProposal::new(ContentEncoding::Brotli, Some(0.8))?);
+/// accept.push(EncodingProposal::new(ContentEncoding::Gzip, Some(0.4))?);
+/// accept.push(ContentEncoding::Identity);
+///
+/// let mut res = Response::new(200);
+/// let encoding = accept.negotiate(&[ContentEncoding::Brotli, ContentEncoding::Gzip])?;
+/// encoding.apply(&mut res);
+///
+/// assert_eq!(res["Content-Encoding"], "br");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AcceptEncoding {
+    entries: Vec<EncodingProposal>,
+}
+
+impl AcceptEncoding {
+    /// Create a new instance of `AcceptEncoding`.
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Create an instance of `AcceptEncoding` from a `Headers` instance.
+    ///
+    /// All `Accept-Encoding` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(ACCEPT_ENCODING) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in values {
+            for part in split_list(value.as_str()) {
+                accept.push(part.parse::<EncodingProposal>()?);
+            }
+        }
+        Ok(Some(accept))
+    }
+
+    /// Push a directive into the list of entries.
+    pub fn push(&mut self, prop: impl Into<EncodingProposal>) {
+        self.entries.push(prop.into());
+    }
+
+    /// Sort the entries in-place, from most to least preferred.
+    ///
+    /// Entries are ordered by weight, and the `*` wildcard is placed after
+    /// explicit codings of the same weight.
+    pub fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.quality()
+                .partial_cmp(&a.quality())
+                .unwrap()
+                .then_with(|| a.is_wildcard().cmp(&b.is_wildcard()))
+        });
+    }
+
+    /// Get the weight the client gives to a content coding, if it's
+    /// acceptable at all.
+    ///
+    /// An explicit entry for the coding takes precedence over `*`. The
+    /// `identity` coding is acceptable by default, unless it is excluded with
+    /// `identity;q=0` or `*;q=0`.
+    pub fn quality_of(&self, encoding: &ContentEncoding) -> Option<f32> {
+        let explicit = self.entries.iter().find(|proposal| *proposal == *encoding);
+        let wildcard = self.entries.iter().find(|proposal| proposal.is_wildcard());
+        match explicit.or(wildcard) {
+            Some(proposal) => Some(proposal.quality()),
+            None if *encoding == ContentEncoding::Identity => Some(1.0),
+            None => None,
+        }
+    }
+
+    /// Determine the most suitable `Content-Encoding` encoding.
+    ///
+    /// The available encodings are expected to be in the server's order of
+    /// preference, which is used to break ties between equally weighted
+    /// encodings. When none of them is acceptable, `identity` is returned if
+    /// the client accepts it.
+    ///
+    /// # Errors
+    ///
+    /// If no suitable encoding is found, an error with the status of `406` will be returned.
+    pub fn negotiate(&self, available: &[ContentEncoding]) -> crate::Result<ContentEncoding> {
+        let mut best: Option<(&ContentEncoding, f32)> = None;
+        for encoding in available {
+            let quality = match self.quality_of(encoding) {
+                Some(quality) if quality > 0.0 => quality,
+                _ => continue,
+            };
+            match best {
+                Some((_, best_quality)) if best_quality >= quality => {}
+                _ => best = Some((encoding, quality)),
+            }
+        }
+
+        match best {
+            Some((encoding, _)) => Ok(encoding.clone()),
+            None => match self.quality_of(&ContentEncoding::Identity) {
+                Some(quality) if quality > 0.0 => Ok(ContentEncoding::Identity),
+                _ => Err(Error::from_str(
+                    StatusCode::NotAcceptable,
+                    "No suitable Content-Encoding found",
+                )),
+            },
+        }
+    }
+
+    /// Sets the `Accept-Encoding` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT_ENCODING
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Content codings should be valid ASCII")
+    }
+
+    /// An iterator visiting all entries.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+}
+
+impl Display for AcceptEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, proposal) in self.entries.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", proposal)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AcceptEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = split_list(s)
+            .into_iter()
+            .map(|part| part.parse())
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { entries })
+    }
+}
+
+impl IntoIterator for AcceptEncoding {
+    type Item = EncodingProposal;
+    type IntoIter = std::vec::IntoIter<EncodingProposal>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AcceptEncoding {
+    type Item = &'a EncodingProposal;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `AcceptEncoding`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, EncodingProposal>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a EncodingProposal;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response;
+
+    use ContentEncoding::{Brotli, Deflate, Gzip, Identity, Zstd};
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut accept = AcceptEncoding::new();
+        accept.push(Gzip);
+
+        let mut headers = Response::new(200);
+        accept.apply(&mut headers);
+
+        let accept = AcceptEncoding::from_headers(headers)?.unwrap();
+        assert_eq!(accept.iter().next().unwrap(), Gzip);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rfc_examples() -> crate::Result<()> {
+        let accept: AcceptEncoding = "gzip;q=1.0, identity; q=0.5, *;q=0".parse()?;
+        assert_eq!(accept.quality_of(&Gzip), Some(1.0));
+        assert_eq!(accept.quality_of(&Identity), Some(0.5));
+        assert_eq!(accept.quality_of(&Brotli), Some(0.0));
+
+        let accept: AcceptEncoding = "compress, gzip".parse()?;
+        assert_eq!(accept.quality_of(&Gzip), Some(1.0));
+        assert_eq!(accept.quality_of(&Brotli), None);
+        assert_eq!(accept.quality_of(&Identity), Some(1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn sort_by_weight() -> crate::Result<()> {
+        let mut accept: AcceptEncoding = "*, gzip;q=0.5, br".parse()?;
+        accept.sort();
+        assert_eq!(accept.to_string(), "br, *, gzip;q=0.5");
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate() -> crate::Result<()> {
+        let accept: AcceptEncoding = "gzip;q=0.8, br".parse()?;
+        assert_eq!(accept.negotiate(&[Gzip, Brotli])?, Brotli);
+        assert_eq!(accept.negotiate(&[Gzip, Deflate])?, Gzip);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_prefers_server_order_on_ties() -> crate::Result<()> {
+        let accept: AcceptEncoding = "*".parse()?;
+        assert_eq!(accept.negotiate(&[Zstd, Gzip])?, Zstd);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_falls_back_to_identity() -> crate::Result<()> {
+        let accept: AcceptEncoding = "gzip".parse()?;
+        assert_eq!(accept.negotiate(&[Brotli])?, Identity);
+
+        let accept = AcceptEncoding::new();
+        assert_eq!(accept.negotiate(&[Gzip])?, Identity);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_not_acceptable() -> crate::Result<()> {
+        let accept: AcceptEncoding = "gzip, identity;q=0".parse()?;
+        let err = accept.negotiate(&[Brotli]).unwrap_err();
+        assert_eq!(err.status(), 406);
+
+        let accept: AcceptEncoding = "*;q=0".parse()?;
+        let err = accept.negotiate(&[Gzip]).unwrap_err();
+        assert_eq!(err.status(), 406);
+        Ok(())
+    }
+}
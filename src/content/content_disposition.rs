@@ -0,0 +1,333 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::content::DispositionType;
+use crate::headers::{HeaderName, HeaderValue, Headers, CONTENT_DISPOSITION};
+use crate::parse_utils::{
+    fmt_ascii_fallback, fmt_ext_value, fmt_quoted_string, fmt_token_or_quoted_string,
+    is_printable_ascii, is_token, parse_ext_value, parse_param, split_outside_quotes,
+};
+use crate::{Error, StatusCode};
+
+/// How content should be presented, and the name it should be saved under.
+///
+/// The header is used both in responses, and for the parts of
+/// `multipart/form-data` bodies. Parameters which aren't printable ASCII are
+/// sent in their RFC 5987 `*` form, such as `filename*`. Field names and
+/// filenames also get an ASCII fallback for older recipients.
+///
+/// # Specifications
+///
+/// - [RFC6266: Use of the Content-Disposition Header Field in HTTP](https://tools.ietf.org/html/rfc6266)
+/// - [RFC5987: Character Set and Language Encoding for HTTP Header Field Parameters](https://tools.ietf.org/html/rfc5987)
+/// - [RFC7578, section 4.2: Content-Disposition Header Field for Each Part](https://tools.ietf.org/html/rfc7578#section-4.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{ContentDisposition, DispositionType};
+/// use http_types::Response;
+///
+/// let mut disposition = ContentDisposition::new(DispositionType::Attachment);
+/// disposition.set_filename("€ rates.pdf");
+///
+/// let mut res = Response::new(200);
+/// disposition.apply(&mut res);
+/// assert_eq!(
+///     res["Content-Disposition"],
+///     r#"attachment; filename="_ rates.pdf"; filename*=UTF-8''%E2%82%AC%20rates.pdf"#
+/// );
+///
+/// let disposition = ContentDisposition::from_headers(res)?.unwrap();
+/// assert_eq!(disposition.filename(), Some("€ rates.pdf"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    disposition: DispositionType,
+    name: Option<String>,
+    filename: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl ContentDisposition {
+    /// Create a new instance of `ContentDisposition`.
+    pub fn new(disposition: DispositionType) -> Self {
+        Self {
+            disposition,
+            name: None,
+            filename: None,
+            params: vec![],
+        }
+    }
+
+    /// Create a new instance from headers.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let value = match headers.as_ref().get(CONTENT_DISPOSITION) {
+            Some(values) => values.last(),
+            None => return Ok(None),
+        };
+        Ok(Some(value.as_str().parse()?))
+    }
+
+    /// Sets the `Content-Disposition` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_DISPOSITION
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Content-Disposition should be valid ASCII")
+    }
+
+    /// Get the disposition type.
+    pub fn disposition(&self) -> &DispositionType {
+        &self.disposition
+    }
+
+    /// Set the disposition type.
+    pub fn set_disposition(&mut self, disposition: DispositionType) {
+        self.disposition = disposition;
+    }
+
+    /// Returns `true` if the content should be downloaded rather than
+    /// displayed.
+    ///
+    /// Unknown disposition types are treated as `attachment`.
+    pub fn is_attachment(&self) -> bool {
+        !matches!(
+            self.disposition,
+            DispositionType::Inline | DispositionType::FormData
+        )
+    }
+
+    /// Get the name of the `multipart/form-data` field.
+    ///
+    /// When both are present, `name*` is preferred over `name`.
+    pub fn field_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Set the name of the `multipart/form-data` field.
+    pub fn set_field_name(&mut self, name: impl Into<String>) {
+        self.name = Some(name.into());
+    }
+
+    /// Get the suggested filename.
+    ///
+    /// When both are present, `filename*` is preferred over `filename`.
+    /// Recipients should not trust the filename to be a safe path.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Set the suggested filename.
+    pub fn set_filename(&mut self, filename: impl Into<String>) {
+        self.filename = Some(filename.into());
+    }
+
+    /// Get an extension parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set an extension parameter, replacing any previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name isn't a valid token.
+    pub fn set_param(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into().to_ascii_lowercase();
+        assert!(
+            is_token(&name),
+            "Content-Disposition parameter names must be tokens"
+        );
+        let value = value.into();
+        match self.params.iter_mut().find(|(n, _)| *n == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name, value)),
+        }
+    }
+}
+
+impl Display for ContentDisposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.disposition)?;
+        if let Some(name) = &self.name {
+            fmt_with_fallback(f, "name", name)?;
+        }
+        if let Some(filename) = &self.filename {
+            fmt_with_fallback(f, "filename", filename)?;
+        }
+        for (name, value) in &self.params {
+            if is_printable_ascii(value) {
+                write!(f, "; {}={}", name, fmt_token_or_quoted_string(value))?;
+            } else {
+                write!(f, "; {}*={}", name, fmt_ext_value(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write a quoted parameter. Values which aren't printable ASCII are written
+/// with an ASCII fallback, followed by their `*` form.
+fn fmt_with_fallback(f: &mut fmt::Formatter<'_>, name: &str, value: &str) -> fmt::Result {
+    if is_printable_ascii(value) {
+        return write!(f, "; {}={}", name, fmt_quoted_string(value));
+    }
+    let fallback = fmt_ascii_fallback(value);
+    write!(f, "; {}={}", name, fmt_quoted_string(&fallback))?;
+    write!(f, "; {}*={}", name, fmt_ext_value(value))
+}
+
+impl FromStr for ContentDisposition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Content-Disposition", s),
+            )
+        };
+
+        let mut parts = split_outside_quotes(s, ';').into_iter();
+        let disposition = parts.next().ok_or_else(invalid)?.parse()?;
+        let mut output = Self::new(disposition);
+
+        // Parameters in their `*` form take precedence, so they're applied
+        // last. Unsupported charsets leave the plain parameter in place.
+        let mut ext_params = vec![];
+        for param in parts {
+            let (name, value) = parse_param(param).ok_or_else(invalid)?;
+            let name = name.to_ascii_lowercase();
+            match name.as_str() {
+                _ if name.len() > 1 && name.ends_with('*') => {
+                    if let Some(value) = parse_ext_value(&value) {
+                        ext_params.push((name[..name.len() - 1].to_string(), value));
+                    }
+                }
+                "name" if output.name.is_none() => output.name = Some(value),
+                "filename" if output.filename.is_none() => output.filename = Some(value),
+                "name" | "filename" => {}
+                _ if output.param(&name).is_none() => output.params.push((name, value)),
+                _ => {}
+            }
+        }
+
+        let mut seen = vec![];
+        for (name, value) in ext_params {
+            if seen.contains(&name) {
+                continue;
+            }
+            match name.as_str() {
+                "name" => output.name = Some(value),
+                "filename" => output.filename = Some(value),
+                _ => output.set_param(name.as_str(), value),
+            }
+            seen.push(name);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let disposition: ContentDisposition =
+            "Attachment; FILENAME*=UTF-8''na%C3%AFve.txt; filename=\"naive.txt\"; size=42"
+                .parse()?;
+        assert_eq!(disposition.disposition(), &DispositionType::Attachment);
+        assert!(disposition.is_attachment());
+        assert_eq!(disposition.filename(), Some("naïve.txt"));
+        assert_eq!(disposition.param("Size"), Some("42"));
+
+        let disposition: ContentDisposition =
+            "form-data; name=\"avatar\"; filename=\"a \\\"b\\\".png\"".parse()?;
+        assert_eq!(disposition.disposition(), &DispositionType::FormData);
+        assert!(!disposition.is_attachment());
+        assert_eq!(disposition.field_name(), Some("avatar"));
+        assert_eq!(disposition.filename(), Some("a \"b\".png"));
+
+        let disposition: ContentDisposition =
+            "attachment; filename=plain.txt; filename*=UTF-16''x".parse()?;
+        assert_eq!(disposition.filename(), Some("plain.txt"));
+
+        let disposition: ContentDisposition = "x-custom".parse()?;
+        assert_eq!(
+            disposition.disposition(),
+            &DispositionType::Custom("x-custom".into())
+        );
+        assert!(disposition.is_attachment());
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        let mut disposition = ContentDisposition::new(DispositionType::FormData);
+        disposition.set_field_name("file");
+        disposition.set_filename("Résumé\r\n.pdf");
+        disposition.set_param("creation-date", "Wed, 12 Feb 1997 16:29:51 -0500");
+
+        let mut headers = Headers::new();
+        disposition.apply(&mut headers);
+        assert_eq!(
+            headers["Content-Disposition"],
+            "form-data; name=\"file\"; filename=\"R_sum___.pdf\"; \
+             filename*=UTF-8''R%C3%A9sum%C3%A9%0D%0A.pdf; \
+             creation-date=\"Wed, 12 Feb 1997 16:29:51 -0500\""
+        );
+        assert_eq!(
+            ContentDisposition::from_headers(headers)?.unwrap(),
+            disposition
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_params() -> crate::Result<()> {
+        let mut disposition = ContentDisposition::new(DispositionType::FormData);
+        disposition.set_field_name("prénom");
+        disposition.set_param("x-note", "naïve");
+
+        let mut headers = Headers::new();
+        disposition.apply(&mut headers);
+        assert_eq!(
+            headers["Content-Disposition"],
+            "form-data; name=\"pr_nom\"; name*=UTF-8''pr%C3%A9nom; x-note*=UTF-8''na%C3%AFve"
+        );
+        assert_eq!(
+            ContentDisposition::from_headers(headers)?.unwrap(),
+            disposition
+        );
+
+        let disposition: ContentDisposition =
+            "inline; x-note*=UTF-8''%C3%A9; x-note=plain; x-note*=UTF-8''ignored".parse()?;
+        assert_eq!(disposition.param("x-note"), Some("é"));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "in line", "attachment; filename=\"a", "attachment; =b"] {
+            let err = s.parse::<ContentDisposition>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::content::AcceptEncoding;
+use crate::headers::{HeaderName, HeaderValue, Headers, CONTENT_ENCODING};
+use crate::parse_utils::{is_token, split_list};
+use crate::{Error, StatusCode};
+
+/// A content coding, as used by the `Content-Encoding` and `Accept-Encoding`
+/// headers.
+///
+/// Content codings are compared case-insensitively, and the `x-gzip` alias
+/// is recognized as `gzip`.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 3.1.2.2: Content-Encoding](https://tools.ietf.org/html/rfc7231#section-3.1.2.2)
+/// - [IANA HTTP Content Coding Registry](https://www.iana.org/assignments/http-parameters/http-parameters.xhtml#content-coding)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::content::ContentEncoding;
+///
+/// let mut res = Response::new(200);
+/// ContentEncoding::Gzip.apply(&mut res);
+/// assert_eq!(res["Content-Encoding"], "gzip");
+///
+/// let encoding = ContentEncoding::from_headers(res)?.unwrap();
+/// assert_eq!(encoding, ContentEncoding::Gzip);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    /// No transformation is used.
+    Identity,
+    /// The Gzip encoding.
+    Gzip,
+    /// The Deflate encoding.
+    Deflate,
+    /// The Brotli encoding.
+    Brotli,
+    /// The Zstd encoding.
+    Zstd,
+    /// A content coding not covered by the other variants, stored lowercase.
+    Custom(String),
+}
+
+impl ContentEncoding {
+    /// Create a new instance from the `Content-Encoding` header.
+    ///
+    /// When multiple codings have been applied, the last one is returned: this
+    /// is the coding which needs to be removed first.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Ok(Self::all_from_headers(headers)?.pop())
+    }
+
+    /// Create a list of all codings from the `Content-Encoding` header, in
+    /// the order they were applied.
+    pub fn all_from_headers(headers: impl AsRef<Headers>) -> crate::Result<Vec<Self>> {
+        let mut encodings = vec![];
+        if let Some(values) = headers.as_ref().get(CONTENT_ENCODING) {
+            for value in values {
+                for encoding in split_list(value.as_str()) {
+                    encodings.push(encoding.parse()?);
+                }
+            }
+        }
+        Ok(encodings)
+    }
+
+    /// Choose the encoding a response should use, given the request headers.
+    ///
+    /// The available encodings are expected to be in the server's order of
+    /// preference. When the request has no `Accept-Encoding` header,
+    /// `identity` is chosen.
+    ///
+    /// # Errors
+    ///
+    /// If no suitable encoding is found, an error with the status of `406` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> http_types::Result<()> {
+    /// #
+    /// use http_types::content::ContentEncoding;
+    /// use http_types::{Method, Request, Response, Url};
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.insert_header("Accept-Encoding", "gzip;q=0.8, br");
+    ///
+    /// let available = [ContentEncoding::Gzip, ContentEncoding::Brotli];
+    /// let encoding = ContentEncoding::negotiate(&req, &available)?;
+    ///
+    /// let mut res = Response::new(200);
+    /// encoding.apply(&mut res);
+    /// assert_eq!(res["Content-Encoding"], "br");
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn negotiate(headers: impl AsRef<Headers>, available: &[Self]) -> crate::Result<Self> {
+        match AcceptEncoding::from_headers(headers)? {
+            Some(accept) => accept.negotiate(available),
+            None => Ok(Self::Identity),
+        }
+    }
+
+    /// Sets the `Content-Encoding` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_ENCODING
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `Custom` coding contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Content codings should be valid ASCII")
+    }
+}
+
+impl Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Identity => write!(f, "identity"),
+            Self::Gzip => write!(f, "gzip"),
+            Self::Deflate => write!(f, "deflate"),
+            Self::Brotli => write!(f, "br"),
+            Self::Zstd => write!(f, "zstd"),
+            Self::Custom(encoding) => write!(f, "{}", encoding),
+        }
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !is_token(s) {
+            return Err(Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid content coding", s),
+            ));
+        }
+
+        // NOTE: content codings are case-insensitive.
+        let encoding = match s.to_ascii_lowercase().as_str() {
+            "identity" => Self::Identity,
+            "gzip" | "x-gzip" => Self::Gzip,
+            "deflate" => Self::Deflate,
+            "br" => Self::Brotli,
+            "zstd" => Self::Zstd,
+            s => Self::Custom(s.to_string()),
+        };
+        Ok(encoding)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        ContentEncoding::Brotli.apply(&mut headers);
+
+        let encoding = ContentEncoding::from_headers(headers)?.unwrap();
+        assert_eq!(encoding, ContentEncoding::Brotli);
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!("GZIP".parse::<ContentEncoding>()?, ContentEncoding::Gzip);
+        assert_eq!("x-gzip".parse::<ContentEncoding>()?, ContentEncoding::Gzip);
+        assert_eq!("zstd".parse::<ContentEncoding>()?, ContentEncoding::Zstd);
+        assert_eq!(
+            "Compress".parse::<ContentEncoding>()?,
+            ContentEncoding::Custom("compress".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_codings() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert("Content-Encoding", "deflate, gzip");
+        assert_eq!(
+            ContentEncoding::all_from_headers(&headers)?,
+            vec![ContentEncoding::Deflate, ContentEncoding::Gzip]
+        );
+        assert_eq!(
+            ContentEncoding::from_headers(&headers)?,
+            Some(ContentEncoding::Gzip)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate() -> crate::Result<()> {
+        let available = [ContentEncoding::Brotli, ContentEncoding::Gzip];
+
+        let headers = Headers::new();
+        let encoding = ContentEncoding::negotiate(&headers, &available)?;
+        assert_eq!(encoding, ContentEncoding::Identity);
+
+        let mut headers = Headers::new();
+        headers.insert("Accept-Encoding", "gzip, deflate");
+        let encoding = ContentEncoding::negotiate(&headers, &available)?;
+        assert_eq!(encoding, ContentEncoding::Gzip);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Content-Encoding", "not/valid");
+        let err = ContentEncoding::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
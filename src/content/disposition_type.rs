@@ -0,0 +1,55 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::parse_utils::is_token;
+use crate::{Error, StatusCode};
+
+/// The disposition type of the `Content-Disposition` header.
+///
+/// Disposition types are compared case-insensitively.
+///
+/// # Specifications
+///
+/// - [RFC6266, section 4.2: Disposition Type](https://tools.ietf.org/html/rfc6266#section-4.2)
+/// - [RFC7578, section 4.2: Content-Disposition Header Field for Each Part](https://tools.ietf.org/html/rfc7578#section-4.2)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DispositionType {
+    /// The content is displayed as part of a page, or as the page.
+    Inline,
+    /// The content is downloaded, and saved locally.
+    Attachment,
+    /// The content is a field of a `multipart/form-data` body.
+    FormData,
+    /// A disposition type not covered by the other variants, stored
+    /// lowercase. Recipients treat unknown types as `attachment`.
+    Custom(String),
+}
+
+impl Display for DispositionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inline => write!(f, "inline"),
+            Self::Attachment => write!(f, "attachment"),
+            Self::FormData => write!(f, "form-data"),
+            Self::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for DispositionType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        match s.as_str() {
+            "inline" => Ok(Self::Inline),
+            "attachment" => Ok(Self::Attachment),
+            "form-data" => Ok(Self::FormData),
+            _ if is_token(&s) => Ok(Self::Custom(s)),
+            _ => Err(Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid disposition type", s),
+            )),
+        }
+    }
+}
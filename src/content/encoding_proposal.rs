@@ -0,0 +1,201 @@
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::content::{fmt_weight, parse_weight, ContentEncoding};
+use crate::parse_utils::{parse_token, trim_ows};
+use crate::{Error, StatusCode};
+
+/// A proposed content coding for the `Accept-Encoding` header.
+///
+/// A coding of `None` represents the `*` wildcard, which matches any coding
+/// not explicitly listed.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 5.3.4: Accept-Encoding](https://tools.ietf.org/html/rfc7231#section-5.3.4)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingProposal {
+    /// The proposed encoding, or `None` for `*`.
+    encoding: Option<ContentEncoding>,
+
+    /// The weight of the proposal.
+    ///
+    /// This is a number between 0.0 and 1.0, and is max 3 decimal points.
+    weight: Option<f32>,
+}
+
+impl EncodingProposal {
+    /// Create a new instance of `EncodingProposal`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0.0 and 1.0.
+    pub fn new(encoding: impl Into<ContentEncoding>, weight: Option<f32>) -> crate::Result<Self> {
+        Self::with_encoding(Some(encoding.into()), weight)
+    }
+
+    /// Create a new wildcard (`*`) proposal.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0.0 and 1.0.
+    pub fn wildcard(weight: Option<f32>) -> crate::Result<Self> {
+        Self::with_encoding(None, weight)
+    }
+
+    fn with_encoding(
+        encoding: Option<ContentEncoding>,
+        weight: Option<f32>,
+    ) -> crate::Result<Self> {
+        if let Some(weight) = weight {
+            crate::ensure!(
+                (0.0..=1.0).contains(&weight),
+                "EncodingProposal should have a weight between 0.0 and 1.0"
+            )
+        }
+
+        Ok(Self { encoding, weight })
+    }
+
+    /// Get the proposed encoding, or `None` if this is the `*` wildcard.
+    pub fn encoding(&self) -> Option<&ContentEncoding> {
+        self.encoding.as_ref()
+    }
+
+    /// Returns `true` if this is the `*` wildcard.
+    pub fn is_wildcard(&self) -> bool {
+        self.encoding.is_none()
+    }
+
+    /// Get the weight of the proposal.
+    pub fn weight(&self) -> Option<f32> {
+        self.weight
+    }
+
+    /// The weight of the proposal, defaulting to 1.0 when it wasn't set.
+    pub(crate) fn quality(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+}
+
+impl From<ContentEncoding> for EncodingProposal {
+    fn from(encoding: ContentEncoding) -> Self {
+        Self {
+            encoding: Some(encoding),
+            weight: None,
+        }
+    }
+}
+
+impl PartialEq<ContentEncoding> for EncodingProposal {
+    fn eq(&self, other: &ContentEncoding) -> bool {
+        self.encoding.as_ref() == Some(other)
+    }
+}
+
+impl PartialEq<ContentEncoding> for &EncodingProposal {
+    fn eq(&self, other: &ContentEncoding) -> bool {
+        self.encoding.as_ref() == Some(other)
+    }
+}
+
+impl Deref for EncodingProposal {
+    type Target = Option<ContentEncoding>;
+    fn deref(&self) -> &Self::Target {
+        &self.encoding
+    }
+}
+
+impl Display for EncodingProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.encoding {
+            Some(encoding) => write!(f, "{}", encoding)?,
+            None => write!(f, "*")?,
+        }
+        if let Some(weight) = self.weight {
+            write!(f, ";q={}", fmt_weight(weight))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EncodingProposal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid encoding proposal", s),
+            )
+        };
+
+        let (coding, rest) = match parse_token(trim_ows(s)) {
+            (Some(coding), rest) => (coding, trim_ows(rest)),
+            (None, _) => return Err(invalid()),
+        };
+        let encoding = match coding {
+            "*" => None,
+            coding => Some(coding.parse()?),
+        };
+
+        let weight = if rest.is_empty() {
+            None
+        } else {
+            let param = trim_ows(rest.strip_prefix(';').ok_or_else(invalid)?);
+            let mut parts = param.splitn(2, '=');
+            match (parts.next().map(trim_ows), parts.next().map(trim_ows)) {
+                (Some(name), Some(value)) if name.eq_ignore_ascii_case("q") => {
+                    Some(parse_weight(value)?)
+                }
+                _ => return Err(invalid()),
+            }
+        };
+
+        Ok(Self { encoding, weight })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let _ = EncodingProposal::new(ContentEncoding::Gzip, Some(0.0))?;
+        let _ = EncodingProposal::new(ContentEncoding::Brotli, Some(0.5))?;
+        let _ = EncodingProposal::wildcard(Some(1.0))?;
+        Ok(())
+    }
+
+    #[test]
+    fn error_code_500() {
+        let err = EncodingProposal::new(ContentEncoding::Gzip, Some(1.1)).unwrap_err();
+        assert_eq!(err.status(), 500);
+
+        let err = EncodingProposal::wildcard(Some(-0.1)).unwrap_err();
+        assert_eq!(err.status(), 500);
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let proposal: EncodingProposal = "gzip;q=0.5".parse()?;
+        assert_eq!(proposal, ContentEncoding::Gzip);
+        assert_eq!(proposal.weight(), Some(0.5));
+
+        let proposal: EncodingProposal = "* ; Q=0".parse()?;
+        assert!(proposal.is_wildcard());
+        assert_eq!(proposal.weight(), Some(0.0));
+        assert_eq!(proposal.to_string(), "*;q=0");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "gzip;", "gzip;level=1", "gzip;q=2", "gzip q=1"] {
+            let err = s.parse::<EncodingProposal>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::content::{fmt_weight, parse_weight};
+use crate::mime::ParamKind;
+use crate::{Error, Mime, StatusCode};
+
+/// A proposed Media Type for the `Accept` header, also known as a media range.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 5.3.2: Accept](https://tools.ietf.org/html/rfc7231#section-5.3.2)
+#[derive(Debug, Clone)]
+pub struct MediaTypeProposal {
+    /// The proposed media type.
+    pub(crate) media_type: Mime,
+
+    /// The weight of the proposal.
+    ///
+    /// This is a number between 0.0 and 1.0, and is max 3 decimal points.
+    weight: Option<f32>,
+}
+
+impl MediaTypeProposal {
+    /// Create a new instance of `MediaTypeProposal`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0.0 and 1.0.
+    pub fn new(media_type: impl Into<Mime>, weight: Option<f32>) -> crate::Result<Self> {
+        if let Some(weight) = weight {
+            crate::ensure!(
+                (0.0..=1.0).contains(&weight),
+                "MediaTypeProposal should have a weight between 0.0 and 1.0"
+            )
+        }
+
+        Ok(Self {
+            media_type: media_type.into(),
+            weight,
+        })
+    }
+
+    /// Get the proposed media type.
+    pub fn media_type(&self) -> &Mime {
+        &self.media_type
+    }
+
+    /// Get the weight of the proposal.
+    pub fn weight(&self) -> Option<f32> {
+        self.weight
+    }
+
+    /// The weight of the proposal, defaulting to 1.0 when it wasn't set.
+    pub(crate) fn quality(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    /// Returns `true` if the given media type falls within this media range.
+    ///
+    /// Wildcards match any type or subtype, and every parameter of the range
+    /// must be present with the same value in the media type.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        let range = &self.media_type;
+        let basetype = range.basetype() == "*" || range.basetype() == mime.basetype();
+        let subtype = range.subtype() == "*" || range.subtype() == mime.subtype();
+        basetype
+            && subtype
+            && match &range.params {
+                Some(ParamKind::Vec(params)) => params.iter().all(|(name, value)| {
+                    matches!(
+                        mime.param(name.as_str()),
+                        Some(other) if other.as_str().eq_ignore_ascii_case(value.as_str())
+                    )
+                }),
+                Some(ParamKind::Utf8) => matches!(
+                    mime.param("charset"),
+                    Some(charset) if charset == "utf8" || charset.as_str().eq_ignore_ascii_case("utf-8")
+                ),
+                None => true,
+            }
+    }
+
+    /// How specific this media range is, used to pick the range which applies
+    /// to a media type when several of them match.
+    pub(crate) fn specificity(&self) -> usize {
+        let range = &self.media_type;
+        match (range.basetype(), range.subtype()) {
+            ("*", _) => 0,
+            (_, "*") => 1,
+            _ => match &range.params {
+                Some(ParamKind::Vec(params)) => 2 + params.len(),
+                Some(ParamKind::Utf8) => 3,
+                None => 2,
+            },
+        }
+    }
+}
+
+impl From<Mime> for MediaTypeProposal {
+    fn from(media_type: Mime) -> Self {
+        Self {
+            media_type,
+            weight: None,
+        }
+    }
+}
+
+impl From<MediaTypeProposal> for Mime {
+    fn from(proposal: MediaTypeProposal) -> Self {
+        proposal.media_type
+    }
+}
+
+impl PartialEq<Mime> for MediaTypeProposal {
+    fn eq(&self, other: &Mime) -> bool {
+        &self.media_type == other
+    }
+}
+
+impl PartialEq<Mime> for &MediaTypeProposal {
+    fn eq(&self, other: &Mime) -> bool {
+        &self.media_type == other
+    }
+}
+
+impl Deref for MediaTypeProposal {
+    type Target = Mime;
+    fn deref(&self) -> &Self::Target {
+        &self.media_type
+    }
+}
+
+impl Display for MediaTypeProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{};q={}", self.media_type, fmt_weight(weight)),
+            None => write!(f, "{}", self.media_type),
+        }
+    }
+}
+
+impl FromStr for MediaTypeProposal {
+    type Err = Error;
+
+    /// Parse a media range, with an optional `q` parameter.
+    ///
+    /// Any parameters following the `q` parameter are accept extensions, and
+    /// are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut media_type: Mime = s.parse().map_err(|mut err: Error| {
+            err.set_status(StatusCode::BadRequest);
+            err
+        })?;
+
+        let mut weight = None;
+        if let Some(ParamKind::Vec(params)) = &mut media_type.params {
+            if let Some(index) = params.iter().position(|(name, _)| name.as_str() == "q") {
+                weight = Some(parse_weight(params[index].1.as_str())?);
+                params.truncate(index);
+            }
+            if params.is_empty() {
+                media_type.params = None;
+            }
+        }
+
+        Ok(Self { media_type, weight })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mime;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let _ = MediaTypeProposal::new(mime::JSON, Some(0.0))?;
+        let _ = MediaTypeProposal::new(mime::XML, Some(0.5))?;
+        let _ = MediaTypeProposal::new(mime::HTML, Some(1.0))?;
+        Ok(())
+    }
+
+    #[test]
+    fn error_code_500() {
+        let err = MediaTypeProposal::new(mime::JSON, Some(1.1)).unwrap_err();
+        assert_eq!(err.status(), 500);
+
+        let err = MediaTypeProposal::new(mime::JSON, Some(-0.1)).unwrap_err();
+        assert_eq!(err.status(), 500);
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let proposal: MediaTypeProposal = "text/html;level=1;q=0.7;ext=1".parse()?;
+        assert_eq!(proposal.essence(), "text/html");
+        assert_eq!(proposal.param("level").unwrap(), "1");
+        assert!(proposal.param("ext").is_none());
+        assert_eq!(proposal.weight(), Some(0.7));
+        assert_eq!(proposal.to_string(), "text/html;level=1;q=0.7");
+        Ok(())
+    }
+
+    #[test]
+    fn matching() -> crate::Result<()> {
+        let html = mime::HTML;
+        let level_1: Mime = "text/html;level=1".parse()?;
+
+        for (range, mime, expected) in &[
+            ("*/*", &html, true),
+            ("text/*", &html, true),
+            ("image/*", &html, false),
+            ("text/html", &level_1, true),
+            ("text/html;level=1", &level_1, true),
+            ("text/html;level=2", &level_1, false),
+            ("text/html;level=1", &html, false),
+        ] {
+            let proposal: MediaTypeProposal = range.parse()?;
+            assert_eq!(proposal.matches(mime), *expected, "{} {}", range, mime);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["text", "text/html;q=2", "text/html;q=high"] {
+            let err = s.parse::<MediaTypeProposal>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
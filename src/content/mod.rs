@@ -0,0 +1,94 @@
+//! HTTP Content headers.
+//!
+//! These headers are used for "proactive content negotiation": the client
+//! advertises what it is able to understand, and the server picks the best
+//! available representation.
+//!
+//! # Specifications
+//!
+//! - [RFC7231, section 5.3: Content Negotiation](https://tools.ietf.org/html/rfc7231#section-5.3)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::content::Accept;
+//! use http_types::{mime, Method, Request, Response, Url};
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//! req.insert_header("Accept", "text/html, application/json;q=0.9");
+//!
+//! let accept = Accept::from_headers(&req)?.unwrap();
+//! let content_type = accept.negotiate(&[mime::JSON, mime::HTML])?;
+//!
+//! let mut res = Response::new(200);
+//! res.set_content_type(content_type);
+//! assert_eq!(res["Content-Type"], "text/html;charset=utf-8");
+//! #
+//! # Ok(()) }
+//! ```
+
+mod accept;
+mod accept_encoding;
+mod content_disposition;
+mod content_encoding;
+mod disposition_type;
+mod encoding_proposal;
+mod media_type_proposal;
+
+pub use accept::Accept;
+pub use accept_encoding::AcceptEncoding;
+pub use content_disposition::ContentDisposition;
+pub use content_encoding::ContentEncoding;
+pub use disposition_type::DispositionType;
+pub use encoding_proposal::EncodingProposal;
+pub use media_type_proposal::MediaTypeProposal;
+
+use crate::{Error, StatusCode};
+
+/// Parse a [`qvalue`](https://tools.ietf.org/html/rfc7231#section-5.3.1).
+fn parse_weight(s: &str) -> crate::Result<f32> {
+    let valid = match s.find('.') {
+        Some(idx) => idx == 1 && s.len() <= 5 && s[idx + 1..].bytes().all(|b| b.is_ascii_digit()),
+        None => s.len() == 1,
+    };
+    match s.parse::<f32>() {
+        Ok(weight) if valid && (0.0..=1.0).contains(&weight) => Ok(weight),
+        _ => Err(Error::from_str(
+            StatusCode::BadRequest,
+            format!("`{}` is not a valid weight", s),
+        )),
+    }
+}
+
+/// Format a weight as a `qvalue`, with at most 3 decimal points.
+fn fmt_weight(weight: f32) -> String {
+    let output = format!("{:.3}", weight);
+    output
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn weights() -> crate::Result<()> {
+        assert_eq!(parse_weight("1")?, 1.0);
+        assert_eq!(parse_weight("0.5")?, 0.5);
+        assert_eq!(parse_weight("1.000")?, 1.0);
+        assert_eq!(parse_weight("0.")?, 0.0);
+        for s in &["", "2", "1.5", "0.0001", ".5", "-0", "0.a"] {
+            assert!(parse_weight(s).is_err(), "{}", s);
+        }
+
+        assert_eq!(fmt_weight(1.0), "1");
+        assert_eq!(fmt_weight(0.0), "0");
+        assert_eq!(fmt_weight(0.25), "0.25");
+        assert_eq!(fmt_weight(0.1234), "0.123");
+        Ok(())
+    }
+}
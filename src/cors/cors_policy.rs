@@ -0,0 +1,441 @@
+use std::time::Duration;
+
+use crate::headers::{
+    HeaderName, Headers, ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+    ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+    VARY,
+};
+use crate::parse_utils::split_list;
+use crate::{Method, Request};
+
+/// The kind of a request, as seen by a `CorsPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CorsRequest {
+    /// The request has no `Origin` header, so CORS doesn't apply.
+    NotCors,
+    /// An allowed cross-origin request, which should be performed as usual.
+    Simple,
+    /// An allowed preflight request, which should be answered with an empty
+    /// `204 No Content` response.
+    Preflight,
+    /// A cross-origin request which isn't allowed by the policy. Its response
+    /// won't be readable by the client.
+    Disallowed,
+}
+
+/// A Cross-Origin Resource Sharing policy.
+///
+/// # Specifications
+///
+/// - [Fetch Standard, section 3.2: CORS protocol](https://fetch.spec.whatwg.org/#http-cors-protocol)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::cors::{CorsPolicy, CorsRequest};
+/// use http_types::{Method, Request, Response, StatusCode, Url};
+///
+/// let mut policy = CorsPolicy::new();
+/// policy
+///     .allow_origin("https://*.example.com")
+///     .allow_method(Method::Put)
+///     .allow_header("Content-Type");
+///
+/// let mut req = Request::new(Method::Options, Url::parse("https://api.example.com")?);
+/// req.insert_header("Origin", "https://app.example.com");
+/// req.insert_header("Access-Control-Request-Method", "PUT");
+/// assert_eq!(policy.classify(&req), CorsRequest::Preflight);
+///
+/// let mut res = Response::new(StatusCode::NoContent);
+/// policy.apply(&req, &mut res);
+/// assert_eq!(res["Access-Control-Allow-Origin"], "https://app.example.com");
+/// assert_eq!(res["Access-Control-Allow-Methods"], "PUT");
+/// assert_eq!(res["Vary"], "Origin");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsPolicy {
+    origins: Vec<OriginPattern>,
+    methods: Vec<Method>,
+    headers: Vec<HeaderName>,
+    any_header: bool,
+    expose_headers: Vec<HeaderName>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    /// Create a new instance of `CorsPolicy`, which allows no origins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow an origin.
+    ///
+    /// Pass `*` to allow any origin. A single `*` may also be used as a
+    /// wildcard inside an origin, e.g. `https://*.example.com`.
+    pub fn allow_origin<T: AsRef<str>>(&mut self, origin: T) -> &mut Self {
+        self.origins.push(OriginPattern::new(origin.as_ref()));
+        self
+    }
+
+    /// Allow a method in cross-origin requests.
+    ///
+    /// `GET`, `HEAD` and `POST` are always allowed.
+    pub fn allow_method(&mut self, method: Method) -> &mut Self {
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+        self
+    }
+
+    /// Allow a request header in cross-origin requests.
+    ///
+    /// Pass `*` to allow any header.
+    pub fn allow_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        let name = name.into();
+        if name == "*" {
+            self.any_header = true;
+        } else if !self.headers.contains(&name) {
+            self.headers.push(name);
+        }
+        self
+    }
+
+    /// Expose a response header to the client.
+    pub fn expose_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        let name = name.into();
+        if !self.expose_headers.contains(&name) {
+            self.expose_headers.push(name);
+        }
+        self
+    }
+
+    /// Allow requests to include credentials, such as cookies.
+    ///
+    /// The `Origin` of the request is then echoed back instead of `*` when
+    /// any origin is allowed.
+    pub fn allow_credentials(&mut self) -> &mut Self {
+        self.credentials = true;
+        self
+    }
+
+    /// Set how long the result of a preflight request may be cached.
+    pub fn max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns `true` if the origin is allowed.
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        self.origins.iter().any(|pattern| pattern.matches(origin))
+    }
+
+    /// Classify a request according to the policy.
+    pub fn classify(&self, req: &Request) -> CorsRequest {
+        let origin = match req.header(ORIGIN) {
+            Some(origin) => origin.last().as_str(),
+            None => return CorsRequest::NotCors,
+        };
+        if !self.is_origin_allowed(origin) {
+            return CorsRequest::Disallowed;
+        }
+
+        let method = match req.header(ACCESS_CONTROL_REQUEST_METHOD) {
+            Some(method) if req.method() == Method::Options => method.last().as_str(),
+            _ => return CorsRequest::Simple,
+        };
+        let method_allowed = match method.parse::<Method>() {
+            Ok(Method::Get) | Ok(Method::Head) | Ok(Method::Post) => true,
+            Ok(method) => self.methods.contains(&method),
+            Err(_) => false,
+        };
+        let headers_allowed = self.any_header
+            || requested_headers(req)
+                .iter()
+                .all(|name| self.headers.iter().any(|allowed| allowed == name));
+
+        if method_allowed && headers_allowed {
+            CorsRequest::Preflight
+        } else {
+            CorsRequest::Disallowed
+        }
+    }
+
+    /// Set the CORS headers for a response to the request, and return the
+    /// classification of the request.
+    ///
+    /// `Vary: Origin` is appended whenever the headers depend on the origin
+    /// of the request, so caches don't serve them to other origins.
+    pub fn apply(&self, req: &Request, mut headers: impl AsMut<Headers>) -> CorsRequest {
+        let headers = headers.as_mut();
+        let kind = self.classify(req);
+        let any_origin = !self.credentials && self.origins.contains(&OriginPattern::Any);
+        if !any_origin {
+            headers.append(VARY, "Origin");
+        }
+
+        let origin = match (kind, req.header(ORIGIN)) {
+            (CorsRequest::Simple, Some(origin)) | (CorsRequest::Preflight, Some(origin)) => {
+                origin.last().as_str()
+            }
+            _ => return kind,
+        };
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_ORIGIN,
+            if any_origin { "*" } else { origin },
+        );
+        if self.credentials {
+            headers.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+        }
+
+        if kind == CorsRequest::Simple {
+            if !self.expose_headers.is_empty() {
+                headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, join(&self.expose_headers));
+            }
+            return kind;
+        }
+
+        if !self.methods.is_empty() {
+            headers.insert(ACCESS_CONTROL_ALLOW_METHODS, join(&self.methods));
+        }
+        let allow_headers = match self.any_header {
+            true => requested_headers(req),
+            false => self.headers.clone(),
+        };
+        if !allow_headers.is_empty() {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, join(&allow_headers));
+        }
+        if let Some(max_age) = self.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().to_string());
+        }
+        kind
+    }
+}
+
+/// The headers listed in `Access-Control-Request-Headers`.
+fn requested_headers(req: &Request) -> Vec<HeaderName> {
+    let mut names = vec![];
+    if let Some(values) = req.header(ACCESS_CONTROL_REQUEST_HEADERS) {
+        for value in values {
+            for name in split_list(value.as_str()) {
+                if let Ok(name) = name.parse() {
+                    names.push(name);
+                }
+            }
+        }
+    }
+    names
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// An allowed origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginPattern {
+    /// Any origin, `*`.
+    Any,
+    /// A single origin.
+    Exact(String),
+    /// An origin with a wildcard, split around the `*`.
+    Wildcard(String, String),
+}
+
+impl OriginPattern {
+    fn new(origin: &str) -> Self {
+        let origin = origin.trim().trim_end_matches('/').to_ascii_lowercase();
+        if origin == "*" {
+            return Self::Any;
+        }
+        match origin.find('*') {
+            Some(idx) => Self::Wildcard(origin[..idx].to_string(), origin[idx + 1..].to_string()),
+            None => Self::Exact(origin),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        let origin = origin.to_ascii_lowercase();
+        match self {
+            Self::Any => true,
+            Self::Exact(allowed) => *allowed == origin,
+            Self::Wildcard(prefix, suffix) => {
+                origin.len() > prefix.len() + suffix.len()
+                    && origin.starts_with(prefix.as_str())
+                    && origin.ends_with(suffix.as_str())
+                    && !origin[prefix.len()..origin.len() - suffix.len()].contains('/')
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Response, Url};
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(method, Url::parse("https://api.example.com").unwrap());
+        for (name, value) in headers {
+            req.append_header(*name, *value);
+        }
+        req
+    }
+
+    fn policy() -> CorsPolicy {
+        let mut policy = CorsPolicy::new();
+        policy
+            .allow_origin("https://example.com/")
+            .allow_origin("https://*.example.org")
+            .allow_method(Method::Delete)
+            .allow_header("X-Requested-With")
+            .expose_header("X-Request-Id")
+            .max_age(Duration::from_secs(600));
+        policy
+    }
+
+    #[test]
+    fn origins() {
+        let policy = policy();
+        assert!(policy.is_origin_allowed("https://example.com"));
+        assert!(policy.is_origin_allowed("https://APP.example.org"));
+        assert!(!policy.is_origin_allowed("https://example.org"));
+        assert!(!policy.is_origin_allowed("https://evil.com/.example.org"));
+        assert!(!policy.is_origin_allowed("http://example.com"));
+        assert!(!policy.is_origin_allowed("null"));
+    }
+
+    #[test]
+    fn classify() {
+        let policy = policy();
+        let origin = ("Origin", "https://example.com");
+        let delete = ("Access-Control-Request-Method", "DELETE");
+        let put = ("Access-Control-Request-Method", "PUT");
+
+        let cases = [
+            (request(Method::Get, &[]), CorsRequest::NotCors),
+            (request(Method::Get, &[origin]), CorsRequest::Simple),
+            (request(Method::Options, &[origin]), CorsRequest::Simple),
+            (
+                request(Method::Options, &[origin, delete]),
+                CorsRequest::Preflight,
+            ),
+            (
+                request(Method::Options, &[origin, put]),
+                CorsRequest::Disallowed,
+            ),
+            (
+                request(Method::Get, &[("Origin", "https://evil.com")]),
+                CorsRequest::Disallowed,
+            ),
+            (
+                request(
+                    Method::Options,
+                    &[
+                        origin,
+                        delete,
+                        ("Access-Control-Request-Headers", "x-requested-with"),
+                    ],
+                ),
+                CorsRequest::Preflight,
+            ),
+            (
+                request(
+                    Method::Options,
+                    &[
+                        origin,
+                        delete,
+                        ("Access-Control-Request-Headers", "X-Other"),
+                    ],
+                ),
+                CorsRequest::Disallowed,
+            ),
+        ];
+        for (req, expected) in &cases {
+            assert_eq!(policy.classify(req), *expected, "{:?}", req);
+        }
+    }
+
+    #[test]
+    fn apply_simple() {
+        let req = request(Method::Get, &[("Origin", "https://example.com")]);
+        let mut res = Response::new(200);
+        res.insert_header("Vary", "Accept-Encoding");
+
+        assert_eq!(policy().apply(&req, &mut res), CorsRequest::Simple);
+        assert_eq!(res["Access-Control-Allow-Origin"], "https://example.com");
+        assert_eq!(res["Access-Control-Expose-Headers"], "x-request-id");
+        assert!(res.header("Access-Control-Allow-Methods").is_none());
+        let vary: Vec<_> = res["Vary"].iter().map(|v| v.as_str()).collect();
+        assert_eq!(vary, vec!["Accept-Encoding", "Origin"]);
+    }
+
+    #[test]
+    fn apply_preflight() {
+        let req = request(
+            Method::Options,
+            &[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", "DELETE"),
+            ],
+        );
+        let mut res = Response::new(204);
+
+        assert_eq!(policy().apply(&req, &mut res), CorsRequest::Preflight);
+        assert_eq!(res["Access-Control-Allow-Origin"], "https://example.com");
+        assert_eq!(res["Access-Control-Allow-Methods"], "DELETE");
+        assert_eq!(res["Access-Control-Allow-Headers"], "x-requested-with");
+        assert_eq!(res["Access-Control-Max-Age"], "600");
+        assert!(res.header("Access-Control-Expose-Headers").is_none());
+    }
+
+    #[test]
+    fn apply_disallowed() {
+        let req = request(Method::Get, &[("Origin", "https://evil.com")]);
+        let mut res = Response::new(200);
+
+        assert_eq!(policy().apply(&req, &mut res), CorsRequest::Disallowed);
+        assert!(res.header("Access-Control-Allow-Origin").is_none());
+        assert_eq!(res["Vary"], "Origin");
+    }
+
+    #[test]
+    fn apply_any_origin() {
+        let req = request(
+            Method::Options,
+            &[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", "POST"),
+                ("Access-Control-Request-Headers", "X-Custom, Content-Type"),
+            ],
+        );
+
+        let mut policy = CorsPolicy::new();
+        policy.allow_origin("*").allow_header("*");
+        let mut res = Response::new(204);
+        assert_eq!(policy.apply(&req, &mut res), CorsRequest::Preflight);
+        assert_eq!(res["Access-Control-Allow-Origin"], "*");
+        assert_eq!(
+            res["Access-Control-Allow-Headers"],
+            "x-custom, content-type"
+        );
+        assert!(res.header("Vary").is_none());
+
+        // With credentials the origin is echoed back.
+        policy.allow_credentials();
+        let mut res = Response::new(204);
+        policy.apply(&req, &mut res);
+        assert_eq!(res["Access-Control-Allow-Origin"], "https://example.com");
+        assert_eq!(res["Access-Control-Allow-Credentials"], "true");
+        assert_eq!(res["Vary"], "Origin");
+    }
+}
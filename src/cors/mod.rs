@@ -0,0 +1,31 @@
+//! Cross-Origin Resource Sharing.
+//!
+//! # Specifications
+//!
+//! - [Fetch Standard, section 3.2: CORS protocol](https://fetch.spec.whatwg.org/#http-cors-protocol)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::cors::{CorsPolicy, CorsRequest};
+//! use http_types::{Method, Request, Response, StatusCode, Url};
+//!
+//! let mut policy = CorsPolicy::new();
+//! policy.allow_origin("https://example.com").allow_credentials();
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://api.example.com")?);
+//! req.insert_header("Origin", "https://example.com");
+//!
+//! let mut res = Response::new(StatusCode::Ok);
+//! assert_eq!(policy.apply(&req, &mut res), CorsRequest::Simple);
+//! assert_eq!(res["Access-Control-Allow-Origin"], "https://example.com");
+//! assert_eq!(res["Access-Control-Allow-Credentials"], "true");
+//! #
+//! # Ok(()) }
+//! ```
+
+mod cors_policy;
+
+pub use cors_policy::{CorsPolicy, CorsRequest};
@@ -0,0 +1,335 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::headers::HeaderValue;
+use crate::{Error, StatusCode};
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const WEEKDAYS_LONG: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// An HTTP date, as used by the `Date`, `Expires`, `Last-Modified` and
+/// `Retry-After` headers among others.
+///
+/// Dates have a resolution of one second, and dates before the unix epoch
+/// can't be represented. All three formats allowed by the RFC are parsed,
+/// but dates are always formatted as an IMF-fixdate.
+///
+/// # Specifications
+///
+/// - [RFC7231, section 7.1.1.1: Date/Time Formats](https://tools.ietf.org/html/rfc7231#section-7.1.1.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{HttpDate, Response};
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// let date: HttpDate = "Sunday, 06-Nov-94 08:49:37 GMT".parse()?;
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+///
+/// let time: SystemTime = date.into();
+/// assert_eq!(time, UNIX_EPOCH + Duration::from_secs(784_111_777));
+///
+/// let mut res = Response::new(200);
+/// res.set_last_modified(date);
+/// assert_eq!(res["Last-Modified"], "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert_eq!(res.last_modified(), Some(date));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    /// Seconds since the unix epoch.
+    secs: u64,
+}
+
+impl HttpDate {
+    /// Create a new instance from the current system time.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+}
+
+/// Converts a point in time to a date, dropping sub-second precision.
+///
+/// HTTP dates can't represent times before the Unix epoch, so those are
+/// clamped to the epoch, `Thu, 01 Jan 1970 00:00:00 GMT`.
+impl From<SystemTime> for HttpDate {
+    fn from(time: SystemTime) -> Self {
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .map(|dur| dur.as_secs())
+            .unwrap_or(0);
+        Self { secs }
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> Self {
+        UNIX_EPOCH + Duration::from_secs(date.secs)
+    }
+}
+
+impl Display for HttpDate {
+    /// Formats the date as an IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.secs / SECONDS_PER_DAY;
+        let secs_of_day = self.secs % SECONDS_PER_DAY;
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            WEEKDAYS[((days + 4) % 7) as usize],
+            day,
+            MONTHS[(month - 1) as usize],
+            year,
+            secs_of_day / 3600,
+            secs_of_day % 3600 / 60,
+            secs_of_day % 60
+        )
+    }
+}
+
+impl From<HttpDate> for HeaderValue {
+    fn from(date: HttpDate) -> Self {
+        let s = date.to_string();
+        HeaderValue::from_str(&s).expect("HTTP dates should be valid ASCII")
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = Error;
+
+    /// Parses an IMF-fixdate, or one of the obsolete RFC 850 and asctime formats.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let date = match s.find(',') {
+            Some(idx) => {
+                let (weekday, rest) = (&s[..idx], s[idx + 1..].trim_start());
+                if rest.contains('-') {
+                    parse_rfc850(weekday, rest)
+                } else {
+                    parse_imf_fixdate(weekday, rest)
+                }
+            }
+            None => parse_asctime(s),
+        };
+        date.ok_or_else(|| {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid HTTP date", s),
+            )
+        })
+    }
+}
+
+/// `Sun, 06 Nov 1994 08:49:37 GMT`, with the weekday already split off.
+fn parse_imf_fixdate(weekday: &str, rest: &str) -> Option<HttpDate> {
+    if !WEEKDAYS.contains(&weekday) {
+        return None;
+    }
+    let mut parts = rest.split(' ');
+    let day = parse_digits(parts.next()?, 2)?;
+    let month = parse_month(parts.next()?)?;
+    let year = parse_digits(parts.next()?, 4)?;
+    let time = parts.next()?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    from_parts(year, month, day, time)
+}
+
+/// `Sunday, 06-Nov-94 08:49:37 GMT`, with the weekday already split off.
+fn parse_rfc850(weekday: &str, rest: &str) -> Option<HttpDate> {
+    if !WEEKDAYS_LONG.contains(&weekday) {
+        return None;
+    }
+    let mut parts = rest.split(' ');
+    let mut date = parts.next()?.split('-');
+    let time = parts.next()?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    let day = parse_digits(date.next()?, 2)?;
+    let month = parse_month(date.next()?)?;
+    let year = parse_digits(date.next()?, 2)?;
+    if date.next().is_some() {
+        return None;
+    }
+    // Two digit years are assumed to be in the range 1970 to 2069.
+    let year = if year < 70 { year + 2000 } else { year + 1900 };
+    from_parts(year, month, day, time)
+}
+
+/// `Sun Nov  6 08:49:37 1994`
+fn parse_asctime(s: &str) -> Option<HttpDate> {
+    let mut parts = s.split_whitespace();
+    if !WEEKDAYS.contains(&parts.next()?) {
+        return None;
+    }
+    let month = parse_month(parts.next()?)?;
+    let day = parts.next()?;
+    let day = match day.len() {
+        1 => parse_digits(day, 1)?,
+        _ => parse_digits(day, 2)?,
+    };
+    let time = parts.next()?;
+    let year = parse_digits(parts.next()?, 4)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    from_parts(year, month, day, time)
+}
+
+fn parse_digits(s: &str, len: usize) -> Option<u64> {
+    if s.len() != len || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn parse_month(s: &str) -> Option<u64> {
+    MONTHS
+        .iter()
+        .position(|month| *month == s)
+        .map(|idx| idx as u64 + 1)
+}
+
+fn from_parts(year: u64, month: u64, day: u64, time: &str) -> Option<HttpDate> {
+    let mut time = time.split(':');
+    let hour = parse_digits(time.next()?, 2)?;
+    let minute = parse_digits(time.next()?, 2)?;
+    let second = parse_digits(time.next()?, 2)?;
+    if time.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    if year < 1970 || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    let secs = days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second;
+    Some(HttpDate { secs })
+}
+
+fn is_leap_year(year: u64) -> bool {
+    match (year % 4, year % 100, year % 400) {
+        (_, _, 0) => true,
+        (_, 0, _) => false,
+        (0, _, _) => true,
+        _ => false,
+    }
+}
+
+fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the unix epoch for a date in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of `days_from_civil`, returning `(year, month, day)`.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_formats() -> crate::Result<()> {
+        let expected = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784_111_777));
+        assert_eq!(
+            "Sun, 06 Nov 1994 08:49:37 GMT".parse::<HttpDate>()?,
+            expected
+        );
+        assert_eq!(
+            "Sunday, 06-Nov-94 08:49:37 GMT".parse::<HttpDate>()?,
+            expected
+        );
+        assert_eq!("Sun Nov  6 08:49:37 1994".parse::<HttpDate>()?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_imf_fixdate() {
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784_111_777));
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        let date = HttpDate::from(UNIX_EPOCH);
+        assert_eq!(date.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(951_782_400));
+        assert_eq!(date.to_string(), "Tue, 29 Feb 2000 00:00:00 GMT");
+        let date = HttpDate::from(UNIX_EPOCH - Duration::from_secs(1));
+        assert_eq!(date.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        for secs in &[0, 68_169_599, 951_868_799, 1_600_000_000, 4_107_542_399] {
+            let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(*secs));
+            assert_eq!(date.to_string().parse::<HttpDate>()?, date);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "",
+            "Sun, 06 Nov 1994 08:49:37",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun, 31 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 06 Nov 1969 08:49:37 GMT",
+            "Sunday, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov 06 08:49:37",
+        ] {
+            let err = s.parse::<HttpDate>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! HTTP `Expect: 100-continue` handshakes.
+//!
+//! A client can ask for confirmation before sending a large request body by
+//! setting the `Expect: 100-continue` header. The server then either sends an
+//! interim `100 Continue` response, after which the client sends the body, or
+//! responds with a final status straight away without reading the body.
+//!
+//! In `http-types` the server transport calls
+//! [`Request::recv_continue`][recv_continue] once it has set the request
+//! body, and hands the request to the application. Reading the body then
+//! signals the transport to send `100 Continue`. If the request is dropped
+//! without reading the body, the transport is signalled not to.
+//!
+//! [recv_continue]: ../struct.Request.html#method.recv_continue
+//!
+//! ## Example
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+//! #
+//! use http_types::{Method, Request, Url};
+//!
+//! let mut req = Request::new(Method::Put, Url::parse("https://example.com")?);
+//! req.insert_header("Expect", "100-continue");
+//! req.set_body("Hello Nori");
+//! assert!(req.expects_continue());
+//!
+//! // In the transport.
+//! let receiver = req.recv_continue();
+//!
+//! // In the application.
+//! let body = req.body_string().await?;
+//!
+//! // Back in the transport, once the application started reading.
+//! assert!(receiver.await);
+//! #
+//! # Ok(()) }) }
+//! ```
+//!
+//! ## See Also
+//! - [RFC7231, section 5.1.1: Expect](https://tools.ietf.org/html/rfc7231#section-5.1.1)
+
+use async_std::io::{self, BufRead, Read};
+use async_std::prelude::*;
+use async_std::sync;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::Body;
+
+/// The sending half of a channel to ask for `100 Continue` to be sent.
+///
+/// Like `trailers::Sender`, the signal can only be sent once.
+#[derive(Debug)]
+pub(crate) struct Sender {
+    sender: sync::Sender<()>,
+}
+
+impl Sender {
+    /// Signal the receiver, without waiting.
+    ///
+    /// The channel has room for a single signal, so this never fails.
+    fn send(self) {
+        let _ = self.sender.try_send(());
+    }
+}
+
+/// The receiving half of a channel to ask for `100 Continue` to be sent.
+///
+/// Resolves to `true` when the request body starts being read, and the
+/// transport should send `100 Continue`. Resolves to `false` when the
+/// request was dropped without reading the body, in which case the transport
+/// should not send `100 Continue`, and must not expect the body to arrive.
+#[must_use = "Futures do nothing unless polled or .awaited"]
+#[derive(Debug)]
+pub struct Receiver {
+    receiver: sync::Receiver<()>,
+}
+
+impl Future for Receiver {
+    type Output = bool;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll_next(cx)
+            .map(|signal| signal.is_some())
+    }
+}
+
+/// Create a channel for the `100 Continue` signal.
+pub(crate) fn channel() -> (Sender, Receiver) {
+    let (sender, receiver) = sync::channel(1);
+    (Sender { sender }, Receiver { receiver })
+}
+
+/// Wrap a body so the first attempt to read it sends the signal.
+pub(crate) fn signal_on_read(body: Body, sender: Sender) -> Body {
+    let len = body.len();
+    let mime = body.mime().clone();
+    let reader = SignalOnRead {
+        inner: body,
+        sender: Some(sender),
+    };
+    let mut body = Body::from_reader(reader, len);
+    body.set_mime(mime);
+    body
+}
+
+/// A reader which sends the signal before it's first read from.
+#[derive(Debug)]
+struct SignalOnRead {
+    inner: Body,
+    sender: Option<Sender>,
+}
+
+impl SignalOnRead {
+    fn signal(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            sender.send();
+        }
+    }
+}
+
+impl Read for SignalOnRead {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.signal();
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+impl BufRead for SignalOnRead {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        this.signal();
+        Pin::new(&mut this.inner).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut self.inner).consume(amt)
+    }
+}
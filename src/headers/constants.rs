@@ -1,5 +1,7 @@
 use super::HeaderName;
 
+/// The `Content-Disposition` Header
+pub const CONTENT_DISPOSITION: HeaderName = HeaderName::from_lowercase_str("content-disposition");
 /// The `Content-Encoding` Header
 pub const CONTENT_ENCODING: HeaderName = HeaderName::from_lowercase_str("content-encoding");
 /// The `Content-Language` Header
@@ -12,6 +14,12 @@ pub const CONTENT_LOCATION: HeaderName = HeaderName::from_lowercase_str("content
 pub const CONTENT_MD5: HeaderName = HeaderName::from_lowercase_str("content-md5");
 /// The `Content-Range` Header
 pub const CONTENT_RANGE: HeaderName = HeaderName::from_lowercase_str("content-range");
+/// The `Content-Security-Policy` Header
+pub const CONTENT_SECURITY_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy");
+/// The `Content-Security-Policy-Report-Only` Header
+pub const CONTENT_SECURITY_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy-report-only");
 /// The `Content-Type` Header
 pub const CONTENT_TYPE: HeaderName = HeaderName::from_lowercase_str("content-type");
 
@@ -24,12 +32,31 @@ pub const SET_COOKIE: HeaderName = HeaderName::from_lowercase_str("set-cookie");
 /// The `Transfer-Encoding` Header
 pub const TRANSFER_ENCODING: HeaderName = HeaderName::from_lowercase_str("transfer-encoding");
 
+/// The `Cross-Origin-Embedder-Policy` Header
+pub const CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-embedder-policy");
+/// The `Cross-Origin-Embedder-Policy-Report-Only` Header
+pub const CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-embedder-policy-report-only");
+/// The `Cross-Origin-Opener-Policy` Header
+pub const CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-opener-policy");
+/// The `Cross-Origin-Opener-Policy-Report-Only` Header
+pub const CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-opener-policy-report-only");
+/// The `Cross-Origin-Resource-Policy` Header
+pub const CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-resource-policy");
+
 /// The `Date` Header
 pub const DATE: HeaderName = HeaderName::from_lowercase_str("date");
 
 /// The `Host` Header
 pub const HOST: HeaderName = HeaderName::from_lowercase_str("host");
 
+/// The `Forwarded` Header
+pub const FORWARDED: HeaderName = HeaderName::from_lowercase_str("forwarded");
+
 /// The `Origin` Header
 pub const ORIGIN: HeaderName = HeaderName::from_lowercase_str("origin");
 
@@ -114,12 +141,21 @@ pub const IF_UNMODIFIED_SINCE: HeaderName = HeaderName::from_lowercase_str("if-u
 ///  The `Last-Modified` Header
 pub const LAST_MODIFIED: HeaderName = HeaderName::from_lowercase_str("last-modified");
 
+///  The `Link` Header
+pub const LINK: HeaderName = HeaderName::from_lowercase_str("link");
+
 ///  The `Location` Header
 pub const LOCATION: HeaderName = HeaderName::from_lowercase_str("location");
 
 ///  The `Max-Forwards` Header
 pub const MAX_FORWARDS: HeaderName = HeaderName::from_lowercase_str("max-forwards");
 
+///  The `NEL` Header
+pub const NEL: HeaderName = HeaderName::from_lowercase_str("nel");
+
+///  The `Permissions-Policy` Header
+pub const PERMISSIONS_POLICY: HeaderName = HeaderName::from_lowercase_str("permissions-policy");
+
 ///  The `Pragma` Header
 pub const PRAGMA: HeaderName = HeaderName::from_lowercase_str("pragma");
 
@@ -128,15 +164,31 @@ pub const PROXY_AUTHENTICATE: HeaderName = HeaderName::from_lowercase_str("proxy
 ///  The `Proxy-Authorization` Header
 pub const PROXY_AUTHORIZATION: HeaderName = HeaderName::from_lowercase_str("proxy-authorization");
 
+///  The `Range` Header
+pub const RANGE: HeaderName = HeaderName::from_lowercase_str("range");
+
 ///  The `Referer` Header
 pub const REFERER: HeaderName = HeaderName::from_lowercase_str("referer");
 
+///  The `Report-To` Header
+pub const REPORT_TO: HeaderName = HeaderName::from_lowercase_str("report-to");
+
+///  The `Reporting-Endpoints` Header
+pub const REPORTING_ENDPOINTS: HeaderName = HeaderName::from_lowercase_str("reporting-endpoints");
+
 ///  The `Retry-After` Header
 pub const RETRY_AFTER: HeaderName = HeaderName::from_lowercase_str("retry-after");
 
 ///  The `Server` Header
 pub const SERVER: HeaderName = HeaderName::from_lowercase_str("server");
 
+///  The `Server-Timing` Header
+pub const SERVER_TIMING: HeaderName = HeaderName::from_lowercase_str("server-timing");
+
+///  The `Strict-Transport-Security` Header
+pub const STRICT_TRANSPORT_SECURITY: HeaderName =
+    HeaderName::from_lowercase_str("strict-transport-security");
+
 ///  The `Te` Header
 pub const TE: HeaderName = HeaderName::from_lowercase_str("te");
 
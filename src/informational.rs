@@ -0,0 +1,235 @@
+//! HTTP informational `1xx` responses.
+//!
+//! Before sending the final response to a request, a server may send any
+//! number of interim responses. For example `103 Early Hints` lets the client
+//! preload resources while the final response is being prepared, and `102
+//! Processing` tells it the request is still being worked on.
+//!
+//! Interim responses are sent while the application is still handling the
+//! request, so the channel lives on the `Request`: the server transport calls
+//! [`Request::recv_informational`][req_recv] before handing the request to
+//! the application, and drains the receiver until the final response is
+//! ready. The application calls
+//! [`Request::send_informational`][req_send] to get a sender.
+//!
+//! [req_send]: ../struct.Request.html#method.send_informational
+//! [req_recv]: ../struct.Request.html#method.recv_informational
+//!
+//! ## Example
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::prelude::*;
+//! use http_types::informational::Informational;
+//! use http_types::links::Link;
+//! use http_types::{Method, Request, StatusCode, Url};
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//!
+//! // In the transport.
+//! let mut receiver = req.recv_informational();
+//!
+//! // In the application.
+//! let sender = req.send_informational();
+//! let mut hints = Informational::new(StatusCode::EarlyHints)?;
+//! let mut link = Link::new(Url::parse("https://example.com/style.css")?);
+//! link.push_rel("preload");
+//! hints.push_link(link);
+//! sender.send(hints);
+//! drop(sender);
+//!
+//! // Back in the transport.
+//! let hints = receiver.next().await.unwrap();
+//! assert_eq!(hints.status(), StatusCode::EarlyHints);
+//! assert_eq!(hints["Link"], r#"<https://example.com/style.css>; rel="preload""#);
+//! #
+//! # Ok(()) }) }
+//! ```
+//!
+//! ## See Also
+//! - [RFC7231, section 6.2: Informational 1xx](https://tools.ietf.org/html/rfc7231#section-6.2)
+//! - [RFC8297: An HTTP Status Code for Indicating Hints](https://tools.ietf.org/html/rfc8297)
+
+use async_std::prelude::*;
+use async_std::sync;
+
+use std::convert::TryInto;
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut, Index};
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+
+use crate::headers::{HeaderName, HeaderValue, HeaderValues, Headers, LINK};
+use crate::links::Link;
+use crate::{Error, StatusCode};
+
+/// The number of interim responses buffered until the transport drains them.
+pub(crate) const CAPACITY: usize = 16;
+
+/// An interim response, sent before the final response to a request.
+#[derive(Debug, Clone)]
+pub struct Informational {
+    status: StatusCode,
+    headers: Headers,
+}
+
+impl Informational {
+    /// Create a new instance of `Informational`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the status isn't in the `1xx` range, or is
+    /// `101 Switching Protocols`, which is a final response for the
+    /// connection. Use the `upgrade` module for protocol upgrades instead.
+    pub fn new<S>(status: S) -> crate::Result<Self>
+    where
+        S: TryInto<StatusCode>,
+        S::Error: Debug,
+    {
+        let status = status
+            .try_into()
+            .expect("Could not convert into a valid `StatusCode`");
+        if !status.is_informational() || status == StatusCode::SwitchingProtocols {
+            return Err(Error::from_str(
+                StatusCode::InternalServerError,
+                format!("`{}` is not an interim response status", status),
+            ));
+        }
+        Ok(Self {
+            status,
+            headers: Headers::new(),
+        })
+    }
+
+    /// Get the status.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Add a link, as used by `103 Early Hints` to ask for resources to be
+    /// preloaded.
+    pub fn push_link(&mut self, link: Link) {
+        let value = link.to_string();
+        let value = HeaderValue::from_str(&value).expect("Links should be valid ASCII");
+        self.headers.append(LINK, value);
+    }
+}
+
+impl Deref for Informational {
+    type Target = Headers;
+
+    fn deref(&self) -> &Self::Target {
+        &self.headers
+    }
+}
+
+impl DerefMut for Informational {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.headers
+    }
+}
+
+impl AsRef<Headers> for Informational {
+    fn as_ref(&self) -> &Headers {
+        &self.headers
+    }
+}
+
+impl AsMut<Headers> for Informational {
+    fn as_mut(&mut self) -> &mut Headers {
+        &mut self.headers
+    }
+}
+
+impl Index<HeaderName> for Informational {
+    type Output = HeaderValues;
+
+    /// Returns a reference to the value corresponding to the supplied name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not present in `Informational`.
+    #[inline]
+    fn index(&self, name: HeaderName) -> &HeaderValues {
+        self.headers.index(name)
+    }
+}
+
+impl Index<&str> for Informational {
+    type Output = HeaderValues;
+
+    /// Returns a reference to the value corresponding to the supplied name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not present in `Informational`.
+    #[inline]
+    fn index(&self, name: &str) -> &HeaderValues {
+        self.headers.index(name)
+    }
+}
+
+/// The sending half of a channel to send interim responses.
+///
+/// Unlike `trailers::Sender`, any number of responses can be sent.
+#[derive(Debug, Clone)]
+pub struct Sender {
+    sender: sync::Sender<Informational>,
+}
+
+impl Sender {
+    /// Create a new instance of `Sender`.
+    #[doc(hidden)]
+    pub fn new(sender: sync::Sender<Informational>) -> Self {
+        Self { sender }
+    }
+
+    /// Send an interim response.
+    ///
+    /// Interim responses are advisory, so this never waits: the response is
+    /// dropped if the transport doesn't support interim responses, or has
+    /// fallen too far behind in sending them.
+    pub fn send(&self, informational: Informational) {
+        let _ = self.sender.try_send(informational);
+    }
+}
+
+/// The receiving half of a channel to send interim responses.
+///
+/// The stream ends once the request and all senders have been dropped.
+#[must_use = "Streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct Receiver {
+    receiver: sync::Receiver<Informational>,
+}
+
+impl Receiver {
+    /// Create a new instance of `Receiver`.
+    pub(crate) fn new(receiver: sync::Receiver<Informational>) -> Self {
+        Self { receiver }
+    }
+}
+
+impl Stream for Receiver {
+    type Item = Informational;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_interim_statuses() {
+        assert!(Informational::new(StatusCode::Continue).is_ok());
+        assert!(Informational::new(102).is_ok());
+        assert!(Informational::new(StatusCode::EarlyHints).is_ok());
+        assert!(Informational::new(StatusCode::SwitchingProtocols).is_err());
+        assert!(Informational::new(StatusCode::Ok).is_err());
+    }
+}
@@ -117,14 +117,25 @@ pub mod url {
 #[macro_use]
 mod utils;
 
+pub mod auth;
+pub mod cache;
+pub mod conditional;
+pub mod content;
+pub mod cors;
 pub mod headers;
+pub mod links;
 pub mod mime;
+pub mod proxies;
+pub mod range;
+pub mod reporting;
 
 mod body;
+mod date;
 mod error;
 mod extensions;
 mod macros;
 mod method;
+mod parse_utils;
 mod request;
 mod response;
 mod status;
@@ -144,6 +155,7 @@ cfg_unstable! {
 }
 
 pub use body::Body;
+pub use date::HttpDate;
 pub use error::{Error, Result};
 pub use method::Method;
 pub use request::Request;
@@ -167,6 +179,8 @@ pub use crate::url::Url;
 #[doc(inline)]
 pub use crate::cookies::Cookie;
 
+pub mod expect;
+pub mod informational;
 pub mod security;
 pub mod trailers;
 
@@ -0,0 +1,417 @@
+use std::fmt::{self, Display};
+
+use crate::parse_utils::{
+    fmt_ascii_fallback, fmt_ext_value, fmt_quoted_string, fmt_token_or_quoted_string,
+    is_printable_ascii, is_token, parse_ext_value, parse_param, split_outside_quotes, trim_ows,
+};
+use crate::{Error, Mime, StatusCode, Url};
+
+/// A single link of the `Link` header.
+///
+/// # Specifications
+///
+/// - [RFC8288: Web Linking](https://tools.ietf.org/html/rfc8288)
+/// - [RFC8187: Indicating Character Encoding and Language for HTTP Header Field Parameters](https://tools.ietf.org/html/rfc8187)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::links::Link;
+/// use http_types::Url;
+///
+/// let base = Url::parse("https://example.com/articles?page=2")?;
+/// let link = Link::parse(r#"</articles?page=3>; rel="next"; title*=UTF-8''n%C3%A4chste"#, &base)?;
+///
+/// assert_eq!(link.target().as_str(), "https://example.com/articles?page=3");
+/// assert!(link.has_rel("next"));
+/// assert_eq!(link.title(), Some("nächste"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    target: Url,
+    rels: Vec<String>,
+    anchor: Option<Url>,
+    media_type: Option<Mime>,
+    hreflang: Vec<String>,
+    title: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl Link {
+    /// Create a new instance of `Link`.
+    pub fn new(target: Url) -> Self {
+        Self {
+            target,
+            rels: vec![],
+            anchor: None,
+            media_type: None,
+            hreflang: vec![],
+            title: None,
+            params: vec![],
+        }
+    }
+
+    /// Parse a single link, resolving its target and anchor against a base
+    /// URL such as `Request::url()`.
+    ///
+    /// Only the first occurrence of the `rel`, `anchor`, `type` and `title`
+    /// parameters is used. RFC 8187 `*` parameters, such as `title*`, take
+    /// precedence over their plain form.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the link is malformed.
+    pub fn parse(s: &str, base: &Url) -> crate::Result<Self> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Link", s),
+            )
+        };
+
+        let rest = trim_ows(s).strip_prefix('<').ok_or_else(invalid)?;
+        let end = rest.find('>').ok_or_else(invalid)?;
+        let target = base.join(rest[..end].trim()).map_err(|_| invalid())?;
+        let mut link = Self::new(target);
+
+        let (mut has_rel, mut has_title_star) = (false, false);
+        for param in split_outside_quotes(&rest[end + 1..], ';') {
+            let (name, value) = parse_param(param).ok_or_else(invalid)?;
+            let name = name.to_ascii_lowercase();
+            match name.as_str() {
+                "rel" if !has_rel => {
+                    has_rel = true;
+                    value.split_whitespace().for_each(|rel| link.push_rel(rel));
+                }
+                "anchor" if link.anchor.is_none() => {
+                    link.anchor = Some(base.join(&value).map_err(|_| invalid())?);
+                }
+                "type" if link.media_type.is_none() => {
+                    link.media_type = Some(value.parse().map_err(|_| invalid())?);
+                }
+                "hreflang" => link.hreflang.push(value),
+                "title" if link.title.is_none() => link.title = Some(value),
+                "title*" if !has_title_star => {
+                    has_title_star = true;
+                    link.title = Some(parse_ext_value(&value).ok_or_else(invalid)?);
+                }
+                "rel" | "anchor" | "type" | "title" | "title*" => {}
+                "rel*" | "anchor*" | "type*" => {}
+                "hreflang*" => {
+                    let lang = parse_ext_value(&value).ok_or_else(invalid)?;
+                    link.hreflang.push(lang);
+                }
+                _ if name.len() > 1 && name.ends_with('*') => {
+                    let value = parse_ext_value(&value).ok_or_else(invalid)?;
+                    link.set_param(&name[..name.len() - 1], value);
+                }
+                _ => link.params.push((name, value)),
+            }
+        }
+        Ok(link)
+    }
+
+    /// Get the target of the link.
+    pub fn target(&self) -> &Url {
+        &self.target
+    }
+
+    /// Set the target of the link.
+    pub fn set_target(&mut self, target: Url) {
+        self.target = target;
+    }
+
+    /// Get the relation types of the link.
+    pub fn rels(&self) -> &[String] {
+        &self.rels
+    }
+
+    /// Returns `true` if the link has the relation type.
+    pub fn has_rel(&self, rel: &str) -> bool {
+        self.rels.iter().any(|r| r.eq_ignore_ascii_case(rel))
+    }
+
+    /// Add a relation type to the link.
+    ///
+    /// Registered relation types are case-insensitive and stored in
+    /// lowercase. Extension relation types are URLs, and kept as is, except
+    /// for whitespace, control and non-ASCII characters which are
+    /// percent-encoded.
+    pub fn push_rel(&mut self, rel: impl Into<String>) {
+        let mut rel = rel.into();
+        if !rel.contains(':') {
+            rel.make_ascii_lowercase();
+        }
+        if !rel.chars().all(|c| c.is_ascii_graphic()) {
+            rel = percent_encode_non_visible(&rel);
+        }
+        if !self.rels.contains(&rel) {
+            self.rels.push(rel);
+        }
+    }
+
+    /// Get the context of the link, if it's not the requested resource.
+    pub fn anchor(&self) -> Option<&Url> {
+        self.anchor.as_ref()
+    }
+
+    /// Set the context of the link.
+    pub fn set_anchor(&mut self, anchor: Url) {
+        self.anchor = Some(anchor);
+    }
+
+    /// Get the media type of the target, from the `type` parameter.
+    pub fn media_type(&self) -> Option<&Mime> {
+        self.media_type.as_ref()
+    }
+
+    /// Set the media type of the target.
+    pub fn set_media_type(&mut self, media_type: Mime) {
+        self.media_type = Some(media_type);
+    }
+
+    /// Get the languages of the target, from the `hreflang` parameters.
+    pub fn hreflang(&self) -> &[String] {
+        &self.hreflang
+    }
+
+    /// Add a language of the target.
+    ///
+    /// Languages which aren't printable ASCII are sent as an RFC 8187
+    /// `hreflang*` parameter.
+    pub fn push_hreflang(&mut self, lang: impl Into<String>) {
+        self.hreflang.push(lang.into());
+    }
+
+    /// Get the human-readable title of the link.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Set the human-readable title of the link.
+    ///
+    /// Titles which aren't printable ASCII are sent as an RFC 8187 `title*`
+    /// parameter, after an ASCII fallback for older recipients.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    /// Get an extension parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set an extension parameter, replacing any previous value.
+    ///
+    /// Values which aren't printable ASCII are sent as an RFC 8187 `name*`
+    /// parameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name isn't a valid token.
+    pub fn set_param(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into().to_ascii_lowercase();
+        assert!(is_token(&name), "Link parameter names must be tokens");
+        let value = value.into();
+        match self.params.iter_mut().find(|(n, _)| *n == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name, value)),
+        }
+    }
+}
+
+impl Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.target)?;
+        if !self.rels.is_empty() {
+            write!(f, "; rel={}", fmt_quoted_string(&self.rels.join(" ")))?;
+        }
+        if let Some(anchor) = &self.anchor {
+            write!(f, "; anchor={}", fmt_quoted_string(anchor.as_str()))?;
+        }
+        if let Some(media_type) = &self.media_type {
+            write!(f, "; type={}", fmt_quoted_string(&media_type.to_string()))?;
+        }
+        for lang in &self.hreflang {
+            fmt_param(f, "hreflang", lang)?;
+        }
+        match &self.title {
+            Some(title) if is_printable_ascii(title) => {
+                write!(f, "; title={}", fmt_quoted_string(title))?
+            }
+            Some(title) => {
+                write!(
+                    f,
+                    "; title={}",
+                    fmt_quoted_string(&fmt_ascii_fallback(title))
+                )?;
+                write!(f, "; title*={}", fmt_ext_value(title))?;
+            }
+            None => {}
+        }
+        for (name, value) in &self.params {
+            match value.as_str() {
+                "" => write!(f, "; {}", name)?,
+                value => fmt_param(f, name, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write a parameter, using the RFC 8187 `name*` form for values which
+/// aren't printable ASCII.
+fn fmt_param(f: &mut fmt::Formatter<'_>, name: &str, value: &str) -> fmt::Result {
+    if is_printable_ascii(value) {
+        write!(f, "; {}={}", name, fmt_token_or_quoted_string(value))
+    } else {
+        write!(f, "; {}*={}", name, fmt_ext_value(value))
+    }
+}
+
+/// Percent-encode the characters of an IRI which aren't visible ASCII,
+/// mapping it to a URI.
+///
+/// [RFC3987, section 3.1](https://tools.ietf.org/html/rfc3987#section-3.1)
+fn percent_encode_non_visible(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_graphic() {
+            output.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                output.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("https://example.com/a/b").unwrap()
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let link = Link::parse(
+            "<../c>;REL=\"Next https://example.com/rels/Custom\";anchor=\"#top\";\
+             type=text/html; hreflang=en; hreflang=de; title=\"C\"; rel=prev; crossorigin; x=\"y; z\"",
+            &base(),
+        )?;
+        assert_eq!(link.target().as_str(), "https://example.com/c");
+        assert_eq!(link.rels(), ["next", "https://example.com/rels/Custom"]);
+        assert!(link.has_rel("NEXT"));
+        assert!(!link.has_rel("prev"));
+        assert_eq!(
+            link.anchor().unwrap().as_str(),
+            "https://example.com/a/b#top"
+        );
+        assert_eq!(link.media_type().unwrap().essence(), "text/html");
+        assert_eq!(link.hreflang(), ["en", "de"]);
+        assert_eq!(link.title(), Some("C"));
+        assert_eq!(link.param("crossorigin"), Some(""));
+        assert_eq!(link.param("X"), Some("y; z"));
+        Ok(())
+    }
+
+    #[test]
+    fn title_star() -> crate::Result<()> {
+        let link = Link::parse(
+            "</>; title*=UTF-8'de'n%c3%a4chstes%20Kapitel; title=\"next chapter\"",
+            &base(),
+        )?;
+        assert_eq!(link.title(), Some("nächstes Kapitel"));
+
+        assert!(Link::parse("</>; title*=UTF-8''%FF", &base()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        let mut link = Link::new(Url::parse("https://example.com/c?d=e,f")?);
+        link.push_rel("Alternate");
+        link.push_rel("next");
+        link.set_anchor(base());
+        link.set_media_type(crate::mime::HTML);
+        link.push_hreflang("en-US");
+        link.set_title("Café");
+        link.set_param("crossorigin", "");
+        link.set_param("media", "screen and (min-width: 600px)");
+
+        let s = link.to_string();
+        assert_eq!(
+            s,
+            "<https://example.com/c?d=e,f>; rel=\"alternate next\"; anchor=\"https://example.com/a/b\"; \
+             type=\"text/html;charset=utf-8\"; hreflang=en-US; title=\"Caf_\"; title*=UTF-8''Caf%C3%A9; \
+             crossorigin; media=\"screen and (min-width: 600px)\""
+        );
+        assert_eq!(Link::parse(&s, &base())?, link);
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_params() -> crate::Result<()> {
+        let mut link = Link::new(base());
+        link.push_rel("https://example.com/rels/übersicht");
+        link.push_hreflang("zh-漢");
+        link.set_param("x", "é");
+
+        let s = link.to_string();
+        assert_eq!(
+            s,
+            "<https://example.com/a/b>; rel=\"https://example.com/rels/%C3%BCbersicht\"; \
+             hreflang*=UTF-8''zh-%E6%BC%A2; x*=UTF-8''%C3%A9"
+        );
+        assert_eq!(Link::parse(&s, &base())?, link);
+
+        let link = Link::parse("</>; x=\"plain\"; x*=UTF-8''%C3%A9", &base())?;
+        assert_eq!(link.param("x"), Some("é"));
+        Ok(())
+    }
+
+    #[test]
+    fn control_characters_are_encoded() -> crate::Result<()> {
+        let mut link = Link::new(base());
+        link.push_rel("https://example.com/rels/a\r\nSet-Cookie: b");
+        link.push_hreflang("en\r\n");
+        link.set_title("Hi\r\nSet-Cookie: a=b");
+        link.set_param("x", "\n");
+
+        let s = link.to_string();
+        assert_eq!(
+            s,
+            "<https://example.com/a/b>; rel=\"https://example.com/rels/a%0D%0ASet-Cookie:%20b\"; \
+             hreflang*=UTF-8''en%0D%0A; title=\"Hi__Set-Cookie: a=b\"; \
+             title*=UTF-8''Hi%0D%0ASet-Cookie%3A%20a%3Db; x*=UTF-8''%0A"
+        );
+        assert!(!s.contains(|c: char| c.is_ascii_control()));
+        assert_eq!(Link::parse(&s, &base())?, link);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "",
+            "https://example.com",
+            "<https://example.com",
+            "</>; type=\"x\"",
+            "</>; a=\"b",
+        ] {
+            let err = Link::parse(s, &base()).unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
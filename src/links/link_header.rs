@@ -0,0 +1,181 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, LINK};
+use crate::links::Link;
+use crate::parse_utils::split_list;
+use crate::Url;
+
+/// A list of links for the `Link` header.
+///
+/// # Specifications
+///
+/// - [RFC8288, section 3: Link Serialisation in HTTP Headers](https://tools.ietf.org/html/rfc8288#section-3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::links::Links;
+/// use http_types::{Response, Url};
+///
+/// let base = Url::parse("https://example.com/items?page=2")?;
+///
+/// let mut res = Response::new(200);
+/// res.insert_header("Link", r#"<?page=1>; rel="prev", <?page=3>; rel="next""#);
+///
+/// let links = Links::from_headers(&res, &base)?.unwrap();
+/// let next = links.rel("next").unwrap();
+/// assert_eq!(next.target().as_str(), "https://example.com/items?page=3");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Links {
+    links: Vec<Link>,
+}
+
+impl Links {
+    /// Create a new instance of `Links`.
+    pub fn new() -> Self {
+        Self { links: vec![] }
+    }
+
+    /// Create a new instance from headers, resolving relative URLs against a
+    /// base URL such as `Request::url()`.
+    ///
+    /// All `Link` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>, base: &Url) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(LINK) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut links = Self::new();
+        for value in values {
+            for link in split_list(value.as_str()) {
+                links.push(Link::parse(link, base)?);
+            }
+        }
+        Ok(Some(links))
+    }
+
+    /// Sets the `Link` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        LINK
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Links should be valid ASCII")
+    }
+
+    /// Push a link into the list.
+    pub fn push(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Get the first link with a relation type.
+    pub fn rel(&self, rel: &str) -> Option<&Link> {
+        self.links.iter().find(|link| link.has_rel(rel))
+    }
+
+    /// An iterator visiting all links.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.links.iter(),
+        }
+    }
+}
+
+impl Display for Links {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, link) in self.links.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", link)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for Links {
+    type Item = Link;
+    type IntoIter = std::vec::IntoIter<Link>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.links.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Links {
+    type Item = &'a Link;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `Links`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, Link>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Link;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let base = Url::parse("https://example.com")?;
+        let mut headers = Headers::new();
+        headers.append("Link", "</a>; rel=first, </b, c>; rel=\"prev next\"");
+        headers.append("Link", "</d>; rel=last");
+
+        let links = Links::from_headers(&headers, &base)?.unwrap();
+        let targets: Vec<_> = links.iter().map(|link| link.target().path()).collect();
+        assert_eq!(targets, vec!["/a", "/b,%20c", "/d"]);
+        assert_eq!(links.rel("next").unwrap().target().path(), "/b,%20c");
+        assert!(links.rel("self").is_none());
+
+        let mut headers = Headers::new();
+        links.apply(&mut headers);
+        assert_eq!(Links::from_headers(&headers, &base)?.unwrap(), links);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let base = Url::parse("https://example.com").unwrap();
+        let mut headers = Headers::new();
+        headers.insert("Link", "</a>; rel=next, /b; rel=prev");
+        let err = Links::from_headers(headers, &base).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
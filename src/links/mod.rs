@@ -0,0 +1,34 @@
+//! Typed links between resources.
+//!
+//! # Specifications
+//!
+//! - [RFC8288: Web Linking](https://tools.ietf.org/html/rfc8288)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::links::Link;
+//! use http_types::{Response, Url};
+//!
+//! let base = Url::parse("https://example.com/items")?;
+//!
+//! let mut link = Link::new(base.join("?page=2")?);
+//! link.push_rel("next");
+//!
+//! let mut res = Response::new(200);
+//! res.append_link(link);
+//! assert_eq!(res["Link"], r#"<https://example.com/items?page=2>; rel="next""#);
+//!
+//! let links = res.links(&base)?;
+//! assert_eq!(links[0].target().query(), Some("page=2"));
+//! #
+//! # Ok(()) }
+//! ```
+
+mod link;
+mod link_header;
+
+pub use link::Link;
+pub use link_header::{Iter, Links};
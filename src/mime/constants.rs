@@ -58,6 +58,8 @@ mime_const!(BYTE_STREAM, "byte streams", "application", "octet-stream");
 mime_const!(FORM, "forms", "application", "x-www-form-urlencoded");
 mime_const!(MULTIPART_FORM, "multipart forms", "multipart", "form-data");
 mime_const!(WASM, "webassembly", "application", "wasm");
+mime_const!(CSP_REPORT, "CSP violation reports", "application", "csp-report");
+mime_const!(REPORTS_JSON, "Reporting API reports", "application", "reports+json");
 // There are multiple `.ico` mime types known, but `image/x-icon`
 // is what most browser use. See:
 // https://en.wikipedia.org/wiki/ICO_%28file_format%29#MIME_type
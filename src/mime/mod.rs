@@ -102,8 +102,8 @@ impl Mime {
                 ParamKind::Vec(v) => v
                     .iter()
                     .find_map(|(k, v)| if k == &name { Some(v) } else { None }),
-                ParamKind::Utf8 => match name {
-                    ParamName(Cow::Borrowed("charset")) => Some(&ParamValue(Cow::Borrowed("utf8"))),
+                ParamKind::Utf8 => match name.as_str() {
+                    "charset" => Some(&ParamValue(Cow::Borrowed("utf8"))),
                     _ => None,
                 },
             })
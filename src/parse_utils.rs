@@ -0,0 +1,415 @@
+//! Parsers for the grammar shared by structured HTTP header values.
+//!
+//! See [RFC 7230, section 3.2.6](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+/// The largest `delta-seconds` value a cache is required to represent.
+///
+/// [RFC7234, section 1.2.1](https://tools.ietf.org/html/rfc7234#section-1.2.1)
+pub(crate) const DELTA_SECONDS_MAX: u64 = 2_147_483_648;
+
+/// Validates a [`tchar`](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+pub(crate) fn is_tchar(c: char) -> bool {
+    matches!(
+        c,
+        'a'..='z'
+            | 'A'..='Z'
+            | '0'..='9'
+            | '!'
+            | '#'
+            | '$'
+            | '%'
+            | '&'
+            | '\''
+            | '*'
+            | '+'
+            | '-'
+            | '.'
+            | '^'
+            | '_'
+            | '`'
+            | '|'
+            | '~'
+    )
+}
+
+/// Returns `true` if the string is a non-empty `token`.
+pub(crate) fn is_token(input: &str) -> bool {
+    !input.is_empty() && input.chars().all(is_tchar)
+}
+
+/// Validates `qdtext`, allowing `obs-text` for anything outside of ASCII.
+fn is_qdtext(c: char) -> bool {
+    matches!(c, '\t' | ' ' | '!' | '#'..='[' | ']'..='~') || c >= '\u{80}'
+}
+
+/// Validates the second char of a `quoted-pair`.
+fn is_quoted_pair_char(c: char) -> bool {
+    matches!(c, '\t' | ' '..='~') || c >= '\u{80}'
+}
+
+/// Optional whitespace, `OWS` in the RFC grammar.
+pub(crate) fn trim_ows(input: &str) -> &str {
+    input.trim_matches(&[' ', '\t'][..])
+}
+
+/// Strip leading optional whitespace.
+pub(crate) fn trim_ows_start(input: &str) -> &str {
+    input.trim_start_matches(&[' ', '\t'][..])
+}
+
+/// Parse a `token` from the start of the input, returning it and the remainder.
+pub(crate) fn parse_token(input: &str) -> (Option<&str>, &str) {
+    let end = input.find(|c| !is_tchar(c)).unwrap_or(input.len());
+    if end == 0 {
+        (None, input)
+    } else {
+        (Some(&input[..end]), &input[end..])
+    }
+}
+
+/// Parse a `quoted-string` from the start of the input, returning the
+/// unescaped contents and the remainder.
+///
+/// The input is returned unchanged if it does not start with a valid quoted string.
+pub(crate) fn parse_quoted_string(input: &str) -> (Option<Cow<'_, str>>, &str) {
+    if !input.starts_with('"') {
+        return (None, input);
+    }
+
+    let mut unescaped: Option<String> = None;
+    let mut start = 1;
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let value = match unescaped {
+                    Some(mut value) => {
+                        value.push_str(&input[start..i]);
+                        Cow::Owned(value)
+                    }
+                    None => Cow::Borrowed(&input[1..i]),
+                };
+                return (Some(value), &input[i + 1..]);
+            }
+            '\\' => {
+                let value = unescaped.get_or_insert_with(String::new);
+                value.push_str(&input[start..i]);
+                match chars.next() {
+                    Some((j, escaped)) if is_quoted_pair_char(escaped) => {
+                        value.push(escaped);
+                        start = j + escaped.len_utf8();
+                    }
+                    _ => return (None, input),
+                }
+            }
+            c if is_qdtext(c) => {}
+            _ => return (None, input),
+        }
+    }
+
+    // The closing quote was never found.
+    (None, input)
+}
+
+/// Parse either a `token` or a `quoted-string` from the start of the input.
+pub(crate) fn parse_token_or_quoted_string(input: &str) -> (Option<Cow<'_, str>>, &str) {
+    if input.starts_with('"') {
+        parse_quoted_string(input)
+    } else {
+        match parse_token(input) {
+            (Some(token), rest) => (Some(Cow::Borrowed(token)), rest),
+            (None, rest) => (None, rest),
+        }
+    }
+}
+
+/// Parse a `name=value` parameter, whose value is optional. The name is
+/// returned as is, and the value unescaped.
+///
+/// Unquoted values which aren't tokens, like `type=text/html`, are accepted
+/// as well.
+pub(crate) fn parse_param(s: &str) -> Option<(&str, String)> {
+    let (name, rest) = parse_token(s);
+    let rest = trim_ows_start(rest);
+    let rest = match rest.strip_prefix('=') {
+        Some(rest) => trim_ows_start(rest),
+        None if rest.is_empty() => return Some((name?, String::new())),
+        None => return None,
+    };
+
+    let value = match parse_token_or_quoted_string(rest) {
+        (Some(value), rest) if trim_ows(rest).is_empty() => value.into_owned(),
+        _ if !rest.contains(|c: char| c == '"' || c.is_whitespace()) => rest.to_string(),
+        _ => return None,
+    };
+    Some((name?, value))
+}
+
+/// Serialize a value as a `quoted-string`, escaping `"` and `\`.
+pub(crate) fn fmt_quoted_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push('"');
+    output
+}
+
+/// Serialize a value as a `token` if possible, and as a `quoted-string` otherwise.
+pub(crate) fn fmt_token_or_quoted_string(value: &str) -> Cow<'_, str> {
+    if is_token(value) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(fmt_quoted_string(value))
+    }
+}
+
+/// Returns `true` if the value only contains printable ASCII characters,
+/// which may be sent as a `quoted-string` in any recipient's charset.
+pub(crate) fn is_printable_ascii(value: &str) -> bool {
+    value.chars().all(|c| matches!(c, ' '..='~'))
+}
+
+/// Replace the characters of a value which aren't printable ASCII with `_`,
+/// for recipients which don't support RFC 8187 `*` parameters.
+pub(crate) fn fmt_ascii_fallback(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if matches!(c, ' '..='~') { c } else { '_' })
+        .collect()
+}
+
+/// Validates an [`attr-char`](https://tools.ietf.org/html/rfc8187#section-3.2.1),
+/// which may appear unencoded in an `ext-value`.
+fn is_attr_char(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            '!' | '#' | '$' | '&' | '+' | '-' | '.' | '^' | '_' | '`' | '|' | '~'
+        )
+}
+
+/// Parse an RFC 8187 `ext-value`, such as `UTF-8'en'%E2%82%AC%20rates`,
+/// returning the decoded value.
+///
+/// Only the `UTF-8` and `ISO-8859-1` charsets are supported.
+pub(crate) fn parse_ext_value(input: &str) -> Option<String> {
+    let mut parts = input.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut chars = encoded.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() != 2 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+            }
+            c if is_attr_char(c) => bytes.push(c as u8),
+            _ => return None,
+        }
+    }
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Serialize a value as an RFC 8187 `ext-value` with the `UTF-8` charset.
+pub(crate) fn fmt_ext_value(value: &str) -> String {
+    let mut output = String::from("UTF-8''");
+    for byte in value.bytes() {
+        if is_attr_char(byte as char) {
+            output.push(byte as char);
+        } else {
+            output.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    output
+}
+
+/// Parse `delta-seconds`, saturating values too large to represent at
+/// `DELTA_SECONDS_MAX`.
+pub(crate) fn parse_delta_seconds(input: &str) -> Option<Duration> {
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let secs = input
+        .parse()
+        .unwrap_or(DELTA_SECONDS_MAX)
+        .min(DELTA_SECONDS_MAX);
+    Some(Duration::from_secs(secs))
+}
+
+/// Returns `true` if the string is a Structured Fields
+/// [`key`](https://tools.ietf.org/html/rfc8941#section-3.1.2).
+pub(crate) fn is_sf_key(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some('a'..='z') | Some('*'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '*'))
+}
+
+/// Split a comma-separated list (the `#rule` from RFC 7230) into its elements.
+///
+/// Commas inside quoted strings and angle brackets are not treated as
+/// separators. Elements are trimmed, and empty elements are skipped.
+pub(crate) fn split_list(input: &str) -> Vec<&str> {
+    split_outside_quotes(input, ',')
+}
+
+/// Split a string on a delimiter, ignoring delimiters inside quoted strings and
+/// angle brackets. Elements are trimmed, and empty elements are skipped.
+pub(crate) fn split_outside_quotes(input: &str, delimiter: char) -> Vec<&str> {
+    let mut output = vec![];
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut in_brackets = false;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        if in_quotes {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_quotes = false;
+            }
+        } else if in_brackets {
+            if c == '>' {
+                in_brackets = false;
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == '<' {
+            in_brackets = true;
+        } else if c == delimiter {
+            let element = trim_ows(&input[start..i]);
+            if !element.is_empty() {
+                output.push(element);
+            }
+            start = i + c.len_utf8();
+        }
+    }
+
+    let element = trim_ows(&input[start..]);
+    if !element.is_empty() {
+        output.push(element);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token() {
+        assert_eq!(parse_token("foo=bar"), (Some("foo"), "=bar"));
+        assert_eq!(parse_token("foo"), (Some("foo"), ""));
+        assert_eq!(parse_token("=bar"), (None, "=bar"));
+        assert_eq!(parse_token(""), (None, ""));
+        assert!(is_token("x-custom_1.0"));
+        assert!(!is_token("has space"));
+        assert!(!is_token(""));
+    }
+
+    #[test]
+    fn quoted_string() {
+        assert_eq!(
+            parse_quoted_string(r#""hello world", rest"#),
+            (Some(Cow::Borrowed("hello world")), ", rest")
+        );
+        assert_eq!(
+            parse_quoted_string(r#""a \"quoted\" \\ value""#),
+            (Some(Cow::Owned(r#"a "quoted" \ value"#.to_string())), "")
+        );
+        assert_eq!(parse_quoted_string(r#""""#), (Some(Cow::Borrowed("")), ""));
+        assert_eq!(parse_quoted_string(r#""unterminated"#).0, None);
+        assert_eq!(parse_quoted_string("token").0, None);
+        assert_eq!(parse_quoted_string("\"bad\u{7}char\"").0, None);
+    }
+
+    #[test]
+    fn param() {
+        assert_eq!(parse_param("a=b"), Some(("a", "b".to_string())));
+        assert_eq!(
+            parse_param("a = \"b; c\" "),
+            Some(("a", "b; c".to_string()))
+        );
+        assert_eq!(
+            parse_param("type=text/html"),
+            Some(("type", "text/html".to_string()))
+        );
+        assert_eq!(parse_param("flag"), Some(("flag", String::new())));
+        assert_eq!(parse_param("a=\"b"), None);
+        assert_eq!(parse_param("a=b c"), None);
+        assert_eq!(parse_param("=b"), None);
+    }
+
+    #[test]
+    fn quoted_string_roundtrip() {
+        let value = r#"with "quotes" and \ backslashes"#;
+        let quoted = fmt_quoted_string(value);
+        assert_eq!(
+            parse_quoted_string(&quoted),
+            (Some(Cow::Borrowed(value)), "")
+        );
+    }
+
+    #[test]
+    fn ext_value() {
+        assert_eq!(
+            parse_ext_value("UTF-8'en'%E2%82%AC%20rates").as_deref(),
+            Some("\u{20ac} rates")
+        );
+        assert_eq!(
+            parse_ext_value("iso-8859-1''%A3%20rates").as_deref(),
+            Some("\u{a3} rates")
+        );
+        assert_eq!(parse_ext_value("UTF-8''%FF"), None);
+        assert_eq!(parse_ext_value("UTF-8''%2"), None);
+        assert_eq!(parse_ext_value("UTF-8''%+1"), None);
+        assert_eq!(parse_ext_value("UTF-8''a b"), None);
+        assert_eq!(parse_ext_value("UTF-16''a"), None);
+        assert_eq!(parse_ext_value("no quotes"), None);
+
+        let value = "\u{20ac} 100, \"quoted\"";
+        assert_eq!(
+            fmt_ext_value(value),
+            "UTF-8''%E2%82%AC%20100%2C%20%22quoted%22"
+        );
+        assert_eq!(
+            parse_ext_value(&fmt_ext_value(value)).as_deref(),
+            Some(value)
+        );
+    }
+
+    #[test]
+    fn list() {
+        assert_eq!(split_list("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(split_list(" , a,, b, "), vec!["a", "b"]);
+        assert_eq!(
+            split_list(r#"a="x, y", <https://a.b/?c,d>; rel=next, b"#),
+            vec![r#"a="x, y""#, "<https://a.b/?c,d>; rel=next", "b"]
+        );
+        assert_eq!(split_list(r#"a="\"," , b"#), vec![r#"a="\",""#, "b"]);
+        assert!(split_list("").is_empty());
+    }
+}
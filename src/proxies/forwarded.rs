@@ -0,0 +1,546 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, FORWARDED};
+use crate::parse_utils::{
+    fmt_token_or_quoted_string, parse_quoted_string, parse_token, split_list, split_outside_quotes,
+    trim_ows,
+};
+use crate::{Error, StatusCode};
+
+const X_FORWARDED_FOR: &str = "X-Forwarded-For";
+const X_FORWARDED_HOST: &str = "X-Forwarded-Host";
+const X_FORWARDED_PROTO: &str = "X-Forwarded-Proto";
+
+/// Proxy header disclosing information which is altered or lost when a proxy
+/// is involved in the path of a request.
+///
+/// Every proxy appends an element to the header, so the first element
+/// describes the connection from the client to the first proxy, and the last
+/// element the connection to the nearest proxy.
+///
+/// # Specifications
+///
+/// - [RFC7239: Forwarded HTTP Extension](https://tools.ietf.org/html/rfc7239)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::proxies::{Forwarded, ForwardedElement};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut element = ForwardedElement::new();
+/// element.set_for("[2001:db8:cafe::17]:4711");
+/// element.set_proto("https");
+///
+/// let mut forwarded = Forwarded::new();
+/// forwarded.push(element);
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// forwarded.apply(&mut req);
+/// assert_eq!(req["Forwarded"], r#"for="[2001:db8:cafe::17]:4711";proto=https"#);
+///
+/// let forwarded = Forwarded::from_headers(&req)?.unwrap();
+/// assert_eq!(forwarded.forwarded_for(), vec!["[2001:db8:cafe::17]:4711"]);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Forwarded<'a> {
+    elements: Vec<ForwardedElement<'a>>,
+}
+
+impl<'a> Forwarded<'a> {
+    /// Create a new instance of `Forwarded`.
+    pub fn new() -> Self {
+        Self { elements: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// The `Forwarded` header is used if it's present. Otherwise the
+    /// `X-Forwarded-For`, `X-Forwarded-Host` and `X-Forwarded-Proto` headers
+    /// are used.
+    pub fn from_headers(headers: &'a impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        match Self::from_forwarded_header(headers)? {
+            Some(forwarded) => Ok(Some(forwarded)),
+            None => Ok(Self::from_x_forwarded_headers(headers)),
+        }
+    }
+
+    /// Create a new instance from the `Forwarded` header only.
+    ///
+    /// All `Forwarded` header values are combined.
+    pub fn from_forwarded_header(headers: &'a impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(FORWARDED) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut forwarded = Self::new();
+        for value in values {
+            forwarded
+                .elements
+                .extend(Self::parse(value.as_str())?.elements);
+        }
+        Ok(Some(forwarded))
+    }
+
+    /// Create a new instance from the `X-Forwarded-For`, `X-Forwarded-Host`
+    /// and `X-Forwarded-Proto` headers.
+    ///
+    /// Every address in `X-Forwarded-For` becomes an element. Hosts and
+    /// protocols are attributed to the element at the same position, and
+    /// only if their list has as many entries as there are elements. A proxy
+    /// appending to one list but not the other could otherwise attribute a
+    /// value sent by the client to the proxy's own element.
+    pub fn from_x_forwarded_headers(headers: &'a impl AsRef<Headers>) -> Option<Self> {
+        let headers = headers.as_ref();
+        let list = |name| -> Vec<&'a str> {
+            headers
+                .get(name)
+                .map(|values| values.iter())
+                .into_iter()
+                .flatten()
+                .flat_map(|value| split_list(value.as_str()))
+                .collect()
+        };
+        let addresses = list(X_FORWARDED_FOR);
+        let hosts = list(X_FORWARDED_HOST);
+        let protos = list(X_FORWARDED_PROTO);
+        if addresses.is_empty() && hosts.is_empty() && protos.is_empty() {
+            return None;
+        }
+
+        let len = addresses.len().max(1);
+        let mut forwarded = Self::new();
+        for i in 0..len {
+            let mut element = ForwardedElement::new();
+            element.forwarded_for = addresses.get(i).copied().map(Cow::Borrowed);
+            if hosts.len() == len {
+                element.host = Some(Cow::Borrowed(hosts[i]));
+            }
+            if protos.len() == len {
+                element.proto = Some(Cow::Borrowed(protos[i]));
+            }
+            forwarded.push(element);
+        }
+        Some(forwarded)
+    }
+
+    /// Parse a `Forwarded` header value.
+    pub fn parse(s: &'a str) -> crate::Result<Self> {
+        let elements = split_list(s)
+            .into_iter()
+            .map(ForwardedElement::parse)
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { elements })
+    }
+
+    /// Push an element to the end of the list, as a proxy would.
+    pub fn push(&mut self, element: ForwardedElement<'a>) {
+        self.elements.push(element);
+    }
+
+    /// Get the `for` node of every element, starting with the client.
+    ///
+    /// Elements without a `for` parameter are skipped.
+    pub fn forwarded_for(&self) -> Vec<&str> {
+        self.elements
+            .iter()
+            .filter_map(|element| element.forwarded_for())
+            .collect()
+    }
+
+    /// Get the `Host` the client originally requested, if disclosed.
+    pub fn host(&self) -> Option<&str> {
+        self.elements.first()?.host()
+    }
+
+    /// Get the protocol the client originally used, if disclosed.
+    pub fn proto(&self) -> Option<&str> {
+        self.elements.first()?.proto()
+    }
+
+    /// Convert into an instance which doesn't borrow from the headers.
+    pub fn into_owned(self) -> Forwarded<'static> {
+        Forwarded {
+            elements: self
+                .elements
+                .into_iter()
+                .map(ForwardedElement::into_owned)
+                .collect(),
+        }
+    }
+
+    /// Sets the `Forwarded` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        FORWARDED
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Forwarded parameters should be valid ASCII")
+    }
+
+    /// An iterator visiting all elements, starting with the client.
+    pub fn iter(&self) -> Iter<'_, 'a> {
+        Iter {
+            inner: self.elements.iter(),
+        }
+    }
+}
+
+impl<'a> Display for Forwarded<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, element) in self.elements.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", element)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Forwarded<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Forwarded::parse(s)?.into_owned())
+    }
+}
+
+impl<'a> IntoIterator for Forwarded<'a> {
+    type Item = ForwardedElement<'a>;
+    type IntoIter = std::vec::IntoIter<ForwardedElement<'a>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'b, 'a> IntoIterator for &'b Forwarded<'a> {
+    type Item = &'b ForwardedElement<'a>;
+    type IntoIter = Iter<'b, 'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over elements in `Forwarded`.
+#[derive(Debug)]
+pub struct Iter<'b, 'a> {
+    inner: slice::Iter<'b, ForwardedElement<'a>>,
+}
+
+impl<'b, 'a> Iterator for Iter<'b, 'a> {
+    type Item = &'b ForwardedElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A single element of the `Forwarded` header, added by one proxy.
+///
+/// # Specifications
+///
+/// - [RFC7239, section 5: Parameters](https://tools.ietf.org/html/rfc7239#section-5)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForwardedElement<'a> {
+    pub(crate) by: Option<Cow<'a, str>>,
+    pub(crate) forwarded_for: Option<Cow<'a, str>>,
+    pub(crate) host: Option<Cow<'a, str>>,
+    pub(crate) proto: Option<Cow<'a, str>>,
+    extensions: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> ForwardedElement<'a> {
+    /// Create a new, empty, element.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the interface where the request came in to the proxy.
+    pub fn by(&self) -> Option<&str> {
+        self.by.as_deref()
+    }
+
+    /// Set the interface where the request came in to the proxy.
+    pub fn set_by(&mut self, by: impl Into<Cow<'a, str>>) {
+        self.by = Some(by.into());
+    }
+
+    /// Get the node making the request to the proxy.
+    ///
+    /// This is an IP address with an optional port, with IPv6 addresses in
+    /// brackets, an obfuscated identifier starting with `_`, or `unknown`.
+    pub fn forwarded_for(&self) -> Option<&str> {
+        self.forwarded_for.as_deref()
+    }
+
+    /// Set the node making the request to the proxy.
+    pub fn set_for(&mut self, forwarded_for: impl Into<Cow<'a, str>>) {
+        self.forwarded_for = Some(forwarded_for.into());
+    }
+
+    /// Get the `Host` request header field as received by the proxy.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Set the `Host` request header field as received by the proxy.
+    pub fn set_host(&mut self, host: impl Into<Cow<'a, str>>) {
+        self.host = Some(host.into());
+    }
+
+    /// Get the protocol used to make the request, e.g. `https`.
+    pub fn proto(&self) -> Option<&str> {
+        self.proto.as_deref()
+    }
+
+    /// Set the protocol used to make the request.
+    pub fn set_proto(&mut self, proto: impl Into<Cow<'a, str>>) {
+        self.proto = Some(proto.into());
+    }
+
+    /// Get the value of an extension parameter.
+    pub fn extension(&self, name: &str) -> Option<&str> {
+        self.extensions
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Set an extension parameter.
+    pub fn set_extension(&mut self, name: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) {
+        let name = name.into();
+        self.extensions
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(&name));
+        self.extensions.push((name, value.into()));
+    }
+
+    /// Convert into an instance which doesn't borrow from the headers.
+    pub fn into_owned(self) -> ForwardedElement<'static> {
+        let owned = |value: Cow<'a, str>| Cow::Owned(value.into_owned());
+        ForwardedElement {
+            by: self.by.map(owned),
+            forwarded_for: self.forwarded_for.map(owned),
+            host: self.host.map(owned),
+            proto: self.proto.map(owned),
+            extensions: self
+                .extensions
+                .into_iter()
+                .map(|(name, value)| (owned(name), owned(value)))
+                .collect(),
+        }
+    }
+
+    fn parse(s: &'a str) -> crate::Result<Self> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Forwarded element", s),
+            )
+        };
+
+        let mut element = Self::new();
+        for pair in split_outside_quotes(s, ';') {
+            let (name, rest) = match parse_token(pair) {
+                (Some(name), rest) => (name, rest.strip_prefix('=').ok_or_else(invalid)?),
+                (None, _) => return Err(invalid()),
+            };
+            let value = match parse_value(rest) {
+                Some(value) => value,
+                None => return Err(invalid()),
+            };
+
+            // Each parameter must occur at most once per element.
+            let slot = match name.to_ascii_lowercase().as_str() {
+                "by" => &mut element.by,
+                "for" => &mut element.forwarded_for,
+                "host" => &mut element.host,
+                "proto" => &mut element.proto,
+                _ => {
+                    if element.extension(name).is_some() {
+                        return Err(invalid());
+                    }
+                    element.extensions.push((Cow::Borrowed(name), value));
+                    continue;
+                }
+            };
+            if slot.replace(value).is_some() {
+                return Err(invalid());
+            }
+        }
+        Ok(element)
+    }
+}
+
+impl<'a> Display for ForwardedElement<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = [
+            ("by", &self.by),
+            ("for", &self.forwarded_for),
+            ("host", &self.host),
+            ("proto", &self.proto),
+        ];
+        let params = params
+            .iter()
+            .filter_map(|(name, value)| Some((*name, value.as_deref()?)))
+            .chain(
+                self.extensions
+                    .iter()
+                    .map(|(name, value)| (name.as_ref(), value.as_ref())),
+            );
+        for (n, (name, value)) in params.enumerate() {
+            if n > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{}={}", name, fmt_token_or_quoted_string(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a parameter value.
+///
+/// Values should be a `token` or a `quoted-string`, but many proxies don't
+/// quote IPv6 addresses and ports, so other unquoted visible characters are
+/// accepted too.
+fn parse_value(input: &str) -> Option<Cow<'_, str>> {
+    let input = trim_ows(input);
+    if input.starts_with('"') {
+        match parse_quoted_string(input) {
+            (Some(value), rest) if trim_ows(rest).is_empty() => Some(value),
+            _ => None,
+        }
+    } else if !input.is_empty() && input.chars().all(|c| c.is_ascii_graphic() && c != '"') {
+        Some(Cow::Borrowed(input))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_rfc_examples() -> crate::Result<()> {
+        let forwarded = Forwarded::parse(r#"For="[2001:db8:cafe::17]:4711""#)?;
+        assert_eq!(forwarded.forwarded_for(), vec!["[2001:db8:cafe::17]:4711"]);
+
+        let forwarded = Forwarded::parse("for=192.0.2.60;proto=http;by=203.0.113.43")?;
+        let element = forwarded.iter().next().unwrap();
+        assert_eq!(element.forwarded_for(), Some("192.0.2.60"));
+        assert_eq!(element.proto(), Some("http"));
+        assert_eq!(element.by(), Some("203.0.113.43"));
+
+        let forwarded = Forwarded::parse("for=192.0.2.43, for=198.51.100.17")?;
+        assert_eq!(
+            forwarded.forwarded_for(),
+            vec!["192.0.2.43", "198.51.100.17"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> crate::Result<()> {
+        let value = r#"for=_hidden;host="example.com:8080";secret="a\"b", for=unknown"#;
+        let forwarded: Forwarded<'static> = value.parse()?;
+        let element = forwarded.iter().next().unwrap();
+        assert_eq!(element.host(), Some("example.com:8080"));
+        assert_eq!(element.extension("Secret"), Some(r#"a"b"#));
+        assert_eq!(forwarded.to_string(), value);
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_headers() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append("Forwarded", "for=a");
+        headers.append("Forwarded", "for=b, for=c");
+        let forwarded = Forwarded::from_headers(&headers)?.unwrap();
+        assert_eq!(forwarded.forwarded_for(), vec!["a", "b", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn x_forwarded_fallback() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert("X-Forwarded-For", "192.0.2.43, 2001:db8:cafe::17");
+        headers.insert("X-Forwarded-Host", "example.com, internal.example.com");
+        headers.insert("X-Forwarded-Proto", "https");
+        headers.append("X-Forwarded-Proto", "http");
+
+        let forwarded = Forwarded::from_headers(&headers)?.unwrap();
+        assert_eq!(
+            forwarded.forwarded_for(),
+            vec!["192.0.2.43", "2001:db8:cafe::17"]
+        );
+        assert_eq!(forwarded.host(), Some("example.com"));
+        assert_eq!(forwarded.proto(), Some("https"));
+        let last = forwarded.iter().last().unwrap();
+        assert_eq!(last.host(), Some("internal.example.com"));
+        assert_eq!(last.proto(), Some("http"));
+
+        // Hosts which don't line up with the addresses are ignored.
+        headers.insert("X-Forwarded-Host", "example.com");
+        let forwarded = Forwarded::from_headers(&headers)?.unwrap();
+        assert_eq!(forwarded.host(), None);
+        assert_eq!(forwarded.proto(), Some("https"));
+
+        headers.remove("X-Forwarded-For");
+        let forwarded = Forwarded::from_headers(&headers)?.unwrap();
+        assert_eq!(forwarded.forwarded_for(), Vec::<&str>::new());
+        assert_eq!(forwarded.host(), Some("example.com"));
+        assert_eq!(forwarded.proto(), None);
+
+        // The Forwarded header takes precedence.
+        headers.insert("Forwarded", "for=192.0.2.60");
+        let forwarded = Forwarded::from_headers(&headers)?.unwrap();
+        assert_eq!(forwarded.forwarded_for(), vec!["192.0.2.60"]);
+        assert_eq!(forwarded.host(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "for",
+            "for=",
+            "for=[::1] a",
+            r#"for="unterminated"#,
+            "for=a;for=b",
+            "for=a b",
+        ] {
+            let err = Forwarded::parse(s).unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
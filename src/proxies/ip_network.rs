@@ -0,0 +1,173 @@
+use std::fmt::{self, Display};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::{Error, StatusCode};
+
+/// A range of IP addresses in CIDR notation, e.g. `10.0.0.0/8`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::proxies::IpNetwork;
+///
+/// let network: IpNetwork = "192.168.0.0/16".parse()?;
+/// assert!(network.contains("192.168.1.1".parse()?));
+/// assert!(!network.contains("10.0.0.1".parse()?));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Create a new instance from an address and a prefix length.
+    ///
+    /// Bits of the address beyond the prefix length are ignored.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the prefix length is longer than the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> crate::Result<Self> {
+        crate::ensure!(
+            prefix_len <= max_prefix_len(&addr),
+            "IpNetwork prefix length should not be longer than the address"
+        );
+        Ok(Self { addr, prefix_len })
+    }
+
+    /// Get the address of the network.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Get the prefix length of the network.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns `true` if the address is part of the network.
+    ///
+    /// IPv4 addresses are never part of an IPv6 network, nor the other way
+    /// around.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                let mask = mask(self.prefix_len, 32) as u32;
+                u32::from(network) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                let mask = mask(self.prefix_len, 128);
+                u128::from(network) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A mask with the first `prefix_len` bits of a `bits` long address set.
+fn mask(prefix_len: u8, bits: u32) -> u128 {
+    match u32::from(prefix_len) {
+        0 => 0,
+        len => (!0u128 << (128 - len)) >> (128 - bits),
+    }
+}
+
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+impl From<IpAddr> for IpNetwork {
+    fn from(addr: IpAddr) -> Self {
+        let prefix_len = max_prefix_len(&addr);
+        Self { addr, prefix_len }
+    }
+}
+
+impl From<Ipv4Addr> for IpNetwork {
+    fn from(addr: Ipv4Addr) -> Self {
+        IpAddr::V4(addr).into()
+    }
+}
+
+impl From<Ipv6Addr> for IpNetwork {
+    fn from(addr: Ipv6Addr) -> Self {
+        IpAddr::V6(addr).into()
+    }
+}
+
+impl Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = Error;
+
+    /// Parse a network in CIDR notation. A single address without a prefix
+    /// length is parsed as a network containing only that address.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid IP network", s),
+            )
+        };
+
+        let mut parts = s.trim().splitn(2, '/');
+        let addr: IpAddr = match parts.next() {
+            Some(addr) => addr.parse().map_err(|_| invalid())?,
+            None => return Err(invalid()),
+        };
+        match parts.next() {
+            Some(prefix_len) => {
+                let prefix_len = prefix_len.parse().map_err(|_| invalid())?;
+                Self::new(addr, prefix_len).map_err(|_| invalid())
+            }
+            None => Ok(addr.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn contains() -> crate::Result<()> {
+        let network: IpNetwork = "10.0.0.0/8".parse()?;
+        assert!(network.contains("10.255.0.1".parse()?));
+        assert!(!network.contains("11.0.0.1".parse()?));
+        assert!(!network.contains("::ffff:10.0.0.1".parse()?));
+
+        let network: IpNetwork = "2001:db8::/32".parse()?;
+        assert!(network.contains("2001:db8:cafe::17".parse()?));
+        assert!(!network.contains("2001:db9::1".parse()?));
+
+        let network: IpNetwork = "0.0.0.0/0".parse()?;
+        assert!(network.contains("192.0.2.1".parse()?));
+
+        let network: IpNetwork = "::1".parse()?;
+        assert_eq!(network.to_string(), "::1/128");
+        assert!(network.contains("::1".parse()?));
+        assert!(!network.contains("::2".parse()?));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "10.0.0.0/33", "::/129", "10.0.0/8", "10.0.0.0/a"] {
+            let err = s.parse::<IpNetwork>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
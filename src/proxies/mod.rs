@@ -0,0 +1,32 @@
+//! Headers set by proxies.
+//!
+//! # Specifications
+//!
+//! - [RFC7239: Forwarded HTTP Extension](https://tools.ietf.org/html/rfc7239)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::proxies::Forwarded;
+//! use http_types::{Method, Request, Url};
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//! req.insert_header("Forwarded", "for=192.0.2.60;proto=https, for=198.51.100.17");
+//!
+//! let forwarded = Forwarded::from_headers(&req)?.unwrap();
+//! assert_eq!(forwarded.forwarded_for(), vec!["192.0.2.60", "198.51.100.17"]);
+//! assert_eq!(forwarded.proto(), Some("https"));
+//! assert_eq!(req.remote(), Some("192.0.2.60"));
+//! #
+//! # Ok(()) }
+//! ```
+
+mod forwarded;
+mod ip_network;
+mod trusted_proxies;
+
+pub use forwarded::{Forwarded, ForwardedElement};
+pub use ip_network::IpNetwork;
+pub use trusted_proxies::TrustedProxies;
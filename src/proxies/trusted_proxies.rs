@@ -0,0 +1,131 @@
+use std::net::{IpAddr, SocketAddr};
+
+use crate::proxies::IpNetwork;
+
+/// A policy deciding which proxies are trusted to report the client address.
+///
+/// Forwarding headers can be set by anyone, so they can only be relied upon
+/// when they were added by proxies under our control. The forwarding chain is
+/// walked from the peer address inward, and the first address which isn't a
+/// trusted proxy is taken to be the client.
+///
+/// Proxies are trusted either because their address is part of a trusted
+/// network, or because they are within a fixed number of hops from the
+/// server.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::proxies::TrustedProxies;
+/// use http_types::{Method, Request, Url};
+///
+/// let mut policy = TrustedProxies::new();
+/// policy.push("10.0.0.0/8".parse()?);
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.set_peer_addr(Some("10.0.0.2:4711"));
+/// req.insert_header("X-Forwarded-For", "203.0.113.1, 198.51.100.17, 10.0.0.1");
+///
+/// assert_eq!(req.remote(), Some("203.0.113.1"));
+/// assert_eq!(req.remote_with(&policy).as_deref(), Some("198.51.100.17"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<IpNetwork>,
+    hops: usize,
+}
+
+impl TrustedProxies {
+    /// Create a new instance of `TrustedProxies`, which trusts no proxies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust all proxies with an address in the network.
+    pub fn push(&mut self, network: IpNetwork) {
+        self.networks.push(network);
+    }
+
+    /// Get the number of hops from the server which are always trusted.
+    pub fn hops(&self) -> usize {
+        self.hops
+    }
+
+    /// Always trust this number of hops from the server, whatever their
+    /// address.
+    ///
+    /// The peer address of the transport is the first hop, so a value of `1`
+    /// trusts a single proxy in front of the server.
+    pub fn set_hops(&mut self, hops: usize) {
+        self.hops = hops;
+    }
+
+    /// Returns `true` if the node is part of one of the trusted networks.
+    ///
+    /// The node may be an IP address with an optional port, with IPv6
+    /// addresses in brackets. Obfuscated and `unknown` nodes are never
+    /// trusted.
+    pub fn is_trusted(&self, node: &str) -> bool {
+        match parse_node(node) {
+            Some(addr) => self.networks.iter().any(|network| network.contains(addr)),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the node at this number of hops from the server is
+    /// trusted.
+    pub(crate) fn is_trusted_hop(&self, node: &str, hop: usize) -> bool {
+        hop < self.hops || self.is_trusted(node)
+    }
+}
+
+/// Parse the IP address of a node, ignoring the port.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim();
+    if let Ok(addr) = node.parse::<IpAddr>() {
+        return Some(addr);
+    }
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    let addr = node.strip_prefix('[')?.strip_suffix(']')?;
+    addr.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trusted_nodes() -> crate::Result<()> {
+        let mut policy = TrustedProxies::new();
+        policy.push("10.0.0.0/8".parse()?);
+        policy.push("2001:db8::/32".parse()?);
+
+        for node in &[
+            "10.0.0.1",
+            "10.0.0.1:80",
+            "[2001:db8::1]:80",
+            "[2001:db8::1]",
+        ] {
+            assert!(policy.is_trusted(node), "{}", node);
+        }
+        for node in &["192.0.2.1", "[2001:db9::1]", "unknown", "_hidden", ""] {
+            assert!(!policy.is_trusted(node), "{}", node);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn trusted_hops() {
+        let mut policy = TrustedProxies::new();
+        policy.set_hops(2);
+        assert!(policy.is_trusted_hop("192.0.2.1", 0));
+        assert!(policy.is_trusted_hop("unknown", 1));
+        assert!(!policy.is_trusted_hop("192.0.2.1", 2));
+    }
+}
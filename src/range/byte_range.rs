@@ -0,0 +1,123 @@
+use std::fmt::{self, Display};
+use std::ops;
+use std::str::FromStr;
+
+use crate::{Error, StatusCode};
+
+/// A single range of bytes requested by the `Range` header.
+///
+/// # Specifications
+///
+/// - [RFC7233, section 2.1: Byte Ranges](https://tools.ietf.org/html/rfc7233#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::range::ByteRange;
+///
+/// assert_eq!(ByteRange::FromTo(0, 99).resolve(1000), Some(0..100));
+/// assert_eq!(ByteRange::From(900).resolve(1000), Some(900..1000));
+/// assert_eq!(ByteRange::Last(100).resolve(1000), Some(900..1000));
+/// assert_eq!(ByteRange::From(1000).resolve(1000), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// The bytes between the first and last position, both inclusive.
+    FromTo(u64, u64),
+    /// All bytes from the first position to the end of the representation.
+    From(u64),
+    /// The final number of bytes of the representation.
+    Last(u64),
+}
+
+impl ByteRange {
+    /// Resolve the range against the length of a representation.
+    ///
+    /// Returns the half-open range of bytes to send, clipped to the length
+    /// of the representation, or `None` if the range isn't satisfiable.
+    pub fn resolve(&self, len: u64) -> Option<ops::Range<u64>> {
+        match *self {
+            Self::FromTo(first, _) | Self::From(first) if first >= len => None,
+            Self::FromTo(first, last) => Some(first..len.min(last.saturating_add(1))),
+            Self::From(first) => Some(first..len),
+            Self::Last(0) => None,
+            Self::Last(_) if len == 0 => None,
+            Self::Last(suffix) => Some(len.saturating_sub(suffix)..len),
+        }
+    }
+}
+
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FromTo(first, last) => write!(f, "{}-{}", first, last),
+            Self::From(first) => write!(f, "{}-", first),
+            Self::Last(suffix) => write!(f, "-{}", suffix),
+        }
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid byte range", s),
+            )
+        };
+        let parse = |pos: &str| -> crate::Result<u64> {
+            if !pos.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            pos.parse().map_err(|_| invalid())
+        };
+
+        let mut parts = s.trim().splitn(2, '-');
+        let range = match (parts.next(), parts.next()) {
+            (Some(""), Some(suffix)) => Self::Last(parse(suffix)?),
+            (Some(first), Some("")) => Self::From(parse(first)?),
+            (Some(first), Some(last)) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if last < first {
+                    return Err(invalid());
+                }
+                Self::FromTo(first, last)
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!("0-499".parse::<ByteRange>()?, ByteRange::FromTo(0, 499));
+        assert_eq!("9500-".parse::<ByteRange>()?, ByteRange::From(9500));
+        assert_eq!("-500".parse::<ByteRange>()?, ByteRange::Last(500));
+        for s in &["", "-", "500", "5-4", "a-b", "+1-2", "-+1"] {
+            let err = s.parse::<ByteRange>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn resolve() {
+        assert_eq!(ByteRange::FromTo(500, 999).resolve(10000), Some(500..1000));
+        assert_eq!(
+            ByteRange::FromTo(500, 20000).resolve(10000),
+            Some(500..10000)
+        );
+        assert_eq!(ByteRange::FromTo(10000, 10001).resolve(10000), None);
+        assert_eq!(ByteRange::Last(20000).resolve(10000), Some(0..10000));
+        assert_eq!(ByteRange::Last(0).resolve(10000), None);
+        assert_eq!(ByteRange::Last(10).resolve(0), None);
+        assert_eq!(ByteRange::From(0).resolve(0), None);
+    }
+}
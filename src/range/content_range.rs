@@ -0,0 +1,230 @@
+use std::fmt::{self, Display};
+use std::ops;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, CONTENT_RANGE};
+use crate::{Error, StatusCode};
+
+/// Server header indicating which part of a representation is sent.
+///
+/// Only the `bytes` range unit is supported.
+///
+/// # Specifications
+///
+/// - [RFC7233, section 4.2: Content-Range](https://tools.ietf.org/html/rfc7233#section-4.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::ContentRange;
+/// use http_types::Response;
+///
+/// let content_range = ContentRange::new(0..100, Some(1000))?;
+///
+/// let mut res = Response::new(206);
+/// content_range.apply(&mut res);
+/// assert_eq!(res["Content-Range"], "bytes 0-99/1000");
+///
+/// let content_range = ContentRange::from_headers(res)?.unwrap();
+/// assert_eq!(content_range.range(), Some(0..100));
+/// assert_eq!(content_range.complete_length(), Some(1000));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentRange {
+    range: Option<ops::Range<u64>>,
+    complete_length: Option<u64>,
+}
+
+impl ContentRange {
+    /// Create a new instance for a half-open range of bytes.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the range is empty, or if it ends past the
+    /// complete length.
+    pub fn new(range: ops::Range<u64>, complete_length: Option<u64>) -> crate::Result<Self> {
+        crate::ensure!(range.start < range.end, "Content-Range should not be empty");
+        if let Some(complete_length) = complete_length {
+            crate::ensure!(
+                range.end <= complete_length,
+                "Content-Range should end before the complete length"
+            );
+        }
+        Ok(Self {
+            range: Some(range),
+            complete_length,
+        })
+    }
+
+    /// Create a new instance for a `416 Requested Range Not Satisfiable`
+    /// response, e.g. `bytes */1000`.
+    pub fn unsatisfied(complete_length: u64) -> Self {
+        Self {
+            range: None,
+            complete_length: Some(complete_length),
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only a single `Content-Range` header is assumed to exist. If multiple
+    /// headers are found the last one is used.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        match headers.as_ref().get(CONTENT_RANGE) {
+            Some(values) => values.last().as_str().parse().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the half-open range of bytes sent, or `None` for an unsatisfied
+    /// range.
+    pub fn range(&self) -> Option<ops::Range<u64>> {
+        self.range.clone()
+    }
+
+    /// Get the length of the complete representation, if known.
+    pub fn complete_length(&self) -> Option<u64> {
+        self.complete_length
+    }
+
+    /// Sets the `Content-Range` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_RANGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Content ranges should be valid ASCII")
+    }
+}
+
+impl Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.range {
+            Some(range) => write!(f, "bytes {}-{}/", range.start, range.end - 1)?,
+            None => write!(f, "bytes */")?,
+        }
+        match self.complete_length {
+            Some(complete_length) => write!(f, "{}", complete_length),
+            None => write!(f, "*"),
+        }
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Content-Range", s),
+            )
+        };
+        let parse = |pos: &str| -> crate::Result<u64> {
+            if !pos.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            pos.parse().map_err(|_| invalid())
+        };
+
+        let s = s.trim();
+        let rest = match s.get(..6) {
+            Some(unit) if unit.eq_ignore_ascii_case("bytes ") => &s[6..],
+            _ => return Err(invalid()),
+        };
+
+        let mut parts = rest.splitn(2, '/');
+        let (range, complete_length) = match (parts.next(), parts.next()) {
+            (Some(range), Some(complete_length)) => (range, complete_length),
+            _ => return Err(invalid()),
+        };
+        let complete_length = match complete_length {
+            "*" => None,
+            complete_length => Some(parse(complete_length)?),
+        };
+
+        if range == "*" {
+            return match complete_length {
+                Some(complete_length) => Ok(Self::unsatisfied(complete_length)),
+                None => Err(invalid()),
+            };
+        }
+
+        let mut parts = range.splitn(2, '-');
+        let (first, last) = match (parts.next(), parts.next()) {
+            (Some(first), Some(last)) => (parse(first)?, parse(last)?),
+            _ => return Err(invalid()),
+        };
+        if last < first || last == u64::MAX {
+            return Err(invalid());
+        }
+        Self::new(first..last + 1, complete_length).map_err(|_| invalid())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let content_range = ContentRange::new(42..1234, None)?;
+
+        let mut headers = Headers::new();
+        content_range.apply(&mut headers);
+        assert_eq!(headers["Content-Range"], "bytes 42-1233/*");
+
+        let content_range = ContentRange::from_headers(headers)?.unwrap();
+        assert_eq!(content_range.range(), Some(42..1234));
+        assert_eq!(content_range.complete_length(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn unsatisfied() -> crate::Result<()> {
+        let content_range = ContentRange::unsatisfied(1234);
+        assert_eq!(content_range.to_string(), "bytes */1234");
+
+        let content_range: ContentRange = "bytes */1234".parse()?;
+        assert_eq!(content_range.range(), None);
+        assert_eq!(content_range.complete_length(), Some(1234));
+        Ok(())
+    }
+
+    #[test]
+    fn error_code_500() {
+        let err = ContentRange::new(10..10, None).unwrap_err();
+        assert_eq!(err.status(), 500);
+
+        let err = ContentRange::new(0..11, Some(10)).unwrap_err();
+        assert_eq!(err.status(), 500);
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "",
+            "bytes",
+            "bytes 0-9",
+            "bytes */*",
+            "bytes 9-0/10",
+            "bytes 0-10/10",
+            "items 0-9/10",
+            "bytes 0-a/10",
+        ] {
+            let err = s.parse::<ContentRange>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! HTTP range requests.
+//!
+//! Range requests allow a client to request only part of a representation,
+//! e.g. to resume an interrupted download or to seek in a media file.
+//!
+//! # Specifications
+//!
+//! - [RFC7233: Range Requests](https://tools.ietf.org/html/rfc7233)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::range::{ContentRange, Range};
+//! use http_types::{Method, Request, Response, StatusCode, Url};
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//! req.insert_header("Range", "bytes=0-499");
+//!
+//! let range = Range::from_headers(&req)?.unwrap();
+//! let ranges = range.resolve(10_000);
+//!
+//! let mut res = Response::new(StatusCode::PartialContent);
+//! ContentRange::new(ranges[0].clone(), Some(10_000))?.apply(&mut res);
+//! assert_eq!(res["Content-Range"], "bytes 0-499/10000");
+//! #
+//! # Ok(()) }
+//! ```
+
+mod byte_range;
+mod content_range;
+mod partial_content;
+mod range_header;
+
+pub use byte_range::ByteRange;
+pub use content_range::ContentRange;
+pub use partial_content::{respond, MAX_RANGES};
+pub use range_header::Range;
@@ -0,0 +1,323 @@
+use async_std::io::prelude::*;
+use async_std::io::{self, BufReader, SeekFrom};
+use async_std::task::{self, Context, Poll};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use std::collections::VecDeque;
+use std::ops;
+use std::pin::Pin;
+
+use crate::headers::ACCEPT_RANGES;
+use crate::range::{ContentRange, Range};
+use crate::{Body, Method, Mime, Request, Response, StatusCode};
+
+/// The most ranges [`respond`] serves in a single response. Requests asking
+/// for more ranges are answered with the full representation.
+pub const MAX_RANGES: usize = 16;
+
+/// Respond to a request with the ranges of a representation it asked for.
+///
+/// The representation is read from a seekable source, such as a file, of
+/// which the length and media type are known. The `Range` header of `GET`
+/// requests is honored:
+///
+/// - a single satisfiable range is answered with `206 Partial Content`.
+/// - multiple satisfiable ranges are answered with `206 Partial Content` and
+///   a `multipart/byteranges` body.
+/// - if none of the ranges is satisfiable, the response is
+///   `416 Requested Range Not Satisfiable`.
+/// - otherwise, including when the `Range` header is malformed or asks for
+///   more than [`MAX_RANGES`] ranges, the full representation is sent with
+///   `200 OK`.
+///
+/// Overlapping and adjacent ranges are merged, so each byte is sent at most
+/// once.
+///
+/// The body is streamed from the source. Preconditions are not evaluated: use
+/// [`conditional::evaluate`](crate::conditional::evaluate) beforehand, and
+/// don't call this function when the result is
+/// [`Precondition::IgnoreRange`](crate::conditional::Precondition::IgnoreRange).
+///
+/// # Errors
+///
+/// An error is returned if seeking in the source fails.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::Cursor;
+/// use http_types::{mime, range, Method, Request, StatusCode, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Range", "bytes=6-");
+///
+/// let source = Cursor::new("Hello Nori");
+/// let mut res = range::respond(&req, source, 10, mime::PLAIN).await?;
+///
+/// assert_eq!(res.status(), StatusCode::PartialContent);
+/// assert_eq!(res["Content-Range"], "bytes 6-9/10");
+/// assert_eq!(&res.body_string().await?, "Nori");
+/// #
+/// # Ok(()) }) }
+/// ```
+pub async fn respond<R>(
+    req: &Request,
+    mut source: R,
+    len: u64,
+    mime: Mime,
+) -> crate::Result<Response>
+where
+    R: Read + Seek + Unpin + Send + Sync + 'static,
+{
+    // Many small ranges are costly to serve, and a common way to abuse range
+    // requests, see RFC 7233 section 6.1.
+    let range = match req.method() {
+        Method::Get => Range::from_headers(req).ok().flatten(),
+        _ => None,
+    }
+    .filter(|range| range.len() <= MAX_RANGES);
+    let ranges = range.map(|range| range.resolve(len));
+
+    let mut res = match ranges {
+        None => {
+            let mut res = Response::new(StatusCode::Ok);
+            res.set_body(Body::from_reader(
+                BufReader::new(source),
+                Some(len as usize),
+            ));
+            res.set_content_type(mime);
+            res
+        }
+        Some(ranges) if ranges.is_empty() => {
+            let mut res = Response::new(StatusCode::RequestedRangeNotSatisfiable);
+            ContentRange::unsatisfied(len).apply(&mut res);
+            res
+        }
+        Some(ranges) if ranges.len() == 1 => {
+            let range = ranges[0].clone();
+            let size = range.end - range.start;
+            source.seek(SeekFrom::Start(range.start)).await?;
+
+            let mut res = Response::new(StatusCode::PartialContent);
+            ContentRange::new(range, Some(len))?.apply(&mut res);
+            let reader = BufReader::new(source.take(size));
+            res.set_body(Body::from_reader(reader, Some(size as usize)));
+            res.set_content_type(mime);
+            res
+        }
+        Some(ranges) => {
+            let boundary: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(24)
+                .collect();
+            let reader = ByteRanges::new(source, &ranges, len, &mime, &boundary)?;
+            let size = reader.len();
+
+            let mut res = Response::new(StatusCode::PartialContent);
+            res.set_body(Body::from_reader(
+                BufReader::new(reader),
+                Some(size as usize),
+            ));
+            let mime = format!("multipart/byteranges; boundary={}", boundary);
+            res.set_content_type(mime.parse()?);
+            res
+        }
+    };
+
+    res.insert_header(ACCEPT_RANGES, "bytes");
+    Ok(res)
+}
+
+/// A part of a `multipart/byteranges` body.
+#[derive(Debug)]
+enum Segment {
+    /// Bytes written before, between and after the ranges.
+    Bytes(Vec<u8>, usize),
+    /// Seek the source to the start of the next range.
+    Seek(u64),
+    /// Copy the remaining bytes of a range from the source.
+    Copy(u64),
+}
+
+/// A reader streaming a `multipart/byteranges` body from a seekable source.
+#[derive(Debug)]
+struct ByteRanges<R> {
+    source: R,
+    segments: VecDeque<Segment>,
+}
+
+impl<R> ByteRanges<R> {
+    fn new(
+        source: R,
+        ranges: &[ops::Range<u64>],
+        len: u64,
+        mime: &Mime,
+        boundary: &str,
+    ) -> crate::Result<Self> {
+        let mut segments = VecDeque::new();
+        for (n, range) in ranges.iter().enumerate() {
+            let content_range = ContentRange::new(range.clone(), Some(len))?;
+            let delimiter = if n == 0 { "" } else { "\r\n" };
+            let header = format!(
+                "{}--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+                delimiter, boundary, mime, content_range
+            );
+            segments.push_back(Segment::Bytes(header.into_bytes(), 0));
+            segments.push_back(Segment::Seek(range.start));
+            segments.push_back(Segment::Copy(range.end - range.start));
+        }
+        let trailer = format!("\r\n--{}--\r\n", boundary);
+        segments.push_back(Segment::Bytes(trailer.into_bytes(), 0));
+        Ok(Self { source, segments })
+    }
+
+    /// The number of bytes left to read.
+    fn len(&self) -> u64 {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Bytes(bytes, pos) => (bytes.len() - pos) as u64,
+                Segment::Seek(_) => 0,
+                Segment::Copy(remaining) => *remaining,
+            })
+            .sum()
+    }
+}
+
+impl<R: Read + Seek + Unpin> Read for ByteRanges<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.segments.front_mut() {
+                None => return Poll::Ready(Ok(0)),
+                Some(Segment::Bytes(bytes, pos)) if *pos < bytes.len() => {
+                    let n = buf.len().min(bytes.len() - *pos);
+                    buf[..n].copy_from_slice(&bytes[*pos..*pos + n]);
+                    *pos += n;
+                    return Poll::Ready(Ok(n));
+                }
+                Some(Segment::Seek(start)) => {
+                    let seek = Pin::new(&mut this.source).poll_seek(cx, SeekFrom::Start(*start));
+                    task::ready!(seek)?;
+                }
+                Some(Segment::Copy(remaining)) if *remaining > 0 => {
+                    let max = (buf.len() as u64).min(*remaining) as usize;
+                    let read = Pin::new(&mut this.source).poll_read(cx, &mut buf[..max]);
+                    let n = task::ready!(read)?;
+                    if n == 0 && max > 0 {
+                        return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+                    }
+                    *remaining -= n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+                Some(_) => {}
+            }
+            this.segments.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mime, Url};
+    use async_std::io::Cursor;
+
+    const SOURCE: &str = "Hello Chashu and Nori";
+
+    fn request(method: Method, range: Option<&str>) -> Request {
+        let mut req = Request::new(method, Url::parse("https://example.com").unwrap());
+        if let Some(range) = range {
+            req.insert_header("Range", range);
+        }
+        req
+    }
+
+    async fn send(req: &Request) -> crate::Result<Response> {
+        let len = SOURCE.len() as u64;
+        respond(req, Cursor::new(SOURCE), len, mime::PLAIN).await
+    }
+
+    #[async_std::test]
+    async fn full_representation() -> crate::Result<()> {
+        for req in &[
+            request(Method::Get, None),
+            request(Method::Get, Some("bytes=a-b")),
+            request(Method::Post, Some("bytes=0-4")),
+        ] {
+            let mut res = send(req).await?;
+            assert_eq!(res.status(), StatusCode::Ok);
+            assert_eq!(res["Accept-Ranges"], "bytes");
+            assert_eq!(res.len(), Some(SOURCE.len()));
+            assert_eq!(&res.body_string().await?, SOURCE);
+        }
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn single_range() -> crate::Result<()> {
+        let mut res = send(&request(Method::Get, Some("bytes=-4"))).await?;
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res["Content-Range"], "bytes 17-20/21");
+        assert_eq!(res["Content-Type"], "text/plain;charset=utf-8");
+        assert_eq!(res.len(), Some(4));
+        assert_eq!(&res.body_string().await?, "Nori");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn multiple_ranges() -> crate::Result<()> {
+        let mut res = send(&request(Method::Get, Some("bytes=0-4, 17-"))).await?;
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert!(res.header("Content-Range").is_none());
+
+        let content_type = res.content_type().unwrap();
+        assert_eq!(content_type.essence(), "multipart/byteranges");
+        let boundary = content_type.param("boundary").unwrap().to_string();
+
+        let expected = format!(
+            "--{b}\r\nContent-Type: text/plain;charset=utf-8\r\nContent-Range: bytes 0-4/21\r\n\r\nHello\r\n\
+             --{b}\r\nContent-Type: text/plain;charset=utf-8\r\nContent-Range: bytes 17-20/21\r\n\r\nNori\r\n\
+             --{b}--\r\n",
+            b = boundary
+        );
+        assert_eq!(res.len(), Some(expected.len()));
+        assert_eq!(res.body_string().await?, expected);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn overlapping_ranges() -> crate::Result<()> {
+        let mut res = send(&request(Method::Get, Some("bytes=0-,0-,0-"))).await?;
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res["Content-Range"], "bytes 0-20/21");
+        assert_eq!(&res.body_string().await?, SOURCE);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn too_many_ranges() -> crate::Result<()> {
+        let ranges = vec!["0-0"; MAX_RANGES + 1].join(",");
+        let req = request(Method::Get, Some(&format!("bytes={}", ranges)));
+        let mut res = send(&req).await?;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(&res.body_string().await?, SOURCE);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn unsatisfiable_range() -> crate::Result<()> {
+        let mut res = send(&request(Method::Get, Some("bytes=100-"))).await?;
+        assert_eq!(res.status(), StatusCode::RequestedRangeNotSatisfiable);
+        assert_eq!(res["Content-Range"], "bytes */21");
+        assert_eq!(&res.body_string().await?, "");
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt::{self, Display};
+use std::ops;
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, RANGE};
+use crate::parse_utils::split_list;
+use crate::range::ByteRange;
+use crate::{Error, StatusCode};
+
+/// Client header requesting only part of a representation.
+///
+/// Only the `bytes` range unit is supported.
+///
+/// # Specifications
+///
+/// - [RFC7233, section 3.1: Range](https://tools.ietf.org/html/rfc7233#section-3.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::{ByteRange, Range};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut range = Range::new();
+/// range.push(ByteRange::FromTo(0, 99));
+/// range.push(ByteRange::Last(100));
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// range.apply(&mut req);
+/// assert_eq!(req["Range"], "bytes=0-99, -100");
+///
+/// let range = Range::from_headers(req)?.unwrap();
+/// assert_eq!(range.resolve(1000), vec![0..100, 900..1000]);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Range {
+    ranges: Vec<ByteRange>,
+}
+
+impl Range {
+    /// Create a new instance of `Range`.
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only a single `Range` header is assumed to exist. If multiple headers
+    /// are found the last one is used.
+    ///
+    /// # Errors
+    ///
+    /// An error with the status `400` is returned if the header is malformed
+    /// or uses a range unit other than `bytes`.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        match headers.as_ref().get(RANGE) {
+            Some(values) => values.last().as_str().parse().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Push a byte range into the list of ranges.
+    pub fn push(&mut self, range: ByteRange) {
+        self.ranges.push(range);
+    }
+
+    /// Resolve all ranges against the length of a representation.
+    ///
+    /// Ranges which aren't satisfiable are skipped. The others are sorted,
+    /// and ranges which overlap or are adjacent are merged, so no byte is
+    /// sent more than once. If the returned list is empty, the request should
+    /// be answered with `416 Requested Range Not Satisfiable`.
+    pub fn resolve(&self, len: u64) -> Vec<ops::Range<u64>> {
+        let mut ranges: Vec<_> = self
+            .ranges
+            .iter()
+            .filter_map(|range| range.resolve(len))
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut output: Vec<ops::Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match output.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => output.push(range),
+            }
+        }
+        output
+    }
+
+    /// Returns the number of byte ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if there are no byte ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Sets the `Range` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        RANGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Byte ranges should be valid ASCII")
+    }
+
+    /// An iterator visiting all byte ranges.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.ranges.iter(),
+        }
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes=")?;
+        for (n, range) in self.ranges.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", range)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid byte ranges specifier", s),
+            )
+        };
+
+        let mut parts = s.trim().splitn(2, '=');
+        let ranges = match (parts.next(), parts.next()) {
+            (Some(unit), Some(ranges)) if unit.eq_ignore_ascii_case("bytes") => ranges,
+            _ => return Err(invalid()),
+        };
+
+        let ranges = split_list(ranges)
+            .into_iter()
+            .map(|range| range.parse())
+            .collect::<crate::Result<Vec<_>>>()?;
+        if ranges.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { ranges })
+    }
+}
+
+impl IntoIterator for Range {
+    type Item = ByteRange;
+    type IntoIter = std::vec::IntoIter<ByteRange>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Range {
+    type Item = &'a ByteRange;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over byte ranges in `Range`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, ByteRange>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a ByteRange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut range = Range::new();
+        range.push(ByteRange::From(500));
+
+        let mut headers = Headers::new();
+        range.apply(&mut headers);
+
+        let range = Range::from_headers(headers)?.unwrap();
+        assert_eq!(range.iter().next(), Some(&ByteRange::From(500)));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_multiple_ranges() -> crate::Result<()> {
+        let range: Range = "bytes=0-0,-1, 500-".parse()?;
+        assert_eq!(
+            range.into_iter().collect::<Vec<_>>(),
+            vec![
+                ByteRange::FromTo(0, 0),
+                ByteRange::Last(1),
+                ByteRange::From(500)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_skips_unsatisfiable_ranges() -> crate::Result<()> {
+        let range: Range = "bytes=0-9, 2000-, -5".parse()?;
+        assert_eq!(range.resolve(1000), vec![0..10, 995..1000]);
+
+        let range: Range = "bytes=2000-".parse()?;
+        assert!(range.resolve(1000).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_coalesces_ranges() -> crate::Result<()> {
+        let range: Range = "bytes=500-599, 0-,0-, 10-19, -10".parse()?;
+        assert_eq!(range.len(), 5);
+        assert_eq!(range.resolve(1000), vec![0..1000]);
+
+        let range: Range = "bytes=20-29, 0-9, 10-14, 5-7, 40-".parse()?;
+        assert_eq!(range.resolve(50), vec![0..15, 20..30, 40..50]);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["bytes=", "bytes=5-1", "items=0-9", "0-9"] {
+            let mut headers = Headers::new();
+            headers.insert("Range", *s);
+            let err = Range::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
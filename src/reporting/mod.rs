@@ -0,0 +1,52 @@
+//! Report violations, deprecations and network errors to the server.
+//!
+//! Servers declare endpoints in the `Reporting-Endpoints` header, or as
+//! groups in the older `Report-To` header. Policies such as
+//! `Content-Security-Policy` and `NEL` then name the endpoint their reports
+//! are sent to.
+//!
+//! # Specifications
+//!
+//! - [Reporting API (Working Draft)](https://w3c.github.io/reporting/)
+//! - [Network Error Logging (Working Draft)](https://w3c.github.io/network-error-logging/)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::reporting::{EndpointGroups, Nel, ReportTo, ReportToEndpoint, ReportingEndpoints};
+//! use http_types::{Response, Url};
+//! use std::time::Duration;
+//!
+//! let mut res = Response::new(200);
+//!
+//! let mut endpoints = ReportingEndpoints::new();
+//! endpoints.insert("csp", "/reports/csp")?;
+//! endpoints.apply(&mut res);
+//!
+//! let mut group = ReportTo::new(Duration::from_secs(86_400));
+//! group
+//!     .set_group("network-errors")
+//!     .push_endpoint(ReportToEndpoint::new(Url::parse("https://example.com/nel")?));
+//! let mut groups = EndpointGroups::new();
+//! groups.push(group);
+//! groups.apply(&mut res);
+//!
+//! Nel::new("network-errors", Duration::from_secs(86_400)).apply(&mut res);
+//!
+//! assert_eq!(res["Reporting-Endpoints"], r#"csp="/reports/csp""#);
+//! assert_eq!(res["NEL"], r#"{"report_to":"network-errors","max_age":86400}"#);
+//! #
+//! # Ok(()) }
+//! ```
+
+mod nel;
+mod report;
+mod report_to;
+mod reporting_endpoints;
+
+pub use nel::Nel;
+pub use report::Report;
+pub use report_to::{EndpointGroups, GroupsIter, ReportTo, ReportToEndpoint};
+pub use reporting_endpoints::{EndpointsIter, ReportingEndpoints};
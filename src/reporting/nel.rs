@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, NEL};
+use crate::{Error, StatusCode};
+
+/// A Network Error Logging policy, sent in the `NEL` header.
+///
+/// The policy asks the browser to report failed, and optionally successful,
+/// requests to the origin. Reports are sent to the `Report-To` group named
+/// by the policy.
+///
+/// [MDN | Network Error Logging](https://developer.mozilla.org/en-US/docs/Web/HTTP/Network_Error_Logging)
+///
+/// # Specifications
+///
+/// - [Network Error Logging (Working Draft)](https://w3c.github.io/network-error-logging/#nel-response-header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::reporting::Nel;
+/// use http_types::Response;
+/// use std::time::Duration;
+///
+/// let mut nel = Nel::new("network-errors", Duration::from_secs(2_592_000));
+/// nel.set_include_subdomains(true).set_success_fraction(0.01);
+///
+/// let mut res = Response::new(200);
+/// nel.apply(&mut res);
+/// assert_eq!(
+///     res["NEL"],
+///     r#"{"report_to":"network-errors","max_age":2592000,"include_subdomains":true,"success_fraction":0.01}"#
+/// );
+///
+/// let nel = Nel::from_headers(res)?.unwrap();
+/// assert_eq!(nel.failure_fraction(), 1.0);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nel {
+    report_to: String,
+    max_age: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    include_subdomains: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    success_fraction: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure_fraction: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    request_headers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    response_headers: Vec<String>,
+}
+
+impl Nel {
+    /// Create a new policy, reporting to the `Report-To` group `report_to`,
+    /// which the browser remembers for `max_age`.
+    pub fn new(report_to: impl Into<String>, max_age: Duration) -> Self {
+        Self {
+            report_to: report_to.into(),
+            max_age: max_age.as_secs(),
+            include_subdomains: false,
+            success_fraction: None,
+            failure_fraction: None,
+            request_headers: vec![],
+            response_headers: vec![],
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only the first policy is used, as recipients are required to do.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let value = match headers.as_ref().get(NEL) {
+            Some(values) => &values[0],
+            None => return Ok(None),
+        };
+
+        // The header may hold a comma-separated list of policies, which
+        // makes it a JSON array once wrapped in brackets.
+        let list = format!("[{}]", value.as_str());
+        let list: Vec<serde_json::Value> =
+            serde_json::from_str(&list).map_err(|_| invalid(value.as_str()))?;
+        match list.first() {
+            Some(policy) => Ok(Some(policy.to_string().parse()?)),
+            None => Err(invalid(value.as_str())),
+        }
+    }
+
+    /// Sets the `NEL` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        NEL
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group name or a header name contains non-ASCII
+    /// characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("NEL should be valid ASCII")
+    }
+
+    /// Get the name of the `Report-To` group reports are sent to.
+    pub fn report_to(&self) -> &str {
+        &self.report_to
+    }
+
+    /// Set the name of the `Report-To` group reports are sent to.
+    pub fn set_report_to(&mut self, report_to: impl Into<String>) -> &mut Self {
+        self.report_to = report_to.into();
+        self
+    }
+
+    /// Get how long the browser should remember the policy.
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age)
+    }
+
+    /// Set how long the browser should remember the policy.
+    ///
+    /// A `max_age` of zero asks the browser to forget the policy.
+    pub fn set_max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = max_age.as_secs();
+        self
+    }
+
+    /// Returns `true` if the policy applies to all subdomains as well.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Set whether the policy applies to all subdomains as well.
+    pub fn set_include_subdomains(&mut self, include_subdomains: bool) -> &mut Self {
+        self.include_subdomains = include_subdomains;
+        self
+    }
+
+    /// Get the fraction of successful requests which are reported, `0.0` by
+    /// default.
+    pub fn success_fraction(&self) -> f64 {
+        self.success_fraction.unwrap_or(0.0)
+    }
+
+    /// Set the fraction of successful requests which are reported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction isn't between `0.0` and `1.0`.
+    pub fn set_success_fraction(&mut self, fraction: f64) -> &mut Self {
+        assert!(is_fraction(fraction), "NEL fractions must be within 0..=1");
+        self.success_fraction = Some(fraction);
+        self
+    }
+
+    /// Get the fraction of failed requests which are reported, `1.0` by
+    /// default.
+    pub fn failure_fraction(&self) -> f64 {
+        self.failure_fraction.unwrap_or(1.0)
+    }
+
+    /// Set the fraction of failed requests which are reported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction isn't between `0.0` and `1.0`.
+    pub fn set_failure_fraction(&mut self, fraction: f64) -> &mut Self {
+        assert!(is_fraction(fraction), "NEL fractions must be within 0..=1");
+        self.failure_fraction = Some(fraction);
+        self
+    }
+
+    /// Get the names of the request headers included in reports.
+    pub fn request_headers(&self) -> &[String] {
+        &self.request_headers
+    }
+
+    /// Include a request header in reports.
+    pub fn push_request_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        self.request_headers.push(name.into().as_str().to_string());
+        self
+    }
+
+    /// Get the names of the response headers included in reports.
+    pub fn response_headers(&self) -> &[String] {
+        &self.response_headers
+    }
+
+    /// Include a response header in reports.
+    pub fn push_response_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        self.response_headers.push(name.into().as_str().to_string());
+        self
+    }
+}
+
+fn is_fraction(fraction: f64) -> bool {
+    (0.0..=1.0).contains(&fraction)
+}
+
+fn invalid(s: &str) -> Error {
+    Error::from_str(
+        StatusCode::BadRequest,
+        format!("`{}` is not a valid NEL policy", s),
+    )
+}
+
+impl Display for Nel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+impl FromStr for Nel {
+    type Err = Error;
+
+    /// Parse a single policy, serialized as JSON.
+    ///
+    /// Policies with fractions outside of `0.0..=1.0` are invalid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nel: Self = serde_json::from_str(s).map_err(|_| invalid(s))?;
+        let fractions = nel.success_fraction.iter().chain(&nel.failure_fraction);
+        if !fractions.copied().all(is_fraction) {
+            return Err(invalid(s));
+        }
+        Ok(nel)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            "NEL",
+            r#"{"report_to": "nel", "max_age": 60, "failure_fraction": 0.5, "request_headers": ["If-None-Match"]},
+               {"report_to": "other", "max_age": 1}"#,
+        );
+        headers.append("NEL", r#"{"report_to": "ignored", "max_age": 1}"#);
+
+        let nel = Nel::from_headers(&headers)?.unwrap();
+        assert_eq!(nel.report_to(), "nel");
+        assert_eq!(nel.max_age(), Duration::from_secs(60));
+        assert!(!nel.include_subdomains());
+        assert_eq!(nel.success_fraction(), 0.0);
+        assert_eq!(nel.failure_fraction(), 0.5);
+        assert_eq!(nel.request_headers(), ["If-None-Match"]);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "",
+            "{}",
+            r#"{"report_to": "nel"}"#,
+            r#"{"report_to": "nel", "max_age": -1}"#,
+            r#"{"report_to": "nel", "max_age": 1, "success_fraction": 1.5}"#,
+        ] {
+            let mut headers = Headers::new();
+            headers.insert("NEL", *s);
+            let err = Nel::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// A report of the Reporting API, as sent in an `application/reports+json`
+/// batch to the endpoints of a `report-to` group.
+///
+/// The body is kept as JSON by default, as a batch can hold several types of
+/// reports.
+///
+/// # Specifications
+///
+/// - [Reporting API (Working Draft)](https://w3c.github.io/reporting/#media-type)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report<T = serde_json::Value> {
+    /// The type of the report, such as `csp-violation`.
+    #[serde(rename = "type")]
+    pub report_type: String,
+    /// The number of milliseconds between the violation and the delivery of
+    /// the report.
+    pub age: u64,
+    /// The URL of the document which generated the report.
+    pub url: String,
+    /// The `User-Agent` of the browser which generated the report.
+    pub user_agent: String,
+    /// The body of the report.
+    pub body: T,
+}
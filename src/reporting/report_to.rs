@@ -0,0 +1,355 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, REPORT_TO};
+use crate::{Error, StatusCode, Url};
+
+/// An endpoint group of the `Report-To` header.
+///
+/// Policies such as `NEL` and the `report-to` directive of
+/// `Content-Security-Policy` name the group their reports are sent to.
+///
+/// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::reporting::{ReportTo, ReportToEndpoint};
+/// use http_types::Url;
+/// use std::time::Duration;
+///
+/// let mut group = ReportTo::new(Duration::from_secs(10_886_400));
+/// group
+///     .set_group("csp")
+///     .push_endpoint(ReportToEndpoint::new(Url::parse("https://example.com/csp")?));
+///
+/// assert_eq!(
+///     group.to_string(),
+///     r#"{"group":"csp","max_age":10886400,"endpoints":[{"url":"https://example.com/csp"}]}"#
+/// );
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReportTo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    max_age: u64,
+    endpoints: Vec<ReportToEndpoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    include_subdomains: Option<bool>,
+}
+
+impl ReportTo {
+    /// Create a new endpoint group, which the browser remembers for
+    /// `max_age`.
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            group: None,
+            max_age: max_age.as_secs(),
+            endpoints: vec![],
+            include_subdomains: None,
+        }
+    }
+
+    /// Get the name of the group, `default` if it has none.
+    pub fn group(&self) -> &str {
+        self.group.as_deref().unwrap_or("default")
+    }
+
+    /// Set the name of the group.
+    pub fn set_group(&mut self, group: impl Into<String>) -> &mut Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Get how long the browser should remember the group.
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age)
+    }
+
+    /// Set how long the browser should remember the group.
+    ///
+    /// A `max_age` of zero asks the browser to forget the group.
+    pub fn set_max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = max_age.as_secs();
+        self
+    }
+
+    /// Returns `true` if the group receives reports of subdomains as well.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains.unwrap_or(false)
+    }
+
+    /// Set whether the group receives reports of subdomains as well.
+    pub fn set_include_subdomains(&mut self, include_subdomains: bool) -> &mut Self {
+        self.include_subdomains = Some(include_subdomains);
+        self
+    }
+
+    /// Get the endpoints of the group.
+    pub fn endpoints(&self) -> &[ReportToEndpoint] {
+        &self.endpoints
+    }
+
+    /// Add an endpoint to the group.
+    pub fn push_endpoint(&mut self, endpoint: ReportToEndpoint) -> &mut Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+}
+
+impl Display for ReportTo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+impl FromStr for ReportTo {
+    type Err = Error;
+
+    /// Parse a single endpoint group, serialized as JSON.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Report-To group", s),
+            )
+        })
+    }
+}
+
+/// An endpoint in a `Report-To` group.
+///
+/// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReportToEndpoint {
+    url: String,
+}
+
+impl ReportToEndpoint {
+    /// Create a new endpoint.
+    pub fn new(url: Url) -> Self {
+        Self { url: url.into() }
+    }
+
+    /// Get the URL reports are sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// The endpoint groups of the `Report-To` header.
+///
+/// `Report-To` is superseded by `Reporting-Endpoints`, but some browsers
+/// only support the former for `NEL`.
+///
+/// # Specifications
+///
+/// - [Reporting API, 2018 Working Draft](https://www.w3.org/TR/2018/WD-reporting-1-20180925/#header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::reporting::{EndpointGroups, ReportTo, ReportToEndpoint};
+/// use http_types::{Response, Url};
+/// use std::time::Duration;
+///
+/// let mut group = ReportTo::new(Duration::from_secs(86_400));
+/// group
+///     .set_group("network-errors")
+///     .push_endpoint(ReportToEndpoint::new(Url::parse("https://example.com/nel")?));
+///
+/// let mut groups = EndpointGroups::new();
+/// groups.push(group);
+///
+/// let mut res = Response::new(200);
+/// groups.apply(&mut res);
+///
+/// let groups = EndpointGroups::from_headers(res)?.unwrap();
+/// let group = groups.get("network-errors").unwrap();
+/// assert_eq!(group.endpoints()[0].url(), "https://example.com/nel");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointGroups {
+    groups: Vec<ReportTo>,
+}
+
+impl EndpointGroups {
+    /// Create a new instance of `EndpointGroups`.
+    pub fn new() -> Self {
+        Self { groups: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Report-To` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(REPORT_TO) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut groups = Self::new();
+        for value in values {
+            // The header is a comma-separated list of JSON objects, which
+            // makes it a JSON array once wrapped in brackets.
+            let list = format!("[{}]", value.as_str());
+            let list: Vec<ReportTo> = serde_json::from_str(&list).map_err(|_| {
+                Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("`{}` is not a valid Report-To", value),
+                )
+            })?;
+            groups.groups.extend(list);
+        }
+        Ok(Some(groups))
+    }
+
+    /// Sets the `Report-To` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        REPORT_TO
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group name or endpoint contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Report-To should be valid ASCII")
+    }
+
+    /// Push a group into the list.
+    pub fn push(&mut self, group: ReportTo) {
+        self.groups.push(group);
+    }
+
+    /// Get a group by name.
+    ///
+    /// If a name is used more than once, the first group is returned, as
+    /// browsers ignore the others.
+    pub fn get(&self, group: &str) -> Option<&ReportTo> {
+        self.groups.iter().find(|g| g.group() == group)
+    }
+
+    /// Returns `true` if there are no groups.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// An iterator visiting all groups.
+    pub fn iter(&self) -> GroupsIter<'_> {
+        GroupsIter {
+            inner: self.groups.iter(),
+        }
+    }
+}
+
+impl Display for EndpointGroups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, group) in self.groups.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", group)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for EndpointGroups {
+    type Item = ReportTo;
+    type IntoIter = std::vec::IntoIter<ReportTo>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.groups.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EndpointGroups {
+    type Item = &'a ReportTo;
+    type IntoIter = GroupsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `EndpointGroups`.
+#[derive(Debug)]
+pub struct GroupsIter<'a> {
+    inner: slice::Iter<'a, ReportTo>,
+}
+
+impl<'a> Iterator for GroupsIter<'a> {
+    type Item = &'a ReportTo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            "Report-To",
+            r#"{"max_age": 10, "endpoints": [{"url": "https://a.example/"}, {"url": "https://b.example/"}]},
+               {"group": "nel", "max_age": 20, "include_subdomains": true, "endpoints": []}"#,
+        );
+        headers.append(
+            "Report-To",
+            r#"{"group": "csp", "max_age": 0, "endpoints": []}"#,
+        );
+
+        let groups = EndpointGroups::from_headers(&headers)?.unwrap();
+        let names: Vec<_> = groups.iter().map(|group| group.group()).collect();
+        assert_eq!(names, vec!["default", "nel", "csp"]);
+        assert_eq!(groups.get("default").unwrap().endpoints().len(), 2);
+        let nel = groups.get("nel").unwrap();
+        assert_eq!(nel.max_age(), Duration::from_secs(20));
+        assert!(nel.include_subdomains());
+
+        let mut headers = Headers::new();
+        groups.apply(&mut headers);
+        assert_eq!(EndpointGroups::from_headers(headers)?.unwrap(), groups);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Report-To", r#"{"group": "csp"}"#);
+        let err = EndpointGroups::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
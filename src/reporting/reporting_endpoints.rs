@@ -0,0 +1,255 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, REPORTING_ENDPOINTS};
+use crate::parse_utils::{fmt_quoted_string, is_sf_key, parse_quoted_string, split_outside_quotes};
+use crate::{Error, StatusCode};
+
+/// The named endpoints of the `Reporting-Endpoints` header.
+///
+/// Reports are sent to an endpoint by naming it, for example in the
+/// `report-to` directive of `Content-Security-Policy`. Endpoint URLs may be
+/// relative to the URL of the response.
+///
+/// # Specifications
+///
+/// - [Reporting API (Working Draft)](https://w3c.github.io/reporting/#header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::reporting::ReportingEndpoints;
+/// use http_types::Response;
+///
+/// let mut endpoints = ReportingEndpoints::new();
+/// endpoints.insert("csp", "https://example.com/csp")?;
+/// endpoints.insert("default", "/reports")?;
+///
+/// let mut res = Response::new(200);
+/// endpoints.apply(&mut res);
+/// assert_eq!(
+///     res["Reporting-Endpoints"],
+///     r#"csp="https://example.com/csp", default="/reports""#
+/// );
+///
+/// let endpoints = ReportingEndpoints::from_headers(res)?.unwrap();
+/// assert_eq!(endpoints.get("default"), Some("/reports"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportingEndpoints {
+    endpoints: Vec<(String, String)>,
+}
+
+impl ReportingEndpoints {
+    /// Create a new instance of `ReportingEndpoints`.
+    pub fn new() -> Self {
+        Self { endpoints: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Reporting-Endpoints` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(REPORTING_ENDPOINTS) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut endpoints = Self::new();
+        for value in values {
+            let parsed: Self = value.as_str().parse()?;
+            for (name, url) in parsed.endpoints {
+                endpoints.set(name, url);
+            }
+        }
+        Ok(Some(endpoints))
+    }
+
+    /// Sets the `Reporting-Endpoints` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        REPORTING_ENDPOINTS
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Reporting-Endpoints should be valid ASCII")
+    }
+
+    /// Insert a named endpoint, replacing the URL if the name is already
+    /// used.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the name isn't a lowercase Structured Fields
+    /// key, or the URL contains characters other than printable ASCII.
+    pub fn insert(&mut self, name: &str, url: &str) -> crate::Result<()> {
+        if !is_sf_key(name) {
+            crate::bail!("`{}` is not a valid reporting endpoint name", name);
+        }
+        if !url.chars().all(|c| matches!(c, ' '..='~')) {
+            crate::bail!("`{}` is not a valid reporting endpoint URL", url);
+        }
+        self.set(name.to_string(), url.to_string());
+        Ok(())
+    }
+
+    fn set(&mut self, name: String, url: String) {
+        match self.endpoints.iter_mut().find(|(n, _)| *n == name) {
+            Some(endpoint) => endpoint.1 = url,
+            None => self.endpoints.push((name, url)),
+        }
+    }
+
+    /// Get the URL of a named endpoint.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.endpoints
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, url)| url.as_str())
+    }
+
+    /// Remove a named endpoint, returning its URL.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self.endpoints.iter().position(|(n, _)| n == name)?;
+        Some(self.endpoints.remove(index).1)
+    }
+
+    /// Returns `true` if there are no endpoints.
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// An iterator visiting all endpoints, as names and URLs.
+    pub fn iter(&self) -> EndpointsIter<'_> {
+        EndpointsIter {
+            inner: self.endpoints.iter(),
+        }
+    }
+}
+
+impl Display for ReportingEndpoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, (name, url)) in self.endpoints.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}={}", name, fmt_quoted_string(url))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ReportingEndpoints {
+    type Err = Error;
+
+    /// Parse a `Reporting-Endpoints` value.
+    ///
+    /// The value is a Structured Fields dictionary of strings. When a name
+    /// appears more than once, the last URL is used. Parameters are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Reporting-Endpoints", s),
+            )
+        };
+
+        let mut endpoints = Self::new();
+        for member in split_outside_quotes(s, ',') {
+            let eq = member.find('=').ok_or_else(invalid)?;
+            let name = &member[..eq];
+            if !is_sf_key(name) {
+                return Err(invalid());
+            }
+            let (url, rest) = parse_quoted_string(&member[eq + 1..]);
+            let url = url.ok_or_else(invalid)?;
+            if !rest.is_empty() && !rest.starts_with(';') {
+                return Err(invalid());
+            }
+            endpoints.set(name.to_string(), url.into_owned());
+        }
+        Ok(endpoints)
+    }
+}
+
+impl<'a> IntoIterator for &'a ReportingEndpoints {
+    type Item = (&'a str, &'a str);
+    type IntoIter = EndpointsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `ReportingEndpoints`.
+#[derive(Debug)]
+pub struct EndpointsIter<'a> {
+    inner: slice::Iter<'a, (String, String)>,
+}
+
+impl<'a> Iterator for EndpointsIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(name, url)| (name.as_str(), url.as_str()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            "Reporting-Endpoints",
+            r#"csp="https://a.example/csp";x=1, nel="/n\"el""#,
+        );
+        headers.append("Reporting-Endpoints", r#"csp="https://b.example/csp""#);
+
+        let endpoints = ReportingEndpoints::from_headers(headers)?.unwrap();
+        let entries: Vec<_> = endpoints.iter().collect();
+        assert_eq!(
+            entries,
+            vec![("csp", "https://b.example/csp"), ("nel", r#"/n"el"#)]
+        );
+        assert_eq!(
+            endpoints.to_string(),
+            r#"csp="https://b.example/csp", nel="/n\"el""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_names() {
+        let mut endpoints = ReportingEndpoints::new();
+        assert!(endpoints.insert("CSP", "/csp").is_err());
+        assert!(endpoints.insert("csp", "/csp\n").is_err());
+        assert!(endpoints.is_empty());
+
+        for s in &["csp", "csp=/csp", "Csp=\"/csp\"", "csp=\"/csp\" x"] {
+            let err = s.parse::<ReportingEndpoints>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
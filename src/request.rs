@@ -1,6 +1,7 @@
 use async_std::io::{self, BufRead, Read};
 use async_std::sync;
 
+use std::borrow::Cow;
 use std::convert::{Into, TryInto};
 use std::mem;
 use std::ops::Index;
@@ -8,13 +9,19 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::convert::{DeserializeOwned, Serialize};
+use crate::expect;
 use crate::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_TYPE, DATE, EXPECT, IF_MODIFIED_SINCE, IF_UNMODIFIED_SINCE,
 };
-use crate::mime::Mime;
+use crate::informational::{self, Informational};
+use crate::mime::{self, Mime};
+use crate::parse_utils::split_list;
+use crate::proxies::{Forwarded, ForwardedElement, TrustedProxies};
+use crate::reporting::Report;
+use crate::security::{CspReport, CspViolation};
 use crate::trailers::{self, Trailers};
-use crate::{Body, Extensions, Method, StatusCode, Url, Version};
+use crate::{Body, Extensions, HttpDate, Method, Status, StatusCode, Url, Version};
 
 pin_project_lite::pin_project! {
     /// An HTTP request.
@@ -41,6 +48,8 @@ pin_project_lite::pin_project! {
         trailers_sender: Option<sync::Sender<Trailers>>,
         trailers_receiver: Option<sync::Receiver<Trailers>>,
         has_trailers: bool,
+        informational_sender: Option<sync::Sender<Informational>>,
+        informational_receiver: Option<sync::Receiver<Informational>>,
     }
 }
 
@@ -53,6 +62,7 @@ impl Request {
     {
         let url = url.try_into().expect("Could not convert into a valid url");
         let (trailers_sender, trailers_receiver) = sync::channel(1);
+        let (informational_sender, informational_receiver) = sync::channel(informational::CAPACITY);
         Self {
             method,
             url,
@@ -65,6 +75,8 @@ impl Request {
             trailers_receiver: Some(trailers_receiver),
             trailers_sender: Some(trailers_sender),
             has_trailers: false,
+            informational_sender: Some(informational_sender),
+            informational_receiver: Some(informational_receiver),
         }
     }
 
@@ -97,48 +109,174 @@ impl Request {
     /// Get the remote address for this request.
     ///
     /// This is determined in the following priority:
-    /// 1. `Forwarded` header `for` key
-    /// 2. The first `X-Forwarded-For` header
+    /// 1. `Forwarded` header `for` key of the first element
+    /// 2. The first `X-Forwarded-For` address
     /// 3. Peer address of the transport
+    ///
+    /// A malformed `Forwarded` header is ignored. Quoted values containing
+    /// escapes can't be borrowed from the header, and are skipped; use
+    /// [`remote_unescaped`](#method.remote_unescaped) to get them.
     pub fn remote(&self) -> Option<&str> {
         self.forwarded_for().or_else(|| self.peer_addr())
     }
 
+    /// Get the remote address for this request, unescaping quoted values of
+    /// the `Forwarded` header.
+    ///
+    /// This is determined in the same priority as [`remote`](#method.remote).
+    pub fn remote_unescaped(&self) -> Option<Cow<'_, str>> {
+        self.forwarded_part(|element| element.forwarded_for)
+            .or_else(|| self.x_forwarded_part("X-Forwarded-For").map(Cow::Borrowed))
+            .or_else(|| self.peer_addr().map(Cow::Borrowed))
+    }
+
     /// Get the destination host for this request.
     ///
     /// This is determined in the following priority:
-    /// 1. `Forwarded` header `host` key
+    /// 1. `Forwarded` header `host` key of the first element
     /// 2. The first `X-Forwarded-Host` header
     /// 3. `Host` header
     /// 4. URL domain, if any
+    ///
+    /// A malformed `Forwarded` header is ignored. Quoted values containing
+    /// escapes can't be borrowed from the header, and are skipped; use
+    /// [`host_unescaped`](#method.host_unescaped) to get them.
     pub fn host(&self) -> Option<&str> {
-        self.forwarded_header_part("host")
-            .or_else(|| {
-                self.header("X-Forwarded-Host")
-                    .and_then(|h| h.as_str().split(',').next())
-            })
+        self.forwarded_part(|element| element.host)
+            .and_then(borrowed)
+            .or_else(|| self.x_forwarded_part("X-Forwarded-Host"))
             .or_else(|| self.header(&headers::HOST).map(|h| h.as_str()))
             .or_else(|| self.url().host_str())
     }
 
-    fn forwarded_header_part(&self, part: &str) -> Option<&str> {
-        self.header("Forwarded").and_then(|header| {
-            header.as_str().split(';').find_map(|key_equals_value| {
-                let parts = key_equals_value.split('=').collect::<Vec<_>>();
-                if parts.len() == 2 && parts[0].eq_ignore_ascii_case(part) {
-                    Some(parts[1])
-                } else {
-                    None
-                }
-            })
-        })
+    /// Get the destination host for this request, unescaping quoted values
+    /// of the `Forwarded` header.
+    ///
+    /// This is determined in the same priority as [`host`](#method.host).
+    pub fn host_unescaped(&self) -> Option<Cow<'_, str>> {
+        self.forwarded_part(|element| element.host)
+            .or_else(|| self.x_forwarded_part("X-Forwarded-Host").map(Cow::Borrowed))
+            .or_else(|| self.header(&headers::HOST).map(|h| h.as_str().into()))
+            .or_else(|| self.url().host_str().map(Cow::Borrowed))
+    }
+
+    /// Get the remote address for this request, only trusting forwarding
+    /// headers set by trusted proxies.
+    ///
+    /// The forwarding chain is walked from the peer address of the transport
+    /// inward, and the first address which isn't trusted by the policy is
+    /// returned. `None` is returned if the peer address is unknown, or if a
+    /// trusted proxy didn't disclose the address it received the request from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> http_types::Result<()> {
+    /// #
+    /// use http_types::proxies::TrustedProxies;
+    /// use http_types::{Method, Request, Url};
+    ///
+    /// let mut policy = TrustedProxies::new();
+    /// policy.set_hops(1);
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.set_peer_addr(Some("192.0.2.43:4711"));
+    /// req.insert_header("Forwarded", "for=spoofed, for=198.51.100.17");
+    /// assert_eq!(req.remote_with(&policy).as_deref(), Some("198.51.100.17"));
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn remote_with(&self, policy: &TrustedProxies) -> Option<Cow<'_, str>> {
+        match self.trusted_forwarded_element(policy)? {
+            Some(element) => element.forwarded_for,
+            None => self.peer_addr().map(Cow::Borrowed),
+        }
+    }
+
+    /// Get the destination host for this request, only trusting forwarding
+    /// headers set by trusted proxies.
+    ///
+    /// This is determined in the following priority:
+    /// 1. The `host` the outermost trusted proxy received the request for
+    /// 2. `Host` header
+    /// 3. URL domain, if any
+    ///
+    /// `X-Forwarded-Host` is only used if it has an entry for every address
+    /// in `X-Forwarded-For`.
+    pub fn host_with(&self, policy: &TrustedProxies) -> Option<Cow<'_, str>> {
+        self.trusted_forwarded_element(policy)
+            .flatten()
+            .and_then(|element| element.host)
+            .or_else(|| self.header(&headers::HOST).map(|h| h.as_str().into()))
+            .or_else(|| self.url().host_str().map(Cow::Borrowed))
+    }
+
+    /// Get the scheme of this request, only trusting forwarding headers set
+    /// by trusted proxies.
+    ///
+    /// This is the `proto` the outermost trusted proxy received the request
+    /// with, or the scheme of the URL. Like `host_with`, `X-Forwarded-Proto`
+    /// is only used if it has an entry for every address in `X-Forwarded-For`.
+    pub fn scheme_with(&self, policy: &TrustedProxies) -> Cow<'_, str> {
+        self.trusted_forwarded_element(policy)
+            .flatten()
+            .and_then(|element| element.proto)
+            .unwrap_or_else(|| self.url().scheme().into())
+    }
+
+    /// Find the forwarding element added by the outermost trusted proxy.
+    ///
+    /// Returns `None` if the peer address is unknown, and `Some(None)` if the
+    /// peer isn't a trusted proxy.
+    fn trusted_forwarded_element(
+        &self,
+        policy: &TrustedProxies,
+    ) -> Option<Option<ForwardedElement<'_>>> {
+        let peer = self.peer_addr()?;
+        if !policy.is_trusted_hop(peer, 0) {
+            return Some(None);
+        }
+
+        let mut trusted = None;
+        let elements = self.forwarded().map(Forwarded::into_iter);
+        for (hop, element) in elements.into_iter().flatten().rev().enumerate() {
+            let is_trusted = match element.forwarded_for() {
+                Some(node) => policy.is_trusted_hop(node, hop + 1),
+                None => false,
+            };
+            trusted = Some(element);
+            if !is_trusted {
+                break;
+            }
+        }
+        Some(trusted)
+    }
+
+    /// Parse the forwarding headers, ignoring them if they're malformed.
+    fn forwarded(&self) -> Option<Forwarded<'_>> {
+        Forwarded::from_headers(self).ok().flatten()
     }
 
     fn forwarded_for(&self) -> Option<&str> {
-        self.forwarded_header_part("for").or_else(|| {
-            self.header("X-Forwarded-For")
-                .and_then(|header| header.as_str().split(',').next())
-        })
+        self.forwarded_part(|element| element.forwarded_for)
+            .and_then(borrowed)
+            .or_else(|| self.x_forwarded_part("X-Forwarded-For"))
+    }
+
+    /// Get a key of the first element of the `Forwarded` header, ignoring the
+    /// header if it's malformed.
+    fn forwarded_part<'a>(
+        &'a self,
+        part: fn(ForwardedElement<'a>) -> Option<Cow<'a, str>>,
+    ) -> Option<Cow<'a, str>> {
+        let forwarded = Forwarded::from_forwarded_header(self).ok()??;
+        part(forwarded.into_iter().next()?)
+    }
+
+    /// Get the first entry of an `X-Forwarded-*` header.
+    fn x_forwarded_part(&self, name: &str) -> Option<&str> {
+        let value = self.header(name)?.iter().next()?;
+        split_list(value.as_str()).first().copied()
     }
 
     /// Get the HTTP method
@@ -407,6 +545,64 @@ impl Request {
         body.into_form().await
     }
 
+    /// Read the body as Content-Security-Policy violation reports.
+    ///
+    /// Both legacy `application/csp-report` bodies, sent to `report-uri`, and
+    /// `application/reports+json` batches, sent to `report-to` endpoints, are
+    /// read. Reports of other types in a batch are skipped.
+    ///
+    /// This consumes the request body.
+    ///
+    /// # Errors
+    ///
+    /// A `415 Unsupported Media Type` error is returned for other content
+    /// types, and a `422 Unprocessable Entity` error if the reports can't be
+    /// read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use http_types::{mime, Method, Request, Url};
+    ///
+    /// let mut req = Request::new(Method::Post, Url::parse("https://example.com/csp").unwrap());
+    /// req.set_body(r#"{"csp-report": {
+    ///     "document-uri": "https://example.com/",
+    ///     "violated-directive": "script-src-elem",
+    ///     "original-policy": "script-src 'self'; report-uri /csp",
+    ///     "blocked-uri": "https://evil.example/x.js"
+    /// }}"#);
+    /// req.set_content_type(mime::CSP_REPORT);
+    ///
+    /// let reports = req.body_csp_reports().await?;
+    /// assert_eq!(reports[0].effective_directive, "script-src-elem");
+    /// assert_eq!(reports[0].blocked_url.as_deref(), Some("https://evil.example/x.js"));
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn body_csp_reports(&mut self) -> crate::Result<Vec<CspViolation>> {
+        let content_type = self.content_type();
+        match content_type.as_ref().map(|mime| mime.essence()) {
+            Some(essence) if essence == mime::CSP_REPORT.essence() => {
+                let report: CspReport = self.body_json().await?;
+                Ok(vec![report.csp_report.into()])
+            }
+            Some(essence) if essence == mime::REPORTS_JSON.essence() => {
+                let reports: Vec<Report> = self.body_json().await?;
+                reports
+                    .into_iter()
+                    .filter(|report| report.report_type == "csp-violation")
+                    .map(|report| {
+                        serde_json::from_value(report.body).status(StatusCode::UnprocessableEntity)
+                    })
+                    .collect()
+            }
+            _ => Err(crate::Error::from_str(
+                StatusCode::UnsupportedMediaType,
+                "Expected a CSP report content type",
+            )),
+        }
+    }
+
     /// Get an HTTP header.
     pub fn header(&self, name: impl Into<HeaderName>) -> Option<&HeaderValues> {
         self.headers.get(name)
@@ -487,6 +683,40 @@ impl Request {
         self.header(CONTENT_TYPE)?.last().as_str().parse().ok()
     }
 
+    /// Get the `Date` header, if it's set and valid.
+    pub fn date(&self) -> Option<HttpDate> {
+        self.header(DATE)?.last().as_str().parse().ok()
+    }
+
+    /// Set the `Date` header.
+    pub fn set_date(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(DATE, HeaderValue::from(date))
+    }
+
+    /// Get the `If-Modified-Since` header, if it's set and valid.
+    pub fn if_modified_since(&self) -> Option<HttpDate> {
+        self.header(IF_MODIFIED_SINCE)?.last().as_str().parse().ok()
+    }
+
+    /// Set the `If-Modified-Since` header.
+    pub fn set_if_modified_since(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(IF_MODIFIED_SINCE, HeaderValue::from(date))
+    }
+
+    /// Get the `If-Unmodified-Since` header, if it's set and valid.
+    pub fn if_unmodified_since(&self) -> Option<HttpDate> {
+        self.header(IF_UNMODIFIED_SINCE)?
+            .last()
+            .as_str()
+            .parse()
+            .ok()
+    }
+
+    /// Set the `If-Unmodified-Since` header.
+    pub fn set_if_unmodified_since(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(IF_UNMODIFIED_SINCE, HeaderValue::from(date))
+    }
+
     /// Get the length of the body stream, if it has been set.
     ///
     /// This value is set when passing a fixed-size object into as the body.
@@ -566,6 +796,60 @@ impl Request {
         self.has_trailers
     }
 
+    /// Sends interim `1xx` responses to a receiver, such as `103 Early
+    /// Hints`.
+    ///
+    /// The responses are dropped if the transport doesn't support them. See
+    /// the [`informational`](informational/index.html) module for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a cloned request.
+    pub fn send_informational(&self) -> informational::Sender {
+        let sender = self
+            .informational_sender
+            .clone()
+            .expect("Informational sender is not available on cloned requests");
+        informational::Sender::new(sender)
+    }
+
+    /// Receive interim `1xx` responses from a sender.
+    ///
+    /// This is meant to be called by server transports before handing the
+    /// request to the application, so the responses can be sent while the
+    /// final response is being prepared.
+    pub fn recv_informational(&mut self) -> informational::Receiver {
+        let receiver = self
+            .informational_receiver
+            .take()
+            .expect("Informational receiver can only be constructed once");
+        informational::Receiver::new(receiver)
+    }
+
+    /// Returns `true` if the client waits for `100 Continue` before sending
+    /// the body, as asked for with the `Expect: 100-continue` header.
+    pub fn expects_continue(&self) -> bool {
+        match self.header(EXPECT) {
+            Some(values) => values
+                .iter()
+                .any(|value| value.as_str().trim().eq_ignore_ascii_case("100-continue")),
+            None => false,
+        }
+    }
+
+    /// Receive a signal when the body starts being read, meaning `100
+    /// Continue` should be sent to the client.
+    ///
+    /// This is meant to be called by server transports after setting the
+    /// body, when [`expects_continue`](#method.expects_continue) is `true`.
+    /// See the [`expect`](expect/index.html) module for more.
+    pub fn recv_continue(&mut self) -> expect::Receiver {
+        let (sender, receiver) = expect::channel();
+        let body = mem::replace(&mut self.body, Body::empty());
+        self.body = expect::signal_on_read(body, sender);
+        receiver
+    }
+
     /// An iterator visiting all header pairs in arbitrary order.
     pub fn iter(&self) -> headers::Iter<'_> {
         self.headers.iter()
@@ -882,6 +1166,8 @@ impl Clone for Request {
             peer_addr: self.peer_addr.clone(),
             local_addr: self.local_addr.clone(),
             has_trailers: false,
+            informational_sender: None,
+            informational_receiver: None,
         }
     }
 }
@@ -986,6 +1272,14 @@ impl<'a> IntoIterator for &'a mut Request {
     }
 }
 
+/// Borrow a value parsed by `Forwarded`, if it doesn't contain escapes.
+fn borrowed(value: Cow<'_, str>) -> Option<&str> {
+    match value {
+        Cow::Borrowed(value) => Some(value),
+        Cow::Owned(_) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -997,16 +1291,30 @@ mod tests {
             let mut request = build_test_request();
             set_forwarded(&mut request, "-");
             set_x_forwarded_host(&mut request, "this will not be used");
-            assert_eq!(request.forwarded_header_part("host"), Some("host.com"));
             assert_eq!(request.host(), Some("host.com"));
         }
 
+        #[test]
+        fn when_forwarded_header_has_no_host() {
+            let mut request = build_test_request();
+            request.insert_header("Forwarded", "for=192.0.2.43");
+            set_x_forwarded_host(&mut request, "expected.host");
+            assert_eq!(request.host(), Some("expected.host"));
+        }
+
+        #[test]
+        fn when_forwarded_host_is_escaped() {
+            let mut request = build_test_request();
+            request.insert_header("Forwarded", r#"host="example\.com""#);
+            assert_eq!(request.host(), Some("async.rs"));
+            assert_eq!(request.host_unescaped().as_deref(), Some("example.com"));
+        }
+
         #[test]
         fn when_several_x_forwarded_hosts_exist() {
             let mut request = build_test_request();
             set_x_forwarded_host(&mut request, "expected.host");
 
-            assert_eq!(request.forwarded_header_part("host"), None);
             assert_eq!(request.host(), Some("expected.host"));
         }
 
@@ -1113,6 +1421,18 @@ mod tests {
             assert_eq!(request.remote(), Some("127.0.0.1:8001"));
         }
 
+        #[test]
+        fn when_forwarded_has_several_elements() {
+            let mut request = build_test_request();
+            request.insert_header(
+                "Forwarded",
+                r#"for="[2001:db8:cafe::17]:4711";host="example.com:8080", for=proxy.com"#,
+            );
+
+            assert_eq!(request.remote(), Some("[2001:db8:cafe::17]:4711"));
+            assert_eq!(request.host(), Some("example.com:8080"));
+        }
+
         #[test]
         fn when_forwarded_is_improperly_formatted() {
             let mut request = build_test_request();
@@ -1126,6 +1446,26 @@ mod tests {
             assert_eq!(request.remote(), Some("127.0.0.1:8000"));
         }
 
+        #[test]
+        fn when_forwarded_is_improperly_formatted_and_x_forwarded_for_is_set() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("127.0.0.1:8000"));
+            request.insert_header("Forwarded", "this is an improperly ;;; formatted header");
+            set_x_forwarded_for(&mut request, "forwarded-host.com");
+
+            assert_eq!(request.remote(), Some("forwarded-host.com"));
+        }
+
+        #[test]
+        fn when_forwarded_for_is_escaped() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("127.0.0.1:8000"));
+            request.insert_header("Forwarded", r#"for="_hidden\-node""#);
+
+            assert_eq!(request.remote(), Some("127.0.0.1:8000"));
+            assert_eq!(request.remote_unescaped().as_deref(), Some("_hidden-node"));
+        }
+
         #[test]
         fn when_x_forwarded_for_is_set() {
             let mut request = build_test_request();
@@ -1166,6 +1506,297 @@ mod tests {
         }
     }
 
+    mod trusted_proxies {
+        use super::*;
+
+        fn policy() -> TrustedProxies {
+            let mut policy = TrustedProxies::new();
+            policy.push("10.0.0.0/8".parse().unwrap());
+            policy
+        }
+
+        #[test]
+        fn when_peer_is_untrusted() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("192.0.2.1:8000"));
+            request.insert_header("Forwarded", "for=spoofed;host=spoofed.com;proto=https");
+
+            assert_eq!(
+                request.remote_with(&policy()).as_deref(),
+                Some("192.0.2.1:8000")
+            );
+            assert_eq!(request.host_with(&policy()).as_deref(), Some("async.rs"));
+            assert_eq!(request.scheme_with(&policy()), "http");
+        }
+
+        #[test]
+        fn when_chain_is_partially_trusted() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.1:8000"));
+            request.insert_header(
+                "Forwarded",
+                "for=spoofed;host=spoofed.com, for=192.0.2.1;host=example.com;proto=https, for=10.0.0.2",
+            );
+
+            assert_eq!(request.remote_with(&policy()).as_deref(), Some("192.0.2.1"));
+            assert_eq!(request.host_with(&policy()).as_deref(), Some("example.com"));
+            assert_eq!(request.scheme_with(&policy()), "https");
+        }
+
+        #[test]
+        fn when_chain_is_fully_trusted() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.1:8000"));
+            request.insert_header("X-Forwarded-For", "10.0.0.3, 10.0.0.2");
+
+            assert_eq!(request.remote_with(&policy()).as_deref(), Some("10.0.0.3"));
+        }
+
+        #[test]
+        fn with_hop_count() {
+            let mut policy = TrustedProxies::new();
+            policy.set_hops(2);
+
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("192.0.2.1:8000"));
+            request.insert_header("X-Forwarded-For", "spoofed, 198.51.100.17, 192.0.2.2");
+
+            assert_eq!(
+                request.remote_with(&policy).as_deref(),
+                Some("198.51.100.17")
+            );
+        }
+
+        #[test]
+        fn when_x_forwarded_host_is_spoofed() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.1:8000"));
+            // The client sent `X-Forwarded-Host: spoofed.com`, which the
+            // proxy appended to instead of replacing.
+            request.insert_header("X-Forwarded-For", "192.0.2.1");
+            request.insert_header("X-Forwarded-Host", "spoofed.com, example.com");
+            request.insert_header("X-Forwarded-Proto", "https");
+
+            assert_eq!(request.remote_with(&policy()).as_deref(), Some("192.0.2.1"));
+            assert_eq!(request.host_with(&policy()).as_deref(), Some("async.rs"));
+            assert_eq!(request.scheme_with(&policy()), "https");
+
+            request.insert_header("X-Forwarded-For", "spoofed, 192.0.2.1");
+            assert_eq!(request.remote_with(&policy()).as_deref(), Some("192.0.2.1"));
+            assert_eq!(request.host_with(&policy()).as_deref(), Some("example.com"));
+            assert_eq!(request.scheme_with(&policy()), "http");
+        }
+
+        #[test]
+        fn when_peer_is_unknown() {
+            let mut request = build_test_request();
+            request.insert_header("X-Forwarded-For", "198.51.100.17");
+            assert_eq!(request.remote_with(&policy()).as_deref(), None);
+        }
+    }
+
+    mod dates {
+        use super::*;
+
+        #[test]
+        fn date() -> crate::Result<()> {
+            let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse()?;
+            let mut request = build_test_request();
+            assert_eq!(request.date(), None);
+
+            request.set_date(date);
+            assert_eq!(request["Date"], "Sun, 06 Nov 1994 08:49:37 GMT");
+            assert_eq!(request.date(), Some(date));
+            Ok(())
+        }
+
+        #[test]
+        fn preconditions() -> crate::Result<()> {
+            let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse()?;
+            let mut request = build_test_request();
+            assert_eq!(request.if_modified_since(), None);
+            assert_eq!(request.if_unmodified_since(), None);
+
+            request.set_if_modified_since(date);
+            assert_eq!(
+                request["If-Modified-Since"],
+                "Sun, 06 Nov 1994 08:49:37 GMT"
+            );
+            assert_eq!(request.if_modified_since(), Some(date));
+
+            request.set_if_unmodified_since(date);
+            assert_eq!(
+                request["If-Unmodified-Since"],
+                "Sun, 06 Nov 1994 08:49:37 GMT"
+            );
+            assert_eq!(request.if_unmodified_since(), Some(date));
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_dates() {
+            let mut request = build_test_request();
+            request.insert_header("Date", "yesterday");
+            request.insert_header("If-Modified-Since", "0");
+            request.insert_header("If-Unmodified-Since", "Sun, 31 Nov 1994 08:49:37 GMT");
+            assert_eq!(request.date(), None);
+            assert_eq!(request.if_modified_since(), None);
+            assert_eq!(request.if_unmodified_since(), None);
+        }
+    }
+
+    mod expect_continue {
+        use super::*;
+        use async_std::future::timeout;
+        use std::time::Duration;
+
+        #[test]
+        fn expects_continue() {
+            let mut request = build_test_request();
+            assert!(!request.expects_continue());
+            request.insert_header("Expect", "100-Continue");
+            assert!(request.expects_continue());
+            request.insert_header("Expect", "something-else");
+            assert!(!request.expects_continue());
+        }
+
+        #[async_std::test]
+        async fn signals_when_body_is_read() -> crate::Result<()> {
+            let mut request = build_test_request();
+            request.set_body("Hello Nori");
+            let mut receiver = request.recv_continue();
+            assert_eq!(request.len(), Some(10));
+            assert_eq!(request["Content-Type"], "text/plain;charset=utf-8");
+
+            let pending = timeout(Duration::from_millis(10), &mut receiver).await;
+            assert!(pending.is_err());
+
+            assert_eq!(request.body_string().await?, "Hello Nori");
+            assert!(receiver.await);
+            Ok(())
+        }
+
+        #[async_std::test]
+        async fn no_signal_when_request_is_dropped() {
+            let mut request = build_test_request();
+            request.set_body("Hello Nori");
+            let receiver = request.recv_continue();
+            drop(request);
+            assert!(!receiver.await);
+        }
+    }
+
+    mod informational_responses {
+        use super::*;
+        use crate::informational::Informational;
+        use async_std::prelude::*;
+
+        #[async_std::test]
+        async fn drained_in_order() -> crate::Result<()> {
+            let mut request = build_test_request();
+            let mut receiver = request.recv_informational();
+
+            let sender = request.send_informational();
+            sender.send(Informational::new(StatusCode::Processing)?);
+            let mut hints = Informational::new(StatusCode::EarlyHints)?;
+            hints.insert("Link", "</style.css>; rel=preload");
+            request.send_informational().send(hints);
+            drop(sender);
+            drop(request);
+
+            let processing = receiver.next().await.unwrap();
+            assert_eq!(processing.status(), StatusCode::Processing);
+            let hints = receiver.next().await.unwrap();
+            assert_eq!(hints.status(), StatusCode::EarlyHints);
+            assert_eq!(hints["Link"], "</style.css>; rel=preload");
+            assert!(receiver.next().await.is_none());
+            Ok(())
+        }
+
+        #[test]
+        fn dropped_without_receiver() -> crate::Result<()> {
+            let mut request = build_test_request();
+            drop(request.recv_informational());
+            let sender = request.send_informational();
+            sender.send(Informational::new(StatusCode::Continue)?);
+            Ok(())
+        }
+    }
+
+    mod csp_reports {
+        use super::*;
+
+        #[async_std::test]
+        async fn reports_json_batch() -> crate::Result<()> {
+            let mut request = build_test_request();
+            request.set_body(
+                r#"[{
+                    "type": "deprecation",
+                    "age": 10,
+                    "url": "https://example.com/",
+                    "user_agent": "Mozilla/5.0",
+                    "body": {"id": "x"}
+                }, {
+                    "type": "csp-violation",
+                    "age": 53531,
+                    "url": "https://example.com/",
+                    "user_agent": "Mozilla/5.0",
+                    "body": {
+                        "documentURL": "https://example.com/",
+                        "blockedURL": "inline",
+                        "effectiveDirective": "script-src-elem",
+                        "originalPolicy": "script-src 'self'; report-to csp",
+                        "disposition": "report",
+                        "statusCode": 200,
+                        "lineNumber": 4
+                    }
+                }]"#,
+            );
+            request.insert_header("Content-Type", "application/reports+json");
+
+            let reports = request.body_csp_reports().await?;
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].blocked_url.as_deref(), Some("inline"));
+            assert_eq!(reports[0].disposition, "report");
+            assert_eq!(reports[0].line_number, Some(4));
+            Ok(())
+        }
+
+        #[async_std::test]
+        async fn legacy_report() -> crate::Result<()> {
+            let mut request = build_test_request();
+            request.set_body(
+                r#"{"csp-report": {
+                    "document-uri": "https://example.com/",
+                    "violated-directive": "img-src 'self'",
+                    "original-policy": "img-src 'self'",
+                    "status-code": 200
+                }}"#,
+            );
+            request.insert_header("Content-Type", "application/csp-report; charset=utf-8");
+
+            let reports = request.body_csp_reports().await?;
+            assert_eq!(reports[0].effective_directive, "img-src");
+            assert_eq!(reports[0].disposition, "enforce");
+            assert_eq!(reports[0].status_code, 200);
+            Ok(())
+        }
+
+        #[async_std::test]
+        async fn wrong_content_type() {
+            let mut request = build_test_request();
+            request.set_body(Body::from_json(&vec![0]).unwrap());
+            let err = request.body_csp_reports().await.unwrap_err();
+            assert_eq!(err.status(), StatusCode::UnsupportedMediaType);
+
+            let mut request = build_test_request();
+            request.set_body("[{}]");
+            request.insert_header("Content-Type", "application/reports+json");
+            let err = request.body_csp_reports().await.unwrap_err();
+            assert_eq!(err.status(), StatusCode::UnprocessableEntity);
+        }
+    }
+
     fn build_test_request() -> Request {
         let url = Url::parse("http://async.rs/").unwrap();
         Request::new(Method::Get, url)
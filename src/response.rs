@@ -6,16 +6,21 @@ use std::fmt::Debug;
 use std::mem;
 use std::ops::Index;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::content::{ContentDisposition, ContentEncoding, DispositionType};
 use crate::convert::DeserializeOwned;
 use crate::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, DATE, EXPIRES, LAST_MODIFIED, LINK,
+    RETRY_AFTER,
 };
+use crate::links::{Link, Links};
 use crate::mime::Mime;
 use crate::trailers::{self, Trailers};
-use crate::{Body, Extensions, StatusCode, Version};
+use crate::{Body, Extensions, HttpDate, StatusCode, Url, Version};
 
 cfg_unstable! {
     use crate::upgrade;
@@ -311,6 +316,59 @@ impl Response {
         self.replace_body(Body::empty())
     }
 
+    /// Decode the body according to the `Content-Encoding` header.
+    ///
+    /// The body is wrapped in a streaming decoder for every content coding
+    /// that was applied, and the `Content-Encoding` and `Content-Length`
+    /// headers are removed. The response is left untouched if there is no
+    /// `Content-Encoding` header.
+    ///
+    /// # Errors
+    ///
+    /// An error with the status `415` is returned if one of the content
+    /// codings is not supported, in which case the body is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// #
+    /// use http_types::content::ContentEncoding;
+    /// use http_types::{Body, Response, StatusCode};
+    ///
+    /// let body = Body::from_string("Hello, Nori!".to_string());
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.set_body(body.compress(ContentEncoding::Gzip)?);
+    /// ContentEncoding::Gzip.apply(&mut res);
+    ///
+    /// res.decode_body()?;
+    /// assert!(res.header("Content-Encoding").is_none());
+    /// assert_eq!(&res.body_string().await?, "Hello, Nori!");
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn decode_body(&mut self) -> crate::Result<()> {
+        let encodings = ContentEncoding::all_from_headers(&self.headers)?;
+        if encodings.is_empty() {
+            return Ok(());
+        }
+        if let Some(encoding) = encodings.iter().find(|e| !Body::supports_encoding(e)) {
+            return Err(crate::Error::from_str(
+                StatusCode::UnsupportedMediaType,
+                format!("Content coding `{}` is not supported", encoding),
+            ));
+        }
+
+        let mut body = self.take_body();
+        for encoding in encodings.into_iter().rev() {
+            body = body.decompress(encoding)?;
+        }
+        self.set_body(body);
+        self.remove_header(CONTENT_ENCODING);
+        self.remove_header(CONTENT_LENGTH);
+        Ok(())
+    }
+
     /// Read the body as a string.
     ///
     /// This consumes the response. If you want to read the body without
@@ -441,6 +499,26 @@ impl Response {
         self.insert_header(CONTENT_TYPE, value)
     }
 
+    /// Set the `Content-Disposition` header, asking for the body to be
+    /// downloaded and saved under a filename.
+    ///
+    /// Filenames which aren't ASCII are encoded as described in RFC 6266.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::Response;
+    ///
+    /// let mut res = Response::new(200);
+    /// res.set_attachment("rapport-2020.pdf");
+    /// assert_eq!(res["Content-Disposition"], r#"attachment; filename="rapport-2020.pdf""#);
+    /// ```
+    pub fn set_attachment(&mut self, filename: impl Into<String>) -> Option<HeaderValues> {
+        let mut disposition = ContentDisposition::new(DispositionType::Attachment);
+        disposition.set_filename(filename);
+        self.insert_header(disposition.name(), disposition.value())
+    }
+
     /// Copy MIME data from the body.
     fn copy_content_type_from_body(&mut self) {
         if self.header(CONTENT_TYPE).is_none() {
@@ -453,6 +531,113 @@ impl Response {
         self.header(CONTENT_TYPE)?.last().as_str().parse().ok()
     }
 
+    /// Get the `Date` header, if it's set and valid.
+    pub fn date(&self) -> Option<HttpDate> {
+        self.header(DATE)?.last().as_str().parse().ok()
+    }
+
+    /// Set the `Date` header.
+    pub fn set_date(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(DATE, HeaderValue::from(date))
+    }
+
+    /// Get the `Last-Modified` header, if it's set and valid.
+    pub fn last_modified(&self) -> Option<HttpDate> {
+        self.header(LAST_MODIFIED)?.last().as_str().parse().ok()
+    }
+
+    /// Set the `Last-Modified` header.
+    pub fn set_last_modified(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(LAST_MODIFIED, HeaderValue::from(date))
+    }
+
+    /// Get the `Expires` header, if it's set and valid.
+    ///
+    /// Invalid dates, like `0`, mean the response has already expired and
+    /// are returned as the unix epoch.
+    pub fn expires(&self) -> Option<HttpDate> {
+        let value = self.header(EXPIRES)?.last().as_str();
+        Some(value.parse().unwrap_or_else(|_| UNIX_EPOCH.into()))
+    }
+
+    /// Set the `Expires` header.
+    pub fn set_expires(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(EXPIRES, HeaderValue::from(date))
+    }
+
+    /// Get the `Retry-After` header, if it's set and valid.
+    ///
+    /// A delay in seconds is resolved against the `Date` header of the
+    /// response, or the current time if there is no `Date` header. Delays
+    /// too large to be represented are treated as invalid.
+    pub fn retry_after(&self) -> Option<HttpDate> {
+        let value = self.header(RETRY_AFTER)?.last().as_str();
+        match value.parse::<u64>() {
+            Ok(secs) => {
+                let date: SystemTime = self.date().unwrap_or_else(HttpDate::now).into();
+                Some(date.checked_add(Duration::from_secs(secs))?.into())
+            }
+            Err(_) => value.parse().ok(),
+        }
+    }
+
+    /// Set the `Retry-After` header to a date.
+    pub fn set_retry_after(&mut self, date: HttpDate) -> Option<HeaderValues> {
+        self.insert_header(RETRY_AFTER, HeaderValue::from(date))
+    }
+
+    /// Get the links of the `Link` header, resolving relative URLs against a
+    /// base URL such as `Request::url()`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the `Link` header is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> http_types::Result<()> {
+    /// #
+    /// use http_types::{Response, Url};
+    ///
+    /// let base = Url::parse("https://example.com/items")?;
+    ///
+    /// let mut res = Response::new(200);
+    /// res.insert_header("Link", r#"<?page=2>; rel="next""#);
+    ///
+    /// let links = res.links(&base)?;
+    /// assert!(links[0].has_rel("next"));
+    /// assert_eq!(links[0].target().as_str(), "https://example.com/items?page=2");
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn links(&self, base: &Url) -> crate::Result<Vec<Link>> {
+        match Links::from_headers(self, base)? {
+            Some(links) => Ok(links.into_iter().collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Set the `Link` header to a single link, replacing any existing links.
+    ///
+    /// Parameters which aren't printable ASCII are sent in their RFC 8187 `*`
+    /// form.
+    pub fn insert_link(&mut self, link: Link) {
+        let value = link.to_string();
+        let value = HeaderValue::from_str(&value).expect("Links should be valid ASCII");
+        self.insert_header(LINK, value);
+    }
+
+    /// Add a link to the `Link` header, keeping any existing links.
+    ///
+    /// Parameters which aren't printable ASCII are sent in their RFC 8187 `*`
+    /// form.
+    pub fn append_link(&mut self, link: Link) {
+        let value = link.to_string();
+        let value = HeaderValue::from_str(&value).expect("Links should be valid ASCII");
+        self.append_header(LINK, value);
+    }
+
     /// Get the length of the body stream, if it has been set.
     ///
     /// This value is set when passing a fixed-size object into as the body.
@@ -785,6 +970,9 @@ impl<'a> IntoIterator for &'a mut Response {
 #[cfg(test)]
 mod test {
     use super::Response;
+    use crate::content::ContentEncoding;
+    use crate::links::Link;
+    use crate::{Body, HttpDate, Url};
 
     #[test]
     fn construct_shorthand_with_valid_status_code() {
@@ -796,4 +984,75 @@ mod test {
     fn construct_shorthand_with_invalid_status_code() {
         let _res = Response::new(600);
     }
+
+    #[test]
+    fn links() -> crate::Result<()> {
+        let base = Url::parse("https://example.com/items")?;
+        let mut res = Response::new(200);
+        res.insert_header("Link", "</old>; rel=prev");
+
+        let mut next = Link::new(base.join("?page=2")?);
+        next.push_rel("next");
+        res.append_link(next.clone());
+        assert_eq!(res.links(&base)?.len(), 2);
+
+        res.insert_link(next.clone());
+        assert_eq!(res.links(&base)?, vec![next]);
+        Ok(())
+    }
+
+    #[test]
+    fn dates() -> crate::Result<()> {
+        let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse()?;
+        let mut res = Response::new(200);
+        assert_eq!(res.date(), None);
+
+        res.set_date(date);
+        assert_eq!(res["Date"], "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(res.date(), Some(date));
+
+        res.insert_header("Expires", "0");
+        assert_eq!(res.expires(), Some(HttpDate::from(std::time::UNIX_EPOCH)));
+
+        res.insert_header("Retry-After", "120");
+        let expected: HttpDate = "Sun, 06 Nov 1994 08:51:37 GMT".parse()?;
+        assert_eq!(res.retry_after(), Some(expected));
+
+        res.set_retry_after(date);
+        assert_eq!(res.retry_after(), Some(date));
+
+        res.insert_header("Retry-After", u64::MAX.to_string());
+        assert_eq!(res.retry_after(), None);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn decode_body() -> crate::Result<()> {
+        let body = Body::from_string("Hello, Chashu!".to_string())
+            .compress(ContentEncoding::Deflate)?
+            .compress(ContentEncoding::Gzip)?;
+        let mut res = Response::new(200);
+        res.set_body(body);
+        res.insert_header("Content-Encoding", "deflate, gzip");
+        res.insert_header("Content-Length", "42");
+
+        res.decode_body()?;
+        assert!(res.header("Content-Encoding").is_none());
+        assert!(res.header("Content-Length").is_none());
+        assert_eq!(&res.body_string().await?, "Hello, Chashu!");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn decode_body_unsupported() -> crate::Result<()> {
+        let mut res = Response::new(200);
+        res.set_body("Hello, Chashu!");
+        res.insert_header("Content-Encoding", "gzip, br");
+
+        let err = res.decode_body().unwrap_err();
+        assert_eq!(err.status(), 415);
+        assert_eq!(res["Content-Encoding"], "gzip, br");
+        assert_eq!(&res.body_string().await?, "Hello, Chashu!");
+        Ok(())
+    }
 }
@@ -0,0 +1,557 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, CROSS_ORIGIN_EMBEDDER_POLICY,
+    CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY, CROSS_ORIGIN_OPENER_POLICY,
+    CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY, CROSS_ORIGIN_RESOURCE_POLICY,
+};
+use crate::parse_utils::{fmt_quoted_string, parse_param, split_outside_quotes};
+use crate::{Error, StatusCode};
+
+/// Define `Cross-Origin-Opener-Policy` value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpenerPolicy {
+    /// Set to `unsafe-none`, the default
+    UnsafeNone,
+    /// Set to `same-origin-allow-popups`
+    SameOriginAllowPopups,
+    /// Set to `same-origin`
+    SameOrigin,
+}
+
+impl AsRef<str> for OpenerPolicy {
+    fn as_ref(&self) -> &str {
+        match *self {
+            OpenerPolicy::UnsafeNone => "unsafe-none",
+            OpenerPolicy::SameOriginAllowPopups => "same-origin-allow-popups",
+            OpenerPolicy::SameOrigin => "same-origin",
+        }
+    }
+}
+
+impl FromStr for OpenerPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unsafe-none" => Ok(OpenerPolicy::UnsafeNone),
+            "same-origin-allow-popups" => Ok(OpenerPolicy::SameOriginAllowPopups),
+            "same-origin" => Ok(OpenerPolicy::SameOrigin),
+            s => Err(invalid("Cross-Origin-Opener-Policy", s)),
+        }
+    }
+}
+
+/// Define `Cross-Origin-Embedder-Policy` value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbedderPolicy {
+    /// Set to `unsafe-none`, the default
+    UnsafeNone,
+    /// Set to `require-corp`
+    RequireCorp,
+    /// Set to `credentialless`
+    Credentialless,
+}
+
+impl AsRef<str> for EmbedderPolicy {
+    fn as_ref(&self) -> &str {
+        match *self {
+            EmbedderPolicy::UnsafeNone => "unsafe-none",
+            EmbedderPolicy::RequireCorp => "require-corp",
+            EmbedderPolicy::Credentialless => "credentialless",
+        }
+    }
+}
+
+impl FromStr for EmbedderPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unsafe-none" => Ok(EmbedderPolicy::UnsafeNone),
+            "require-corp" => Ok(EmbedderPolicy::RequireCorp),
+            "credentialless" => Ok(EmbedderPolicy::Credentialless),
+            s => Err(invalid("Cross-Origin-Embedder-Policy", s)),
+        }
+    }
+}
+
+/// Build a `Cross-Origin-Opener-Policy` header.
+///
+/// `Cross-Origin-Opener-Policy` (COOP) keeps a document out of the browsing
+/// context group of cross-origin documents it opens, or is opened by. Along
+/// with `Cross-Origin-Embedder-Policy` it makes a page cross-origin isolated,
+/// which is needed to use `SharedArrayBuffer`.
+///
+/// [Mozilla Developer Network](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Opener-Policy)
+///
+/// # Specifications
+///
+/// - [HTML Living Standard: Cross-origin opener policies](https://html.spec.whatwg.org/multipage/origin.html#cross-origin-opener-policies)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::{CrossOriginOpenerPolicy, OpenerPolicy};
+/// use http_types::{Response, StatusCode};
+///
+/// let mut policy = CrossOriginOpenerPolicy::new(OpenerPolicy::SameOrigin);
+/// policy.report_to("coop").report_only();
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// policy.apply(&mut res);
+/// assert_eq!(
+///     res["Cross-Origin-Opener-Policy-Report-Only"],
+///     r#"same-origin; report-to="coop""#
+/// );
+///
+/// let policy = CrossOriginOpenerPolicy::from_headers_report_only(res)?.unwrap();
+/// assert_eq!(policy.policy(), OpenerPolicy::SameOrigin);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossOriginOpenerPolicy {
+    policy: OpenerPolicy,
+    report_to: Option<String>,
+    report_only_flag: bool,
+}
+
+impl CrossOriginOpenerPolicy {
+    /// Create a new instance.
+    pub fn new(policy: OpenerPolicy) -> Self {
+        Self {
+            policy,
+            report_to: None,
+            report_only_flag: false,
+        }
+    }
+
+    /// Create a new instance from the `Cross-Origin-Opener-Policy` header.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_header(headers.as_ref(), CROSS_ORIGIN_OPENER_POLICY, false)
+    }
+
+    /// Create a new instance from the `Cross-Origin-Opener-Policy-Report-Only`
+    /// header.
+    pub fn from_headers_report_only(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_header(
+            headers.as_ref(),
+            CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY,
+            true,
+        )
+    }
+
+    fn parse_header(
+        headers: &Headers,
+        name: HeaderName,
+        report_only_flag: bool,
+    ) -> crate::Result<Option<Self>> {
+        let value = match headers.get(name) {
+            Some(values) => values.last(),
+            None => return Ok(None),
+        };
+        let mut policy: Self = value.as_str().parse()?;
+        policy.report_only_flag = report_only_flag;
+        Ok(Some(policy))
+    }
+
+    /// Sets the `Cross-Origin-Opener-Policy` header, or its report-only
+    /// variant.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        if self.report_only_flag {
+            CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY
+        } else {
+            CROSS_ORIGIN_OPENER_POLICY
+        }
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reporting endpoint contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Cross-Origin-Opener-Policy should be valid ASCII")
+    }
+
+    /// Get the policy.
+    pub fn policy(&self) -> OpenerPolicy {
+        self.policy
+    }
+
+    /// Get the name of the endpoint violations are reported to.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.report_to.as_deref()
+    }
+
+    /// Report violations to a named endpoint, as set up with the Reporting
+    /// API.
+    pub fn report_to(&mut self, endpoint: impl Into<String>) -> &mut Self {
+        self.report_to = Some(endpoint.into());
+        self
+    }
+
+    /// Change the header to `Cross-Origin-Opener-Policy-Report-Only`
+    pub fn report_only(&mut self) -> &mut Self {
+        self.report_only_flag = true;
+        self
+    }
+
+    /// Returns `true` if violations are only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only_flag
+    }
+}
+
+impl Display for CrossOriginOpenerPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_policy(f, self.policy.as_ref(), self.report_to.as_deref())
+    }
+}
+
+impl FromStr for CrossOriginOpenerPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (policy, report_to) = parse_policy(s, "Cross-Origin-Opener-Policy")?;
+        let mut output = Self::new(policy.parse()?);
+        output.report_to = report_to;
+        Ok(output)
+    }
+}
+
+/// Build a `Cross-Origin-Embedder-Policy` header.
+///
+/// `Cross-Origin-Embedder-Policy` (COEP) stops a document from loading
+/// cross-origin resources which don't explicitly allow it, using CORS or
+/// `Cross-Origin-Resource-Policy`. With `credentialless`, cross-origin
+/// resources are loaded without credentials instead.
+///
+/// [Mozilla Developer Network](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Embedder-Policy)
+///
+/// # Specifications
+///
+/// - [HTML Living Standard: Cross-origin embedder policies](https://html.spec.whatwg.org/multipage/origin.html#coep)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::{CrossOriginEmbedderPolicy, EmbedderPolicy};
+/// use http_types::{Response, StatusCode};
+///
+/// let policy = CrossOriginEmbedderPolicy::new(EmbedderPolicy::Credentialless);
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// policy.apply(&mut res);
+/// assert_eq!(res["Cross-Origin-Embedder-Policy"], "credentialless");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossOriginEmbedderPolicy {
+    policy: EmbedderPolicy,
+    report_to: Option<String>,
+    report_only_flag: bool,
+}
+
+impl CrossOriginEmbedderPolicy {
+    /// Create a new instance.
+    pub fn new(policy: EmbedderPolicy) -> Self {
+        Self {
+            policy,
+            report_to: None,
+            report_only_flag: false,
+        }
+    }
+
+    /// Create a new instance from the `Cross-Origin-Embedder-Policy` header.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_header(headers.as_ref(), CROSS_ORIGIN_EMBEDDER_POLICY, false)
+    }
+
+    /// Create a new instance from the
+    /// `Cross-Origin-Embedder-Policy-Report-Only` header.
+    pub fn from_headers_report_only(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_header(
+            headers.as_ref(),
+            CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY,
+            true,
+        )
+    }
+
+    fn parse_header(
+        headers: &Headers,
+        name: HeaderName,
+        report_only_flag: bool,
+    ) -> crate::Result<Option<Self>> {
+        let value = match headers.get(name) {
+            Some(values) => values.last(),
+            None => return Ok(None),
+        };
+        let mut policy: Self = value.as_str().parse()?;
+        policy.report_only_flag = report_only_flag;
+        Ok(Some(policy))
+    }
+
+    /// Sets the `Cross-Origin-Embedder-Policy` header, or its report-only
+    /// variant.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        if self.report_only_flag {
+            CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY
+        } else {
+            CROSS_ORIGIN_EMBEDDER_POLICY
+        }
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reporting endpoint contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Cross-Origin-Embedder-Policy should be valid ASCII")
+    }
+
+    /// Get the policy.
+    pub fn policy(&self) -> EmbedderPolicy {
+        self.policy
+    }
+
+    /// Get the name of the endpoint violations are reported to.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.report_to.as_deref()
+    }
+
+    /// Report violations to a named endpoint, as set up with the Reporting
+    /// API.
+    pub fn report_to(&mut self, endpoint: impl Into<String>) -> &mut Self {
+        self.report_to = Some(endpoint.into());
+        self
+    }
+
+    /// Change the header to `Cross-Origin-Embedder-Policy-Report-Only`
+    pub fn report_only(&mut self) -> &mut Self {
+        self.report_only_flag = true;
+        self
+    }
+
+    /// Returns `true` if violations are only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only_flag
+    }
+}
+
+impl Display for CrossOriginEmbedderPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_policy(f, self.policy.as_ref(), self.report_to.as_deref())
+    }
+}
+
+impl FromStr for CrossOriginEmbedderPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (policy, report_to) = parse_policy(s, "Cross-Origin-Embedder-Policy")?;
+        let mut output = Self::new(policy.parse()?);
+        output.report_to = report_to;
+        Ok(output)
+    }
+}
+
+/// Build a `Cross-Origin-Resource-Policy` header.
+///
+/// `Cross-Origin-Resource-Policy` (CORP) stops other sites from loading a
+/// resource with `no-cors` requests, such as `<img>` or `<script>` tags.
+/// Pages with `Cross-Origin-Embedder-Policy: require-corp` can only load
+/// cross-origin resources which opt in with `cross-origin`.
+///
+/// [Mozilla Developer Network](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Resource-Policy)
+///
+/// # Specifications
+///
+/// - [Fetch Living Standard: Cross-Origin-Resource-Policy header](https://fetch.spec.whatwg.org/#cross-origin-resource-policy-header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::CrossOriginResourcePolicy;
+/// use http_types::{Response, StatusCode};
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// CrossOriginResourcePolicy::CrossOrigin.apply(&mut res);
+/// assert_eq!(res["Cross-Origin-Resource-Policy"], "cross-origin");
+///
+/// let policy = CrossOriginResourcePolicy::from_headers(res)?.unwrap();
+/// assert_eq!(policy, CrossOriginResourcePolicy::CrossOrigin);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossOriginResourcePolicy {
+    /// Set to `same-site`
+    SameSite,
+    /// Set to `same-origin`
+    SameOrigin,
+    /// Set to `cross-origin`
+    CrossOrigin,
+}
+
+impl CrossOriginResourcePolicy {
+    /// Create a new instance from headers.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let value = match headers.as_ref().get(CROSS_ORIGIN_RESOURCE_POLICY) {
+            Some(values) => values.last(),
+            None => return Ok(None),
+        };
+        Ok(Some(value.as_str().parse()?))
+    }
+
+    /// Sets the `Cross-Origin-Resource-Policy` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CROSS_ORIGIN_RESOURCE_POLICY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        HeaderValue::from_str(self.as_ref()).expect("Cross-Origin-Resource-Policy is valid ASCII")
+    }
+}
+
+impl AsRef<str> for CrossOriginResourcePolicy {
+    fn as_ref(&self) -> &str {
+        match *self {
+            CrossOriginResourcePolicy::SameSite => "same-site",
+            CrossOriginResourcePolicy::SameOrigin => "same-origin",
+            CrossOriginResourcePolicy::CrossOrigin => "cross-origin",
+        }
+    }
+}
+
+impl Display for CrossOriginResourcePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for CrossOriginResourcePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "same-site" => Ok(CrossOriginResourcePolicy::SameSite),
+            "same-origin" => Ok(CrossOriginResourcePolicy::SameOrigin),
+            "cross-origin" => Ok(CrossOriginResourcePolicy::CrossOrigin),
+            _ => Err(invalid("Cross-Origin-Resource-Policy", s)),
+        }
+    }
+}
+
+fn invalid(header: &str, s: &str) -> Error {
+    Error::from_str(
+        StatusCode::BadRequest,
+        format!("`{}` is not a valid {}", s, header),
+    )
+}
+
+/// Serialize a policy token, with an optional `report-to` parameter.
+fn fmt_policy(f: &mut fmt::Formatter<'_>, policy: &str, report_to: Option<&str>) -> fmt::Result {
+    write!(f, "{}", policy)?;
+    if let Some(endpoint) = report_to {
+        write!(f, "; report-to={}", fmt_quoted_string(endpoint))?;
+    }
+    Ok(())
+}
+
+/// Parse a policy token, and its `report-to` parameter. Other parameters are
+/// ignored.
+fn parse_policy<'a>(s: &'a str, header: &str) -> crate::Result<(&'a str, Option<String>)> {
+    let mut parts = split_outside_quotes(s, ';').into_iter();
+    let policy = parts.next().ok_or_else(|| invalid(header, s))?;
+    let mut report_to = None;
+    for param in parts {
+        let (name, value) = parse_param(param).ok_or_else(|| invalid(header, s))?;
+        if name == "report-to" && report_to.is_none() {
+            report_to = Some(value);
+        }
+    }
+    Ok((policy, report_to))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn opener_policy() -> crate::Result<()> {
+        let policy: CrossOriginOpenerPolicy =
+            "same-origin-allow-popups; report-to=\"main\"; x=1".parse()?;
+        assert_eq!(policy.policy(), OpenerPolicy::SameOriginAllowPopups);
+        assert_eq!(policy.endpoint(), Some("main"));
+        assert!(!policy.is_report_only());
+
+        let mut headers = Headers::new();
+        policy.apply(&mut headers);
+        assert_eq!(
+            CrossOriginOpenerPolicy::from_headers(&headers)?.unwrap(),
+            policy
+        );
+        assert!(CrossOriginOpenerPolicy::from_headers_report_only(&headers)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn embedder_policy_report_only() -> crate::Result<()> {
+        let mut policy = CrossOriginEmbedderPolicy::new(EmbedderPolicy::RequireCorp);
+        policy.report_to("coep").report_only();
+
+        let mut headers = Headers::new();
+        policy.apply(&mut headers);
+        assert_eq!(
+            headers["Cross-Origin-Embedder-Policy-Report-Only"],
+            "require-corp; report-to=\"coep\""
+        );
+        assert!(CrossOriginEmbedderPolicy::from_headers(&headers)?.is_none());
+        assert_eq!(
+            CrossOriginEmbedderPolicy::from_headers_report_only(&headers)?.unwrap(),
+            policy
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let err = "same-site".parse::<CrossOriginOpenerPolicy>().unwrap_err();
+        assert_eq!(err.status(), 400);
+        let err = "require-corp; report-to=\"a"
+            .parse::<CrossOriginEmbedderPolicy>()
+            .unwrap_err();
+        assert_eq!(err.status(), 400);
+        let err = "none".parse::<CrossOriginResourcePolicy>().unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -1,10 +1,17 @@
-use crate::Headers;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY,
+};
+use crate::reporting::ReportTo;
+use crate::StatusCode;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+use std::str::FromStr;
 
 /// Define source value
 ///
+/// Nonce and hash sources are defined by [`Nonce`](struct.Nonce.html) and
+/// [`HashSource`](struct.HashSource.html).
+///
 /// [read more](https://content-security-policy.com)
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum Source {
@@ -72,34 +79,20 @@ impl AsRef<str> for Source {
     }
 }
 
-/// Define `report-to` directive value
-///
-/// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ReportTo {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    group: Option<String>,
-    max_age: i32,
-    endpoints: Vec<ReportToEndpoint>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    include_subdomains: Option<bool>,
-}
-
-/// Define `endpoints` for `report-to` directive value
-///
-/// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ReportToEndpoint {
-    url: String,
-}
-
 /// Build a `Content-Security-Policy` header.
 ///
 /// `Content-Security-Policy` (CSP) HTTP headers are used to prevent cross-site
 /// injections. [Read more](https://helmetjs.github.io/docs/csp/)
 ///
+/// Existing policies can be read with `from_headers`, and combined with
+/// `intersect`.
+///
 /// [Mozilla Developer Network](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy)
 ///
+/// # Specifications
+///
+/// - [Content Security Policy Level 3 (Working Draft)](https://w3c.github.io/webappsec-csp/)
+///
 /// # Examples
 ///
 /// ```
@@ -125,20 +118,18 @@ pub struct ReportToEndpoint {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContentSecurityPolicy {
-    policy: Vec<String>,
     report_only_flag: bool,
-    directives: HashMap<String, Vec<String>>,
+    directives: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for ContentSecurityPolicy {
     /// Sets the Content-Security-Policy default to "script-src 'self'; object-src 'self'"
     fn default() -> Self {
-        let policy = String::from("script-src 'self'; object-src 'self'");
-        ContentSecurityPolicy {
-            policy: vec![policy],
-            report_only_flag: false,
-            directives: HashMap::new(),
-        }
+        let mut policy = Self::new();
+        policy
+            .script_src(Source::SameOrigin)
+            .object_src(Source::SameOrigin);
+        policy
     }
 }
 
@@ -146,19 +137,72 @@ impl ContentSecurityPolicy {
     /// Create a new instance.
     pub fn new() -> Self {
         Self {
-            policy: Vec::new(),
             report_only_flag: false,
-            directives: HashMap::new(),
+            directives: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new instance from the `Content-Security-Policy` headers.
+    ///
+    /// A response can carry several policies, in several headers or separated
+    /// by commas. The browser enforces all of them, so they're combined with
+    /// [`intersect`](#method.intersect).
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_headers(headers.as_ref(), CONTENT_SECURITY_POLICY, false)
+    }
+
+    /// Create a new instance from the `Content-Security-Policy-Report-Only`
+    /// headers.
+    pub fn from_headers_report_only(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        Self::parse_headers(headers.as_ref(), CONTENT_SECURITY_POLICY_REPORT_ONLY, true)
+    }
+
+    fn parse_headers(
+        headers: &Headers,
+        name: HeaderName,
+        report_only_flag: bool,
+    ) -> crate::Result<Option<Self>> {
+        let values = match headers.get(name) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut output: Option<Self> = None;
+        for value in values {
+            for serialized in value.as_str().split(',') {
+                let policy: Self = serialized.parse()?;
+                output = Some(match output {
+                    Some(output) => output.intersect(&policy),
+                    None => policy,
+                });
+            }
         }
+        let mut output = output.unwrap_or_default();
+        output.report_only_flag = report_only_flag;
+        Ok(Some(output))
     }
 
     fn insert_directive<T: AsRef<str>>(&mut self, directive: &str, source: T) {
         let directive = String::from(directive);
-        let directives = self.directives.entry(directive).or_insert_with(Vec::new);
+        let directives = self.directives.entry(directive).or_default();
         let source: String = source.as_ref().to_string();
         directives.push(source);
     }
 
+    fn insert_flag(&mut self, directive: &str) {
+        self.directives.entry(directive.to_string()).or_default();
+    }
+
+    /// Get the values of a directive, if it's in the policy.
+    ///
+    /// Directives without values, like `upgrade-insecure-requests`, return
+    /// an empty slice.
+    pub fn directive(&self, name: &str) -> Option<&[String]> {
+        self.directives
+            .get(&name.to_ascii_lowercase())
+            .map(|sources| sources.as_slice())
+    }
+
     /// Defines the Content-Security-Policy `base-uri` directive
     ///
     /// [MDN | base-uri](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/base-uri)
@@ -171,8 +215,15 @@ impl ContentSecurityPolicy {
     ///
     /// [MDN | block-all-mixed-content](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/block-all-mixed-content)
     pub fn block_all_mixed_content(&mut self) -> &mut Self {
-        let policy = String::from("block-all-mixed-content");
-        self.policy.push(policy);
+        self.insert_flag("block-all-mixed-content");
+        self
+    }
+
+    /// Defines the Content-Security-Policy `child-src` directive
+    ///
+    /// [MDN | child-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/child-src)
+    pub fn child_src<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("child-src", source);
         self
     }
 
@@ -232,6 +283,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `manifest-src` directive
+    ///
+    /// [MDN | manifest-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/manifest-src)
+    pub fn manifest_src<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("manifest-src", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `media-src` directive
     ///
     /// [MDN | media-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/media-src)
@@ -256,6 +315,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `prefetch-src` directive
+    ///
+    /// [MDN | prefetch-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/prefetch-src)
+    pub fn prefetch_src<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("prefetch-src", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `require-sri-for` directive
     ///
     /// [MDN | require-sri-for](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/require-sri-for)
@@ -264,6 +331,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `require-trusted-types-for` directive
+    ///
+    /// [MDN | require-trusted-types-for](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/require-trusted-types-for)
+    pub fn require_trusted_types_for<T: AsRef<str>>(&mut self, sink_group: T) -> &mut Self {
+        self.insert_directive("require-trusted-types-for", sink_group);
+        self
+    }
+
     /// Defines the Content-Security-Policy `report-uri` directive
     ///
     /// [MDN | report-uri](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-uri)
@@ -274,18 +349,27 @@ impl ContentSecurityPolicy {
 
     /// Defines the Content-Security-Policy `report-to` directive
     ///
+    /// The group is declared in the `Reporting-Endpoints` or `Report-To`
+    /// header, see the [`reporting`](../reporting/index.html) module.
+    ///
     /// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
+    pub fn report_to_group<T: AsRef<str>>(&mut self, group: T) -> &mut Self {
+        self.insert_directive("report-to", group);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `report-to` directive with the
+    /// names of the groups.
+    ///
+    /// The groups themselves aren't sent, they still need to be declared in a
+    /// `Report-To` header.
+    #[deprecated(
+        since = "2.4.0",
+        note = "use `report_to_group` with the group name, and send the groups with `EndpointGroups`"
+    )]
     pub fn report_to(&mut self, endpoints: Vec<ReportTo>) -> &mut Self {
-        for endpoint in endpoints.iter() {
-            match serde_json::to_string(&endpoint) {
-                Ok(json) => {
-                    let policy = format!("report-to {}", json);
-                    self.policy.push(policy);
-                }
-                Err(error) => {
-                    println!("{:?}", error);
-                }
-            }
+        for endpoint in &endpoints {
+            self.insert_directive("report-to", endpoint.group());
         }
         self
     }
@@ -306,6 +390,22 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `script-src-attr` directive
+    ///
+    /// [MDN | script-src-attr](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src-attr)
+    pub fn script_src_attr<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("script-src-attr", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `script-src-elem` directive
+    ///
+    /// [MDN | script-src-elem](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src-elem)
+    pub fn script_src_elem<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("script-src-elem", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `style-src` directive
     ///
     /// [MDN | style-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src)
@@ -314,12 +414,35 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `style-src-attr` directive
+    ///
+    /// [MDN | style-src-attr](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src-attr)
+    pub fn style_src_attr<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("style-src-attr", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `style-src-elem` directive
+    ///
+    /// [MDN | style-src-elem](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src-elem)
+    pub fn style_src_elem<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("style-src-elem", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `trusted-types` directive
+    ///
+    /// [MDN | trusted-types](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/trusted-types)
+    pub fn trusted_types<T: AsRef<str>>(&mut self, policy_name: T) -> &mut Self {
+        self.insert_directive("trusted-types", policy_name);
+        self
+    }
+
     /// Defines the Content-Security-Policy `upgrade-insecure-requests` directive
     ///
     /// [MDN | upgrade-insecure-requests](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/upgrade-insecure-requests)
     pub fn upgrade_insecure_requests(&mut self) -> &mut Self {
-        let policy = String::from("upgrade-insecure-requests");
-        self.policy.push(policy);
+        self.insert_flag("upgrade-insecure-requests");
         self
     }
 
@@ -337,23 +460,424 @@ impl ContentSecurityPolicy {
         self
     }
 
-    /// Create and retrieve the policy value
-    fn value(&mut self) -> String {
-        for (directive, sources) in &self.directives {
-            let policy = format!("{} {}", directive, sources.join(" "));
-            self.policy.push(policy);
-            self.policy.sort();
+    /// Returns `true` if violations are only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only_flag
+    }
+
+    /// Combine two policies into one which only allows what both policies
+    /// allow, as if the browser enforced both.
+    ///
+    /// Sources are matched as written, so the result may be stricter than
+    /// enforcing both policies: `https://example.com` and `https:` have
+    /// nothing in common here. Directives missing from a policy fall back to
+    /// the directives which would apply in their place, such as `default-src`.
+    /// When either policy has a nonce, a hash or `'strict-dynamic'`, the
+    /// sources browsers ignore next to them, such as `'unsafe-inline'`, are
+    /// dropped from both before intersecting. Reporting directives of both policies are kept. The result is
+    /// report-only if this policy is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> http_types::Result<()> {
+    /// #
+    /// use http_types::security::ContentSecurityPolicy;
+    ///
+    /// let upstream: ContentSecurityPolicy =
+    ///     "default-src 'self' cdn.example; img-src *".parse()?;
+    /// let ours: ContentSecurityPolicy = "script-src 'self'; img-src data: cdn.example".parse()?;
+    ///
+    /// let merged = upstream.intersect(&ours);
+    /// assert_eq!(
+    ///     merged.to_string(),
+    ///     "default-src 'self' cdn.example; img-src cdn.example; script-src 'self'"
+    /// );
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut output = Self::new();
+        output.report_only_flag = self.report_only_flag;
+
+        let names: BTreeSet<&String> = self
+            .directives
+            .keys()
+            .chain(other.directives.keys())
+            .collect();
+        for name in names {
+            let sources = match name.as_str() {
+                "block-all-mixed-content" | "upgrade-insecure-requests" => vec![],
+                "report-uri" | "report-to" | "require-sri-for" | "require-trusted-types-for" => {
+                    let mut sources = self.directives.get(name).cloned().unwrap_or_default();
+                    for source in other.directives.get(name).into_iter().flatten() {
+                        if !sources.contains(source) {
+                            sources.push(source.clone());
+                        }
+                    }
+                    sources
+                }
+                name => match (self.effective(name), other.effective(name)) {
+                    (Some(ours), Some(theirs)) => intersect_sources(name, ours, theirs),
+                    (Some(sources), None) | (None, Some(sources)) => sources.to_vec(),
+                    (None, None) => continue,
+                },
+            };
+            output.directives.insert(name.clone(), sources);
         }
-        self.policy.join("; ")
+        output
     }
 
-    /// Sets the `Content-Security-Policy` (CSP) HTTP header to prevent cross-site injections
-    pub fn apply(&mut self, mut headers: impl AsMut<Headers>) {
-        let name = if self.report_only_flag {
-            "Content-Security-Policy-Report-Only"
+    /// Get the sources of a directive, or of the directive it falls back to.
+    fn effective(&self, name: &str) -> Option<&[String]> {
+        fallbacks(name)
+            .iter()
+            .find_map(|fallback| self.directive(fallback))
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        if self.report_only_flag {
+            CONTENT_SECURITY_POLICY_REPORT_ONLY
         } else {
-            "Content-Security-Policy"
+            CONTENT_SECURITY_POLICY
+        }
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Content-Security-Policy should be valid ASCII")
+    }
+
+    /// Sets the `Content-Security-Policy` (CSP) HTTP header to prevent cross-site injections
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+}
+
+/// The directives whose sources apply to a directive, in order, as listed by
+/// the [effective directive fallback list](https://w3c.github.io/webappsec-csp/#directive-fallback-list).
+fn fallbacks(name: &str) -> Vec<&str> {
+    match name {
+        "script-src-elem" | "script-src-attr" => vec![name, "script-src", "default-src"],
+        "style-src-elem" | "style-src-attr" => vec![name, "style-src", "default-src"],
+        "worker-src" => vec![name, "child-src", "script-src", "default-src"],
+        "frame-src" => vec![name, "child-src", "default-src"],
+        "child-src" | "connect-src" | "font-src" | "img-src" | "manifest-src" | "media-src"
+        | "object-src" | "prefetch-src" | "script-src" | "style-src" => vec![name, "default-src"],
+        _ => vec![name],
+    }
+}
+
+/// Keep the sources which are allowed by both lists.
+fn intersect_sources(name: &str, ours: &[String], theirs: &[String]) -> Vec<String> {
+    let is_none = |sources: &[String]| sources.iter().any(|s| s.eq_ignore_ascii_case("'none'"));
+    if is_none(ours) || is_none(theirs) {
+        return vec![Source::None.as_ref().to_string()];
+    }
+
+    // Nonces and hashes make browsers ignore `'unsafe-inline'`, and
+    // `'strict-dynamic'` also makes them ignore `'self'`, host and scheme
+    // sources for scripts. Those sources may still be allowed by the other
+    // policy, so keeping them would allow more than enforcing both does.
+    let is_script = name == "default-src" || name == "worker-src" || name.starts_with("script-src");
+    let has_source = |prefixes: &[&str]| {
+        ours.iter().chain(theirs).any(|source| {
+            let source = source.to_ascii_lowercase();
+            prefixes.iter().any(|prefix| source.starts_with(prefix))
+        })
+    };
+    let strict_dynamic = is_script && has_source(&["'strict-dynamic'"]);
+    let ignores_inline =
+        strict_dynamic || has_source(&["'nonce-", "'sha256-", "'sha384-", "'sha512-"]);
+    let is_ignored = |source: &String| {
+        (ignores_inline && source.eq_ignore_ascii_case("'unsafe-inline'"))
+            || (strict_dynamic
+                && (source.eq_ignore_ascii_case("'self'") || !source.starts_with('\'')))
+    };
+    let ours: Vec<String> = ours.iter().filter(|s| !is_ignored(s)).cloned().collect();
+    let theirs: Vec<String> = theirs.iter().filter(|s| !is_ignored(s)).cloned().collect();
+    let (ours, theirs) = (&ours[..], &theirs[..]);
+
+    let contains = |sources: &[String], source: &String| {
+        sources.iter().any(|s| s.eq_ignore_ascii_case(source))
+    };
+    let is_wildcard = |sources: &[String]| name != "sandbox" && contains(sources, &"*".into());
+    // `*` matches every network source and `'self'`, but no other keywords
+    // and no local schemes.
+    let is_network = |source: &String| {
+        (source.eq_ignore_ascii_case("'self'") || !source.starts_with('\''))
+            && !matches!(
+                source.to_ascii_lowercase().as_str(),
+                "data:" | "blob:" | "filesystem:" | "mediastream:"
+            )
+    };
+
+    let mut output = vec![];
+    for source in ours {
+        let allowed = contains(theirs, source) || (is_wildcard(theirs) && is_network(source));
+        if allowed && !contains(&output, source) {
+            output.push(source.clone());
+        }
+    }
+    if is_wildcard(ours) {
+        for source in theirs {
+            if is_network(source) && !contains(&output, source) {
+                output.push(source.clone());
+            }
+        }
+    }
+
+    // An empty `sandbox` applies every restriction, other directives need
+    // `'none'` to block everything.
+    if output.is_empty() && name != "sandbox" {
+        output.push(Source::None.as_ref().to_string());
+    }
+    output
+}
+
+impl fmt::Display for ContentSecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, (directive, sources)) in self.directives.iter().enumerate() {
+            if n > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", directive)?;
+            for source in sources {
+                write!(f, " {}", source)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ContentSecurityPolicy {
+    type Err = crate::Error;
+
+    /// Parse a single serialized policy.
+    ///
+    /// Directive names are case-insensitive, and only the first occurrence of
+    /// a directive is used.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self::new();
+        for directive in s.split(';') {
+            let mut tokens = directive.split_ascii_whitespace();
+            let name = match tokens.next() {
+                Some(name) => name.to_ascii_lowercase(),
+                None => continue,
+            };
+            if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                return Err(crate::Error::from_str(
+                    StatusCode::BadRequest,
+                    format!("`{}` is not a valid Content-Security-Policy", s),
+                ));
+            }
+            if policy.directives.contains_key(&name) {
+                continue;
+            }
+            let sources = tokens.map(|token| token.to_string()).collect();
+            policy.directives.insert(name, sources);
+        }
+        Ok(policy)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reporting::{EndpointGroups, ReportToEndpoint};
+    use crate::Url;
+    use std::time::Duration;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let policy: ContentSecurityPolicy =
+            " Script-Src 'self'  https://cdn.example;;upgrade-insecure-requests; script-src *"
+                .parse()?;
+        assert_eq!(
+            policy.directive("script-src").unwrap(),
+            ["'self'", "https://cdn.example"]
+        );
+        assert_eq!(
+            policy.directive("upgrade-insecure-requests").unwrap().len(),
+            0
+        );
+        assert!(policy.directive("img-src").is_none());
+        assert_eq!(
+            policy.to_string(),
+            "script-src 'self' https://cdn.example; upgrade-insecure-requests"
+        );
+
+        let err = "script_src 'self'"
+            .parse::<ContentSecurityPolicy>()
+            .unwrap_err();
+        assert_eq!(err.status(), 400);
+        Ok(())
+    }
+
+    #[test]
+    fn from_headers() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        assert!(ContentSecurityPolicy::from_headers(&headers)?.is_none());
+
+        headers.append("Content-Security-Policy", "default-src 'self' a.example");
+        headers.append(
+            "Content-Security-Policy",
+            "default-src *, object-src 'none'",
+        );
+        let policy = ContentSecurityPolicy::from_headers(&headers)?.unwrap();
+        assert_eq!(
+            policy.to_string(),
+            "default-src 'self' a.example; object-src 'none'"
+        );
+        assert!(!policy.is_report_only());
+        assert!(ContentSecurityPolicy::from_headers_report_only(&headers)?.is_none());
+
+        let mut policy = ContentSecurityPolicy::new();
+        policy.script_src_elem(Source::StrictDynamic).report_only();
+        let mut headers = Headers::new();
+        policy.apply(&mut headers);
+        assert_eq!(
+            ContentSecurityPolicy::from_headers_report_only(&headers)?.unwrap(),
+            policy
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn report_to_round_trip() -> crate::Result<()> {
+        let mut group = ReportTo::new(Duration::from_secs(86_400));
+        group
+            .set_group("csp-endpoint")
+            .push_endpoint(ReportToEndpoint::new(Url::parse(
+                "https://example.com/csp",
+            )?));
+        let mut groups = EndpointGroups::new();
+        groups.push(group);
+
+        let mut policy = ContentSecurityPolicy::new();
+        policy
+            .default_src(Source::SameOrigin)
+            .report_to_group("csp-endpoint");
+
+        let mut headers = Headers::new();
+        groups.apply(&mut headers);
+        policy.apply(&mut headers);
+        assert_eq!(
+            headers["Content-Security-Policy"],
+            "default-src 'self'; report-to csp-endpoint"
+        );
+
+        let policy = ContentSecurityPolicy::from_headers(&headers)?.unwrap();
+        let group = policy.directive("report-to").unwrap()[0].as_str();
+        let groups = EndpointGroups::from_headers(&headers)?.unwrap();
+        assert_eq!(
+            groups.get(group).unwrap().endpoints()[0].url(),
+            "https://example.com/csp"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_report_to() {
+        let mut group = ReportTo::new(Duration::from_secs(86_400));
+        group.set_group("csp-endpoint");
+
+        let mut policy = ContentSecurityPolicy::new();
+        policy.report_to(vec![group, ReportTo::new(Duration::from_secs(60))]);
+        assert_eq!(
+            policy.directive("report-to").unwrap(),
+            ["csp-endpoint", "default"]
+        );
+    }
+
+    #[test]
+    fn intersect() -> crate::Result<()> {
+        let ours: ContentSecurityPolicy = "default-src 'self'; script-src 'self' 'nonce-abc'; \
+             sandbox allow-forms allow-scripts; report-uri /a; upgrade-insecure-requests"
+            .parse()?;
+        let theirs: ContentSecurityPolicy = "default-src * data:; worker-src blob:; \
+             sandbox allow-popups; report-uri /b; frame-ancestors 'none'"
+            .parse()?;
+
+        let policy = ours.intersect(&theirs);
+        assert_eq!(policy.directive("default-src").unwrap(), ["'self'"]);
+        assert_eq!(policy.directive("script-src").unwrap(), ["'self'"]);
+        assert_eq!(policy.directive("worker-src").unwrap(), ["'none'"]);
+        assert_eq!(policy.directive("sandbox").unwrap().len(), 0);
+        assert_eq!(policy.directive("report-uri").unwrap(), ["/a", "/b"]);
+        assert_eq!(policy.directive("frame-ancestors").unwrap(), ["'none'"]);
+        assert!(policy.directive("upgrade-insecure-requests").is_some());
+        assert!(policy.directive("img-src").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn intersect_keywords() -> crate::Result<()> {
+        let intersect = |ours: &str, theirs: &str| -> crate::Result<String> {
+            let ours: ContentSecurityPolicy = ours.parse()?;
+            let theirs: ContentSecurityPolicy = theirs.parse()?;
+            Ok(ours.intersect(&theirs).to_string())
         };
-        headers.as_mut().insert(name, self.value());
+
+        assert_eq!(
+            intersect(
+                "script-src 'unsafe-inline'",
+                "script-src 'unsafe-inline' 'nonce-abc'"
+            )?,
+            "script-src 'none'"
+        );
+        assert_eq!(
+            intersect(
+                "style-src 'unsafe-inline' 'sha256-abc'",
+                "style-src 'unsafe-inline' 'sha256-abc'"
+            )?,
+            "style-src 'sha256-abc'"
+        );
+        assert_eq!(
+            intersect(
+                "script-src 'strict-dynamic' 'nonce-abc' 'self' https://cdn.example",
+                "script-src https://cdn.example 'self' 'nonce-abc'"
+            )?,
+            "script-src 'nonce-abc'"
+        );
+        assert_eq!(
+            intersect(
+                "default-src 'strict-dynamic' 'nonce-abc' https: 'unsafe-inline'",
+                "default-src 'strict-dynamic' 'nonce-abc' *"
+            )?,
+            "default-src 'strict-dynamic' 'nonce-abc'"
+        );
+        assert_eq!(
+            intersect(
+                "img-src https://cdn.example 'strict-dynamic'",
+                "img-src https://cdn.example"
+            )?,
+            "img-src https://cdn.example"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn apply_twice() {
+        let mut policy = ContentSecurityPolicy::default();
+        policy
+            .trusted_types("default")
+            .require_trusted_types_for("'script'");
+
+        let mut headers = Headers::new();
+        policy.apply(&mut headers);
+        policy.apply(&mut headers);
+        assert_eq!(
+            headers["Content-Security-Policy"],
+            "object-src 'self'; require-trusted-types-for 'script'; script-src 'self'; \
+             trusted-types default"
+        );
     }
 }
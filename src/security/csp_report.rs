@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+/// The body of a legacy `application/csp-report` request, which browsers
+/// send to the `report-uri` of a `Content-Security-Policy`.
+///
+/// [MDN | report-uri](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-uri)
+///
+/// # Specifications
+///
+/// - [Content Security Policy Level 2, section 4.4: Reporting](https://www.w3.org/TR/CSP2/#violation-reports)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CspReport {
+    /// The violation.
+    #[serde(rename = "csp-report")]
+    pub csp_report: CspReportBody,
+}
+
+/// A violation in a legacy `application/csp-report` body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CspReportBody {
+    /// The URL of the document in which the violation occurred.
+    pub document_uri: String,
+    /// The referrer of the document in which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
+    /// The directive whose enforcement caused the violation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub violated_directive: Option<String>,
+    /// The directive whose enforcement caused the violation, without its
+    /// values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_directive: Option<String>,
+    /// The policy as it was received by the browser.
+    pub original_policy: String,
+    /// Either `enforce` or `report`, depending on the header of the policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disposition: Option<String>,
+    /// The URL of the resource which was blocked, or `inline` or `eval`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_uri: Option<String>,
+    /// The line number in `source_file` at which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<u32>,
+    /// The column number in `source_file` at which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_number: Option<u32>,
+    /// The URL of the script in which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// The HTTP status code of the document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// The first characters of the blocked inline script, style or event
+    /// handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_sample: Option<String>,
+}
+
+/// The body of a `csp-violation` report of the Reporting API.
+///
+/// It's sent in [`Report`](../reporting/struct.Report.html)s of type
+/// `csp-violation`. Legacy reports are converted into this type with `From`.
+///
+/// # Specifications
+///
+/// - [Content Security Policy Level 3, section 5.3: CSPViolationReportBody](https://w3c.github.io/webappsec-csp/#reporting)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CspViolation {
+    /// The URL of the document in which the violation occurred.
+    #[serde(rename = "documentURL")]
+    pub document_url: String,
+    /// The referrer of the document in which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
+    /// The URL of the resource which was blocked, or `inline` or `eval`.
+    #[serde(
+        rename = "blockedURL",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blocked_url: Option<String>,
+    /// The directive whose enforcement caused the violation.
+    pub effective_directive: String,
+    /// The policy as it was received by the browser.
+    pub original_policy: String,
+    /// The URL of the script in which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// The first characters of the blocked inline script, style or event
+    /// handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<String>,
+    /// Either `enforce` or `report`, depending on the header of the policy.
+    pub disposition: String,
+    /// The HTTP status code of the document.
+    pub status_code: u16,
+    /// The line number in `source_file` at which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<u32>,
+    /// The column number in `source_file` at which the violation occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_number: Option<u32>,
+}
+
+impl From<CspReportBody> for CspViolation {
+    /// Convert a legacy report. Older browsers only send `violated-directive`,
+    /// which is used when `effective-directive` is missing.
+    fn from(report: CspReportBody) -> Self {
+        let violated_directive = report.violated_directive;
+        let effective_directive = report
+            .effective_directive
+            .or_else(|| {
+                let directive = violated_directive?;
+                directive.split_whitespace().next().map(String::from)
+            })
+            .unwrap_or_default();
+        Self {
+            document_url: report.document_uri,
+            referrer: report.referrer,
+            blocked_url: report.blocked_uri,
+            effective_directive,
+            original_policy: report.original_policy,
+            source_file: report.source_file,
+            sample: report.script_sample,
+            disposition: report.disposition.unwrap_or_else(|| "enforce".into()),
+            status_code: report.status_code.unwrap_or_default(),
+            line_number: report.line_number,
+            column_number: report.column_number,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use std::fmt;
+
+use crate::{Error, Request, StatusCode};
+
+/// A `'nonce-…'` source, allowing inline scripts and styles which carry the
+/// same `nonce` attribute.
+///
+/// A new nonce must be used for every response, so attackers can't guess
+/// it. `Nonce::for_request` generates one per request, and stores it in the
+/// request extensions for templates to read.
+///
+/// [MDN | nonce](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src#unsafe_inline_script)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::security::{ContentSecurityPolicy, Nonce, Source};
+/// use http_types::{Method, Request, Response, StatusCode, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+/// Nonce::for_request(&mut req);
+///
+/// // In the template.
+/// let nonce = req.ext().get::<Nonce>().unwrap();
+/// let html = format!(r#"<script nonce="{}">boot()</script>"#, nonce.value());
+///
+/// let mut policy = ContentSecurityPolicy::new();
+/// policy
+///     .script_src(nonce)
+///     .script_src(Source::StrictDynamic)
+///     .object_src(Source::None)
+///     .base_uri(Source::None);
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// policy.apply(&mut res);
+/// assert!(res["Content-Security-Policy"]
+///     .as_str()
+///     .contains(&format!("script-src 'nonce-{}' 'strict-dynamic'", nonce.value())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nonce {
+    source: String,
+}
+
+impl Nonce {
+    /// Generate a new nonce from 128 bits of cryptographically secure
+    /// randomness.
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 16];
+        OsRng.fill_bytes(&mut bytes);
+        Self::from_value(&base64::encode(bytes))
+    }
+
+    /// Get the nonce of a request, generating one and storing it in the
+    /// request extensions if there is none yet.
+    pub fn for_request(req: &mut Request) -> Self {
+        if let Some(nonce) = req.ext().get::<Nonce>() {
+            return nonce.clone();
+        }
+        let nonce = Self::generate();
+        req.ext_mut().insert(nonce.clone());
+        nonce
+    }
+
+    /// Create a new instance from an existing base64 value.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value isn't base64 or base64url encoded.
+    pub fn new(value: &str) -> crate::Result<Self> {
+        if !is_base64(value) {
+            return Err(Error::from_str(
+                StatusCode::InternalServerError,
+                format!("`{}` is not a valid CSP nonce", value),
+            ));
+        }
+        Ok(Self::from_value(value))
+    }
+
+    fn from_value(value: &str) -> Self {
+        Self {
+            source: format!("'nonce-{}'", value),
+        }
+    }
+
+    /// Get the value, for the `nonce` attribute of inline elements.
+    pub fn value(&self) -> &str {
+        &self.source["'nonce-".len()..self.source.len() - 1]
+    }
+}
+
+impl AsRef<str> for Nonce {
+    fn as_ref(&self) -> &str {
+        &self.source
+    }
+}
+
+impl fmt::Display for Nonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// A `'sha256-…'`, `'sha384-…'` or `'sha512-…'` source, allowing an inline
+/// script or style with exactly the hashed contents.
+///
+/// [MDN | hash](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src#unsafe_inline_script)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::security::{ContentSecurityPolicy, HashSource};
+///
+/// // The contents between `<script>` and `</script>`, as is.
+/// let hash = HashSource::sha256("alert('Hello, world.');");
+/// assert_eq!(hash.as_ref(), "'sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng='");
+///
+/// let mut policy = ContentSecurityPolicy::new();
+/// policy.script_src(hash);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HashSource {
+    source: String,
+}
+
+impl HashSource {
+    /// Hash the contents of an inline element with SHA-256.
+    pub fn sha256(contents: impl AsRef<[u8]>) -> Self {
+        Self::new("sha256", &Sha256::digest(contents.as_ref()))
+    }
+
+    /// Hash the contents of an inline element with SHA-384.
+    pub fn sha384(contents: impl AsRef<[u8]>) -> Self {
+        Self::new("sha384", &Sha384::digest(contents.as_ref()))
+    }
+
+    /// Hash the contents of an inline element with SHA-512.
+    pub fn sha512(contents: impl AsRef<[u8]>) -> Self {
+        Self::new("sha512", &Sha512::digest(contents.as_ref()))
+    }
+
+    fn new(algorithm: &str, digest: &[u8]) -> Self {
+        Self {
+            source: format!("'{}-{}'", algorithm, base64::encode(digest)),
+        }
+    }
+}
+
+impl AsRef<str> for HashSource {
+    fn as_ref(&self) -> &str {
+        &self.source
+    }
+}
+
+impl fmt::Display for HashSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Returns `true` if the value matches the `base64-value` grammar of CSP.
+fn is_base64(value: &str) -> bool {
+    let data = value.trim_end_matches('=');
+    !data.is_empty()
+        && value.len() - data.len() <= 2
+        && data
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Method, Url};
+
+    #[test]
+    fn nonce_per_request() {
+        let mut req = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+        let nonce = Nonce::for_request(&mut req);
+        assert_eq!(nonce.value().len(), 24);
+        assert_eq!(nonce.as_ref(), format!("'nonce-{}'", nonce.value()));
+        assert_eq!(Nonce::for_request(&mut req), nonce);
+        assert_eq!(req.ext().get::<Nonce>(), Some(&nonce));
+
+        let mut other = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+        assert_ne!(Nonce::for_request(&mut other), nonce);
+    }
+
+    #[test]
+    fn nonce_values() {
+        assert_eq!(Nonce::new("abc-_+/==").unwrap().value(), "abc-_+/==");
+        for value in &["", "==", "a b", "a'b", "a===", "a=b"] {
+            assert!(Nonce::new(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            HashSource::sha384("").as_ref(),
+            "'sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb'"
+        );
+        assert!(HashSource::sha512(b"x").as_ref().starts_with("'sha512-"));
+    }
+}
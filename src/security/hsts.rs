@@ -0,0 +1,274 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, STRICT_TRANSPORT_SECURITY};
+use crate::parse_utils::{parse_delta_seconds, parse_param, split_outside_quotes};
+use crate::{Error, StatusCode};
+
+/// The shortest `max-age` accepted by the HSTS preload list, one year.
+const PRELOAD_MIN_MAX_AGE: Duration = Duration::from_secs(31_536_000);
+
+/// Build a `Strict-Transport-Security` header, to keep your users on `HTTPS`.
+///
+/// Note that the header won’t tell users on HTTP to switch to HTTPS, it will
+/// tell HTTPS users to stick around. `Hsts::new()` defaults to 60 days.
+///
+/// [read more](https://helmetjs.github.io/docs/hsts/)
+///
+/// # Specifications
+///
+/// - [RFC6797: HTTP Strict Transport Security (HSTS)](https://tools.ietf.org/html/rfc6797)
+/// - [HSTS preload list submission requirements](https://hstspreload.org/#submission-requirements)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::Hsts;
+/// use http_types::{Response, StatusCode};
+/// use std::time::Duration;
+///
+/// let mut hsts = Hsts::new();
+/// hsts.set_max_age(Duration::from_secs(63_072_000))
+///     .set_include_subdomains(true)
+///     .set_preload(true);
+/// hsts.validate()?;
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// hsts.apply(&mut res);
+/// assert_eq!(
+///     res["Strict-Transport-Security"],
+///     "max-age=63072000; includeSubDomains; preload"
+/// );
+///
+/// let hsts = Hsts::from_headers(res)?.unwrap();
+/// assert!(hsts.include_subdomains());
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hsts {
+    max_age: Duration,
+    include_subdomains: bool,
+    preload: bool,
+}
+
+impl Default for Hsts {
+    /// Sets the `max-age` to 60 days, without `includeSubDomains` or `preload`.
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(5_184_000),
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+}
+
+impl Hsts {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only the first `Strict-Transport-Security` header is used, as
+    /// recipients are required to do.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let value = match headers.as_ref().get(STRICT_TRANSPORT_SECURITY) {
+            Some(values) => &values[0],
+            None => return Ok(None),
+        };
+        Ok(Some(value.as_str().parse()?))
+    }
+
+    /// Sets the `Strict-Transport-Security` header.
+    ///
+    /// The policy isn't validated, see [`validate`](#method.validate).
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        STRICT_TRANSPORT_SECURITY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Strict-Transport-Security should be valid ASCII")
+    }
+
+    /// Get how long the browser should only connect over `HTTPS`.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Set how long the browser should only connect over `HTTPS`.
+    ///
+    /// A `max-age` of zero asks the browser to forget the policy.
+    pub fn set_max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns `true` if the policy applies to all subdomains as well.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Set whether the policy applies to all subdomains as well.
+    pub fn set_include_subdomains(&mut self, include_subdomains: bool) -> &mut Self {
+        self.include_subdomains = include_subdomains;
+        self
+    }
+
+    /// Returns `true` if the site asks to be included in browsers' preload
+    /// lists.
+    pub fn preload(&self) -> bool {
+        self.preload
+    }
+
+    /// Set whether the site asks to be included in browsers' preload lists.
+    ///
+    /// The preload lists have further requirements, see
+    /// [`validate`](#method.validate).
+    pub fn set_preload(&mut self, preload: bool) -> &mut Self {
+        self.preload = preload;
+        self
+    }
+
+    /// Check the policy meets the requirements of the HSTS preload list, if
+    /// `preload` is set.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `preload` is set, and either the `max-age` is
+    /// shorter than a year, or `includeSubDomains` isn't set.
+    pub fn validate(&self) -> crate::Result<()> {
+        if !self.preload {
+            return Ok(());
+        }
+        if self.max_age < PRELOAD_MIN_MAX_AGE {
+            crate::bail!("HSTS preload requires a max-age of at least one year");
+        }
+        if !self.include_subdomains {
+            crate::bail!("HSTS preload requires includeSubDomains");
+        }
+        Ok(())
+    }
+}
+
+impl Display for Hsts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "max-age={}", self.max_age.as_secs())?;
+        if self.include_subdomains {
+            write!(f, "; includeSubDomains")?;
+        }
+        if self.preload {
+            write!(f, "; preload")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Hsts {
+    type Err = Error;
+
+    /// Parse a `Strict-Transport-Security` value.
+    ///
+    /// Unknown directives are ignored. A missing `max-age`, or a directive
+    /// which appears more than once, makes the whole value invalid. A
+    /// `max-age` too large to represent is capped at 2^31 seconds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Strict-Transport-Security", s),
+            )
+        };
+
+        let mut max_age = None;
+        let mut output = Self::new();
+        let mut seen: Vec<String> = vec![];
+        for directive in split_outside_quotes(s, ';') {
+            let (name, value) = parse_param(directive).ok_or_else(invalid)?;
+            let name = name.to_ascii_lowercase();
+            if seen.contains(&name) {
+                return Err(invalid());
+            }
+            match name.as_str() {
+                "max-age" => max_age = Some(parse_delta_seconds(&value).ok_or_else(invalid)?),
+                "includesubdomains" => output.include_subdomains = true,
+                "preload" => output.preload = true,
+                _ => {}
+            }
+            seen.push(name);
+        }
+        output.max_age = max_age.ok_or_else(invalid)?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let hsts: Hsts = "max-age=\"31536000\"; INCLUDESUBDOMAINS; x-ext=1".parse()?;
+        assert_eq!(hsts.max_age(), Duration::from_secs(31_536_000));
+        assert!(hsts.include_subdomains());
+        assert!(!hsts.preload());
+
+        let hsts: Hsts = "preload;max-age=0".parse()?;
+        assert_eq!(hsts.max_age(), Duration::from_secs(0));
+        assert!(hsts.preload());
+
+        let hsts: Hsts = "max-age=99999999999999999999999".parse()?;
+        assert_eq!(hsts.max_age(), Duration::from_secs(2_147_483_648));
+        Ok(())
+    }
+
+    #[test]
+    fn from_headers_uses_first_value() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        assert!(Hsts::from_headers(&headers)?.is_none());
+        headers.append("Strict-Transport-Security", "max-age=10");
+        headers.append("Strict-Transport-Security", "max-age=20");
+        let hsts = Hsts::from_headers(headers)?.unwrap();
+        assert_eq!(hsts.max_age(), Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_preload() {
+        let mut hsts = Hsts::new();
+        assert!(hsts.validate().is_ok());
+        hsts.set_preload(true);
+        assert!(hsts.validate().is_err());
+        hsts.set_max_age(Duration::from_secs(31_536_000));
+        assert!(hsts.validate().is_err());
+        hsts.set_include_subdomains(true);
+        assert!(hsts.validate().is_ok());
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "",
+            "includeSubDomains",
+            "max-age=-1",
+            "max-age=1.5",
+            "max-age=+1",
+            "max-age=1; max-age=2",
+            "max-age=1; preload; Preload",
+        ] {
+            let err = s.parse::<Hsts>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
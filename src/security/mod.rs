@@ -12,9 +12,23 @@
 //! ```
 
 use crate::headers::{HeaderName, HeaderValue, Headers};
-pub use csp::{ContentSecurityPolicy, ReportTo, ReportToEndpoint, Source};
+pub use crate::reporting::{ReportTo, ReportToEndpoint};
+pub use cross_origin::{
+    CrossOriginEmbedderPolicy, CrossOriginOpenerPolicy, CrossOriginResourcePolicy, EmbedderPolicy,
+    OpenerPolicy,
+};
+pub use csp::{ContentSecurityPolicy, Source};
+pub use csp_report::{CspReport, CspReportBody, CspViolation};
+pub use csp_source::{HashSource, Nonce};
+pub use hsts::Hsts;
+pub use permissions_policy::{Allowlist, Feature, PermissionsPolicy};
 
+mod cross_origin;
 mod csp;
+mod csp_report;
+mod csp_source;
+mod hsts;
+mod permissions_policy;
 
 /// Apply a set of default protections.
 ///
@@ -32,10 +46,34 @@ pub fn default(mut headers: impl AsMut<Headers>) {
     nosniff(&mut headers);
     frameguard(&mut headers, None);
     powered_by(&mut headers, None);
-    hsts(&mut headers);
+    Hsts::new().apply(&mut headers);
     xss_filter(&mut headers);
 }
 
+/// Make pages cross-origin isolated, which is needed to use
+/// `SharedArrayBuffer` and high-resolution timers.
+///
+/// This sets `Cross-Origin-Opener-Policy: same-origin` and
+/// `Cross-Origin-Embedder-Policy: require-corp`. Cross-origin resources the
+/// page loads must then opt in with CORS, or with
+/// [`CrossOriginResourcePolicy::CrossOrigin`](enum.CrossOriginResourcePolicy.html).
+///
+/// [read more](https://web.dev/coop-coep/)
+///
+/// ## Examples
+/// ```
+/// use http_types::{Response, StatusCode};
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// http_types::security::isolate(&mut res);
+/// assert_eq!(res["Cross-Origin-Opener-Policy"], "same-origin");
+/// assert_eq!(res["Cross-Origin-Embedder-Policy"], "require-corp");
+/// ```
+pub fn isolate(mut headers: impl AsMut<Headers>) {
+    CrossOriginOpenerPolicy::new(OpenerPolicy::SameOrigin).apply(&mut headers);
+    CrossOriginEmbedderPolicy::new(EmbedderPolicy::RequireCorp).apply(&mut headers);
+}
+
 /// Disable browsers’ DNS prefetching by setting the `X-DNS-Prefetch-Control` header.
 ///
 /// [read more](https://helmetjs.github.io/docs/dns-prefetch-control/)
@@ -116,20 +154,10 @@ pub fn powered_by(mut headers: impl AsMut<Headers>, value: Option<HeaderValue>)
 /// stick around. Defaults to 60 days.
 ///
 /// [read more](https://helmetjs.github.io/docs/hsts/)
-///
-// /// ## Examples
-// /// ```
-// /// use http_types::Response;
-// ///
-// /// let mut res = Response::new(StatusCode::Ok);
-// /// http_types::security::hsts(&mut headers);
-// /// assert_eq!(headers["Strict-Transport-Security"], "max-age=5184000");
-// /// ```
+#[deprecated(since = "2.4.0", note = "use `Hsts::new().apply(headers)` instead")]
 #[inline]
-pub fn hsts(mut headers: impl AsMut<Headers>) {
-    headers
-        .as_mut()
-        .insert("Strict-Transport-Security", "max-age=5184000");
+pub fn hsts(headers: impl AsMut<Headers>) {
+    Hsts::new().apply(headers);
 }
 
 /// Prevent browsers from trying to guess (“sniff”) the MIME type, which can have security
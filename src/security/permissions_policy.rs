@@ -0,0 +1,496 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, PERMISSIONS_POLICY};
+use crate::parse_utils::{
+    fmt_quoted_string, is_sf_key, is_tchar, parse_quoted_string, split_outside_quotes, trim_ows,
+};
+use crate::{Error, StatusCode};
+
+/// A browser feature controlled by `Permissions-Policy`.
+///
+/// [MDN | Features](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Feature-Policy#directives)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Set feature `accelerometer`
+    Accelerometer,
+    /// Set feature `ambient-light-sensor`
+    AmbientLightSensor,
+    /// Set feature `autoplay`
+    Autoplay,
+    /// Set feature `battery`
+    Battery,
+    /// Set feature `camera`
+    Camera,
+    /// Set feature `display-capture`
+    DisplayCapture,
+    /// Set feature `document-domain`
+    DocumentDomain,
+    /// Set feature `encrypted-media`
+    EncryptedMedia,
+    /// Set feature `fullscreen`
+    Fullscreen,
+    /// Set feature `geolocation`
+    Geolocation,
+    /// Set feature `gyroscope`
+    Gyroscope,
+    /// Set feature `interest-cohort`
+    InterestCohort,
+    /// Set feature `magnetometer`
+    Magnetometer,
+    /// Set feature `microphone`
+    Microphone,
+    /// Set feature `midi`
+    Midi,
+    /// Set feature `payment`
+    Payment,
+    /// Set feature `picture-in-picture`
+    PictureInPicture,
+    /// Set feature `publickey-credentials-get`
+    PublicKeyCredentialsGet,
+    /// Set feature `screen-wake-lock`
+    ScreenWakeLock,
+    /// Set feature `sync-xhr`
+    SyncXhr,
+    /// Set feature `usb`
+    Usb,
+    /// Set feature `web-share`
+    WebShare,
+    /// Set feature `xr-spatial-tracking`
+    XrSpatialTracking,
+    /// Any other feature, by its lowercase name.
+    Custom(String),
+}
+
+impl Feature {
+    fn as_str(&self) -> &str {
+        match self {
+            Feature::Accelerometer => "accelerometer",
+            Feature::AmbientLightSensor => "ambient-light-sensor",
+            Feature::Autoplay => "autoplay",
+            Feature::Battery => "battery",
+            Feature::Camera => "camera",
+            Feature::DisplayCapture => "display-capture",
+            Feature::DocumentDomain => "document-domain",
+            Feature::EncryptedMedia => "encrypted-media",
+            Feature::Fullscreen => "fullscreen",
+            Feature::Geolocation => "geolocation",
+            Feature::Gyroscope => "gyroscope",
+            Feature::InterestCohort => "interest-cohort",
+            Feature::Magnetometer => "magnetometer",
+            Feature::Microphone => "microphone",
+            Feature::Midi => "midi",
+            Feature::Payment => "payment",
+            Feature::PictureInPicture => "picture-in-picture",
+            Feature::PublicKeyCredentialsGet => "publickey-credentials-get",
+            Feature::ScreenWakeLock => "screen-wake-lock",
+            Feature::SyncXhr => "sync-xhr",
+            Feature::Usb => "usb",
+            Feature::WebShare => "web-share",
+            Feature::XrSpatialTracking => "xr-spatial-tracking",
+            Feature::Custom(name) => name,
+        }
+    }
+}
+
+impl Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Feature {
+    type Err = Error;
+
+    /// Parse a feature name, which must be a Structured Fields key.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !is_sf_key(s) {
+            return Err(Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Permissions-Policy feature", s),
+            ));
+        }
+        let feature = match s {
+            "accelerometer" => Feature::Accelerometer,
+            "ambient-light-sensor" => Feature::AmbientLightSensor,
+            "autoplay" => Feature::Autoplay,
+            "battery" => Feature::Battery,
+            "camera" => Feature::Camera,
+            "display-capture" => Feature::DisplayCapture,
+            "document-domain" => Feature::DocumentDomain,
+            "encrypted-media" => Feature::EncryptedMedia,
+            "fullscreen" => Feature::Fullscreen,
+            "geolocation" => Feature::Geolocation,
+            "gyroscope" => Feature::Gyroscope,
+            "interest-cohort" => Feature::InterestCohort,
+            "magnetometer" => Feature::Magnetometer,
+            "microphone" => Feature::Microphone,
+            "midi" => Feature::Midi,
+            "payment" => Feature::Payment,
+            "picture-in-picture" => Feature::PictureInPicture,
+            "publickey-credentials-get" => Feature::PublicKeyCredentialsGet,
+            "screen-wake-lock" => Feature::ScreenWakeLock,
+            "sync-xhr" => Feature::SyncXhr,
+            "usb" => Feature::Usb,
+            "web-share" => Feature::WebShare,
+            "xr-spatial-tracking" => Feature::XrSpatialTracking,
+            s => Feature::Custom(s.to_string()),
+        };
+        Ok(feature)
+    }
+}
+
+/// An entry of the allowlist of a feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Allowlist {
+    /// Set allowlist `self`, the origin of the document
+    SameOrigin,
+    /// Set allowlist `*`, any origin
+    Any,
+    /// Set an origin, such as `"https://example.com"`
+    Origin(String),
+}
+
+impl Display for Allowlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Allowlist::SameOrigin => write!(f, "self"),
+            Allowlist::Any => write!(f, "*"),
+            Allowlist::Origin(origin) => write!(f, "{}", fmt_quoted_string(origin)),
+        }
+    }
+}
+
+/// Build a `Permissions-Policy` header.
+///
+/// `Permissions-Policy` headers control which browser features the document,
+/// and the frames it embeds, may use. The policy is serialized as a
+/// Structured Fields dictionary, mapping each feature to its allowlist.
+/// Features which aren't in the policy keep the browser's default allowlist.
+///
+/// [Mozilla Developer Network](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Feature-Policy)
+///
+/// # Specifications
+///
+/// - [Permissions Policy (Working Draft)](https://w3c.github.io/webappsec-permissions-policy/#permissions-policy-http-header-field)
+/// - [RFC8941: Structured Field Values for HTTP](https://tools.ietf.org/html/rfc8941#section-3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::{Allowlist, Feature, PermissionsPolicy};
+/// use http_types::{Response, StatusCode};
+///
+/// let mut policy = PermissionsPolicy::new();
+/// policy
+///     .deny(Feature::Camera)
+///     .allow(Feature::Fullscreen, Allowlist::Any)
+///     .allow(Feature::Geolocation, Allowlist::SameOrigin)
+///     .allow(Feature::Geolocation, Allowlist::Origin("https://maps.example".into()));
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// policy.apply(&mut res);
+/// assert_eq!(
+///     res["Permissions-Policy"],
+///     r#"camera=(), fullscreen=*, geolocation=(self "https://maps.example")"#
+/// );
+///
+/// let policy = PermissionsPolicy::from_headers(res)?.unwrap();
+/// assert_eq!(policy.allowlist(&Feature::Camera), Some(&[][..]));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionsPolicy {
+    features: Vec<(Feature, Vec<Allowlist>)>,
+}
+
+impl PermissionsPolicy {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        Self { features: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Permissions-Policy` header values are combined into a single
+    /// dictionary.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(PERMISSIONS_POLICY) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        let values: Vec<_> = values.iter().map(|value| value.as_str()).collect();
+        Ok(Some(values.join(", ").parse()?))
+    }
+
+    /// Sets the `Permissions-Policy` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        PERMISSIONS_POLICY
+    }
+
+    /// Get the `HeaderValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an origin contains non-ASCII characters.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Permissions-Policy should be valid ASCII")
+    }
+
+    /// Add an entry to the allowlist of a feature.
+    pub fn allow(&mut self, feature: Feature, entry: Allowlist) -> &mut Self {
+        let allowlist = self.allowlist_mut(feature);
+        if !allowlist.contains(&entry) {
+            allowlist.push(entry);
+        }
+        self
+    }
+
+    /// Disable a feature, by setting its allowlist to be empty.
+    pub fn deny(&mut self, feature: Feature) -> &mut Self {
+        self.allowlist_mut(feature).clear();
+        self
+    }
+
+    /// Get the allowlist of a feature, if it's in the policy.
+    ///
+    /// An empty allowlist means the feature is disabled.
+    pub fn allowlist(&self, feature: &Feature) -> Option<&[Allowlist]> {
+        self.features
+            .iter()
+            .find(|(f, _)| f == feature)
+            .map(|(_, allowlist)| allowlist.as_slice())
+    }
+
+    fn allowlist_mut(&mut self, feature: Feature) -> &mut Vec<Allowlist> {
+        let index = match self.features.iter().position(|(f, _)| *f == feature) {
+            Some(index) => index,
+            None => {
+                self.features.push((feature, vec![]));
+                self.features.len() - 1
+            }
+        };
+        &mut self.features[index].1
+    }
+}
+
+impl Display for PermissionsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, (feature, allowlist)) in self.features.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}=", feature)?;
+            if let [Allowlist::Any] = allowlist.as_slice() {
+                write!(f, "*")?;
+                continue;
+            }
+            write!(f, "(")?;
+            for (n, entry) in allowlist.iter().enumerate() {
+                if n > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", entry)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PermissionsPolicy {
+    type Err = Error;
+
+    /// Parse a `Permissions-Policy` value.
+    ///
+    /// When a feature appears more than once, the last allowlist is used.
+    /// Parameters and unknown allowlist tokens are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Permissions-Policy", s),
+            )
+        };
+
+        let mut policy = Self::new();
+        for member in split_outside_quotes(s, ',') {
+            let eq = member.find('=').ok_or_else(invalid)?;
+            let feature: Feature = member[..eq].parse().map_err(|_| invalid())?;
+            let value = &member[eq + 1..];
+
+            let is_inner_list = value.starts_with('(');
+            let (items, params) = match value.strip_prefix('(') {
+                Some(rest) => {
+                    let end = find_outside_quotes(rest, ')').ok_or_else(invalid)?;
+                    (&rest[..end], &rest[end + 1..])
+                }
+                None => match find_outside_quotes(value, ';') {
+                    Some(end) => (&value[..end], &value[end..]),
+                    None => (value, ""),
+                },
+            };
+            if !params.is_empty() && !params.starts_with(';') {
+                return Err(invalid());
+            }
+
+            let mut allowlist = vec![];
+            let mut rest = trim_ows(items);
+            while !rest.is_empty() {
+                let (entry, remainder) = parse_item(rest).ok_or_else(invalid)?;
+                let separated =
+                    remainder.is_empty() || (is_inner_list && remainder.starts_with(' '));
+                if !separated {
+                    return Err(invalid());
+                }
+                if let Some(entry) = entry {
+                    if !allowlist.contains(&entry) {
+                        allowlist.push(entry);
+                    }
+                }
+                rest = remainder.trim_start_matches(' ');
+            }
+
+            *policy.allowlist_mut(feature) = allowlist;
+        }
+        Ok(policy)
+    }
+}
+
+/// Find a delimiter which isn't inside a quoted string.
+fn find_outside_quotes(s: &str, delimiter: char) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if in_quotes {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_quotes = false;
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Parse a single allowlist entry, which is either a token or a string.
+///
+/// Tokens other than `self` and `*` are skipped over, returning `None`.
+fn parse_item(s: &str) -> Option<(Option<Allowlist>, &str)> {
+    if s.starts_with('"') {
+        let (origin, rest) = parse_quoted_string(s);
+        return Some((Some(Allowlist::Origin(origin?.into_owned())), rest));
+    }
+
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, c)) if c.is_ascii_alphabetic() || c == '*' => {}
+        _ => return None,
+    }
+    let end = chars
+        .find(|(_, c)| !(is_tchar(*c) || *c == ':' || *c == '/'))
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| s.len());
+    let entry = match &s[..end] {
+        "self" => Some(Allowlist::SameOrigin),
+        "*" => Some(Allowlist::Any),
+        _ => None,
+    };
+    Some((entry, &s[end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let policy: PermissionsPolicy = "camera=(), geolocation=(self \"https://a.example\" src), \
+             payment=*, usb=self;report-to=main, x-custom=(\"https://b.example\");a=1, camera=*"
+            .parse()?;
+        assert_eq!(
+            policy.allowlist(&Feature::Camera),
+            Some(&[Allowlist::Any][..])
+        );
+        assert_eq!(
+            policy.allowlist(&Feature::Geolocation),
+            Some(
+                &[
+                    Allowlist::SameOrigin,
+                    Allowlist::Origin("https://a.example".into())
+                ][..]
+            )
+        );
+        assert_eq!(
+            policy.allowlist(&Feature::Payment),
+            Some(&[Allowlist::Any][..])
+        );
+        assert_eq!(
+            policy.allowlist(&Feature::Usb),
+            Some(&[Allowlist::SameOrigin][..])
+        );
+        assert_eq!(
+            policy.allowlist(&Feature::Custom("x-custom".into())),
+            Some(&[Allowlist::Origin("https://b.example".into())][..])
+        );
+        assert_eq!(policy.allowlist(&Feature::Midi), None);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        let mut policy = PermissionsPolicy::new();
+        policy
+            .allow(Feature::Microphone, Allowlist::SameOrigin)
+            .allow(Feature::Microphone, Allowlist::SameOrigin)
+            .allow(
+                Feature::Payment,
+                Allowlist::Origin("https://pay.example".into()),
+            )
+            .deny(Feature::InterestCohort)
+            .allow(Feature::Autoplay, Allowlist::Any)
+            .allow(Feature::Autoplay, Allowlist::SameOrigin);
+
+        let mut headers = Headers::new();
+        policy.apply(&mut headers);
+        assert_eq!(
+            headers["Permissions-Policy"],
+            "microphone=(self), payment=(\"https://pay.example\"), interest-cohort=(), \
+             autoplay=(* self)"
+        );
+        assert_eq!(PermissionsPolicy::from_headers(headers)?.unwrap(), policy);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "camera",
+            "Camera=()",
+            "camera=(self",
+            "camera=(self)x",
+            "camera=(\"a)",
+            "camera=(self,)",
+            "camera=1",
+            "camera=self *",
+        ] {
+            let err = s.parse::<PermissionsPolicy>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
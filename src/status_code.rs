@@ -20,6 +20,12 @@ pub enum StatusCode {
     /// client, and indicates the protocol the server is switching to.
     SwitchingProtocols = 101,
 
+    /// 102 Processing
+    ///
+    /// This interim response indicates that the server has received and is
+    /// processing the request, but no response is available yet.
+    Processing = 102,
+
     /// 103 Early Hints
     ///
     /// This status code is primarily intended to be used with the Link header,
@@ -476,6 +482,7 @@ impl StatusCode {
         match self {
             StatusCode::Continue => "Continue",
             StatusCode::SwitchingProtocols => "Switching Protocols",
+            StatusCode::Processing => "Processing",
             StatusCode::EarlyHints => "Early Hints",
             StatusCode::Ok => "OK",
             StatusCode::Created => "Created",
@@ -550,6 +557,7 @@ impl std::convert::TryFrom<u16> for StatusCode {
         match num {
             100 => Ok(StatusCode::Continue),
             101 => Ok(StatusCode::SwitchingProtocols),
+            102 => Ok(StatusCode::Processing),
             103 => Ok(StatusCode::EarlyHints),
             200 => Ok(StatusCode::Ok),
             201 => Ok(StatusCode::Created),
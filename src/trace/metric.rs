@@ -0,0 +1,220 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::parse_utils::{
+    fmt_token_or_quoted_string, is_printable_ascii, is_token, parse_param, split_outside_quotes,
+};
+use crate::{Error, StatusCode};
+
+/// A single metric of the `Server-Timing` header.
+///
+/// # Specifications
+///
+/// - [Server Timing (Working Draft)](https://w3c.github.io/server-timing/#the-server-timing-header-field)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::trace::Metric;
+/// use std::time::Duration;
+///
+/// let metric = Metric::new("db", Some(Duration::from_millis(53)), Some("Database lookup".into()))?;
+/// assert_eq!(metric.to_string(), r#"db;dur=53;desc="Database lookup""#);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metric {
+    name: String,
+    dur: Option<Duration>,
+    desc: Option<String>,
+}
+
+impl Metric {
+    /// Create a new instance of `Metric`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the name isn't a valid token, or if the
+    /// description contains characters which aren't printable ASCII.
+    pub fn new(
+        name: impl Into<String>,
+        dur: Option<Duration>,
+        desc: Option<String>,
+    ) -> crate::Result<Self> {
+        let name = name.into();
+        if !is_token(&name) {
+            return Err(Error::from_str(
+                StatusCode::InternalServerError,
+                format!("`{}` is not a valid Server-Timing metric name", name),
+            ));
+        }
+        if let Some(desc) = desc.as_deref().filter(|desc| !is_printable_ascii(desc)) {
+            return Err(Error::from_str(
+                StatusCode::InternalServerError,
+                format!("`{:?}` is not a valid Server-Timing description", desc),
+            ));
+        }
+        Ok(Self { name, dur, desc })
+    }
+
+    /// Get the name of the metric.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the time spent on the metric, if any.
+    pub fn duration(&self) -> Option<Duration> {
+        self.dur
+    }
+
+    /// Set the time spent on the metric.
+    pub fn set_duration(&mut self, dur: Duration) {
+        self.dur = Some(dur);
+    }
+
+    /// Get the human-readable description of the metric, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.desc.as_deref()
+    }
+
+    /// Set the human-readable description of the metric.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the description contains characters which aren't printable
+    /// ASCII, such as control characters.
+    pub fn set_description(&mut self, desc: impl Into<String>) {
+        let desc = desc.into();
+        assert!(
+            is_printable_ascii(&desc),
+            "Server-Timing descriptions must be printable ASCII"
+        );
+        self.desc = Some(desc);
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(dur) = self.dur {
+            // Durations are sent in milliseconds, with microsecond precision.
+            let micros = dur.as_micros();
+            write!(f, ";dur={}", micros / 1000)?;
+            if micros % 1000 != 0 {
+                let fraction = format!("{:03}", micros % 1000);
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+        }
+        if let Some(desc) = &self.desc {
+            write!(f, ";desc={}", fmt_token_or_quoted_string(desc))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Metric {
+    type Err = Error;
+
+    /// Parse a single metric.
+    ///
+    /// Only the first occurrence of `dur` and `desc` is used, and unknown
+    /// parameters are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::from_str(
+                StatusCode::BadRequest,
+                format!("`{}` is not a valid Server-Timing metric", s),
+            )
+        };
+
+        let mut parts = split_outside_quotes(s, ';').into_iter();
+        let name = parts
+            .next()
+            .filter(|name| is_token(name))
+            .ok_or_else(invalid)?;
+        let mut metric = Self {
+            name: name.to_string(),
+            dur: None,
+            desc: None,
+        };
+
+        for param in parts {
+            let (name, value) = parse_param(param).ok_or_else(invalid)?;
+            if name.eq_ignore_ascii_case("dur") && metric.dur.is_none() {
+                let millis: f64 = value.parse().map_err(|_| invalid())?;
+                if !millis.is_finite() || millis < 0.0 {
+                    return Err(invalid());
+                }
+                metric.dur = Some(Duration::from_micros((millis * 1000.0).round() as u64));
+            } else if name.eq_ignore_ascii_case("desc") && metric.desc.is_none() {
+                metric.desc = Some(value);
+            }
+        }
+        Ok(metric)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let metric: Metric = "cache; DESC=\"Cache Read\"; dur=23.2; dur=1; x=y".parse()?;
+        assert_eq!(metric.name(), "cache");
+        assert_eq!(metric.duration(), Some(Duration::from_micros(23_200)));
+        assert_eq!(metric.description(), Some("Cache Read"));
+
+        let metric: Metric = "miss".parse()?;
+        assert_eq!(metric.duration(), None);
+        assert_eq!(metric.description(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> crate::Result<()> {
+        let metric = Metric::new(
+            "app",
+            Some(Duration::from_micros(47_500)),
+            Some("a \"b\"".into()),
+        )?;
+        let s = metric.to_string();
+        assert_eq!(s, r#"app;dur=47.5;desc="a \"b\"""#);
+        assert_eq!(s.parse::<Metric>()?, metric);
+
+        let metric = Metric::new("total", None, Some("Total".into()))?;
+        assert_eq!(metric.to_string(), "total;desc=Total");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_names() {
+        assert!(Metric::new("a b", None, None).is_err());
+        assert!(Metric::new("", None, None).is_err());
+    }
+
+    #[test]
+    fn invalid_descriptions() {
+        assert!(Metric::new("db", None, Some("a\r\nSet-Cookie: b".into())).is_err());
+        assert!(Metric::new("db", None, Some("café".into())).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Server-Timing descriptions must be printable ASCII")]
+    fn set_description_rejects_control_characters() {
+        let mut metric = Metric::new("db", None, None).unwrap();
+        metric.set_description("a\nb");
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "a b", "db;dur=fast", "db;dur=-1", "db;desc=\"a"] {
+            let err = s.parse::<Metric>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
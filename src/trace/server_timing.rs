@@ -0,0 +1,255 @@
+use std::fmt::{self, Display};
+use std::slice;
+use std::str::FromStr;
+
+use crate::headers::{HeaderName, HeaderValue, Headers, SERVER_TIMING};
+use crate::parse_utils::split_list;
+use crate::trace::Metric;
+
+/// Metrics of the time spent processing a request, for the `Server-Timing`
+/// header.
+///
+/// Browsers show these metrics in their developer tools, next to the timings
+/// of the request itself. Metrics which are only known once the body has been
+/// sent can be sent as a trailer instead, by declaring `Server-Timing` in the
+/// `Trailer` header and calling `append` on the `Trailers`.
+///
+/// # Specifications
+///
+/// - [Server Timing (Working Draft)](https://w3c.github.io/server-timing/#the-server-timing-header-field)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::trace::{Metric, ServerTiming};
+/// use http_types::{Response, Trailers};
+/// use std::time::Duration;
+///
+/// let mut timings = ServerTiming::new();
+/// timings.push(Metric::new("db", Some(Duration::from_millis(53)), None)?);
+///
+/// let mut res = Response::new(200);
+/// timings.apply(&mut res);
+/// res.insert_header("Trailer", "Server-Timing");
+///
+/// let mut timings = ServerTiming::new();
+/// timings.push(Metric::new("render", Some(Duration::from_micros(1_500)), None)?);
+///
+/// let mut trailers = Trailers::new();
+/// timings.append(&mut trailers);
+/// assert_eq!(trailers["Server-Timing"], "render;dur=1.5");
+///
+/// let timings = ServerTiming::from_headers(res)?.unwrap();
+/// assert_eq!(timings.iter().next().unwrap().name(), "db");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerTiming {
+    timings: Vec<Metric>,
+}
+
+impl ServerTiming {
+    /// Create a new instance of `ServerTiming`.
+    pub fn new() -> Self {
+        Self { timings: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// All `Server-Timing` header values are combined.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let values = match headers.as_ref().get(SERVER_TIMING) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let mut timings = Self::new();
+        for value in values {
+            for metric in split_list(value.as_str()) {
+                timings.push(metric.parse()?);
+            }
+        }
+        Ok(Some(timings))
+    }
+
+    /// Sets the `Server-Timing` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Appends to the `Server-Timing` header, keeping the metrics which were
+    /// sent before.
+    ///
+    /// This is useful to add metrics to headers or `Trailers` in several
+    /// places.
+    pub fn append(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().append(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        SERVER_TIMING
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+        HeaderValue::from_str(&output).expect("Server-Timing should be valid ASCII")
+    }
+
+    /// Push a metric into the list.
+    pub fn push(&mut self, metric: Metric) {
+        self.timings.push(metric);
+    }
+
+    /// Returns `true` if there are no metrics.
+    pub fn is_empty(&self) -> bool {
+        self.timings.is_empty()
+    }
+
+    /// An iterator visiting all metrics.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.timings.iter(),
+        }
+    }
+
+    /// An iterator visiting all metrics, with mutable references.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            inner: self.timings.iter_mut(),
+        }
+    }
+}
+
+impl Display for ServerTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, metric) in self.timings.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", metric)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for ServerTiming {
+    type Item = Metric;
+    type IntoIter = std::vec::IntoIter<Metric>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.timings.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ServerTiming {
+    type Item = &'a Metric;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ServerTiming {
+    type Item = &'a mut Metric;
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// A borrowing iterator over entries in `ServerTiming`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, Metric>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Metric;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A mutable iterator over entries in `ServerTiming`.
+#[derive(Debug)]
+pub struct IterMut<'a> {
+    inner: slice::IterMut<'a, Metric>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = &'a mut Metric;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Trailers;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append("Server-Timing", "miss, db;dur=53, app;dur=47.2");
+        headers.append("Server-Timing", "cpu;desc=\"a, b\"");
+
+        let timings = ServerTiming::from_headers(&headers)?.unwrap();
+        let names: Vec<_> = timings.iter().map(|metric| metric.name()).collect();
+        assert_eq!(names, vec!["miss", "db", "app", "cpu"]);
+        assert_eq!(
+            timings.iter().nth(1).unwrap().duration(),
+            Some(Duration::from_millis(53))
+        );
+        assert_eq!(timings.iter().nth(3).unwrap().description(), Some("a, b"));
+
+        let mut headers = Headers::new();
+        timings.apply(&mut headers);
+        assert_eq!(ServerTiming::from_headers(headers)?.unwrap(), timings);
+        Ok(())
+    }
+
+    #[test]
+    fn append_to_trailers() -> crate::Result<()> {
+        let mut trailers = Trailers::new();
+        let mut timings = ServerTiming::new();
+        timings.push(Metric::new("db", Some(Duration::from_millis(2)), None)?);
+        timings.append(&mut trailers);
+        timings.append(&mut trailers);
+
+        let timings = ServerTiming::from_headers(&trailers)?.unwrap();
+        assert_eq!(timings.into_iter().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert("Server-Timing", "db;dur=1, app;dur=slow");
+        let err = ServerTiming::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
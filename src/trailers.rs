@@ -177,6 +177,18 @@ impl DerefMut for Trailers {
     }
 }
 
+impl AsRef<Headers> for Trailers {
+    fn as_ref(&self) -> &Headers {
+        &self.headers
+    }
+}
+
+impl AsMut<Headers> for Trailers {
+    fn as_mut(&mut self) -> &mut Headers {
+        &mut self.headers
+    }
+}
+
 impl Index<HeaderName> for Trailers {
     type Output = HeaderValues;
 
@@ -20,3 +20,11 @@ fn security_test() {
 
     assert_eq!(res["content-security-policy"], "base-uri 'none'; default-src 'self' areweasyncyet.rs; object-src 'none'; script-src 'self' 'unsafe-inline'; upgrade-insecure-requests");
 }
+
+#[test]
+#[allow(deprecated)]
+fn hsts_test() {
+    let mut res = Response::new(StatusCode::Ok);
+    security::hsts(&mut res);
+    assert_eq!(res["strict-transport-security"], "max-age=5184000");
+}